@@ -0,0 +1,12 @@
+-- Add up migration script here
+create table if not exists "tokens"
+(
+    id uuid primary key default gen_random_uuid(),
+    user_id uuid not null references "users" (id) on delete cascade,
+    token_hash text unique not null,
+    created_at timestamptz not null default now(),
+    expires_at timestamptz not null,
+    revoked_at timestamptz
+);
+
+create index if not exists "tokens_user_id_idx" on "tokens" (user_id);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+drop table if exists "profiles";
+drop table if exists "identities";
\ No newline at end of file
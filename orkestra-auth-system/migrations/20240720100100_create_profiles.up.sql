@@ -0,0 +1,27 @@
+-- Add up migration script here
+create table if not exists "identities"
+(
+    id uuid primary key default gen_random_uuid(),
+    user_id uuid not null references "users" (id) on delete cascade,
+    provider text not null,
+    provider_uid text not null,
+    created_at timestamptz not null default now(),
+    unique (provider, provider_uid)
+);
+
+create index if not exists "identities_user_id_idx" on "identities" (user_id);
+
+create table if not exists "profiles"
+(
+    user_id uuid primary key references "users" (id) on delete cascade,
+    display_name text not null,
+    avatar_url text,
+    locale text,
+    country text,
+    custom jsonb not null default '{}'::jsonb,
+    updated_at timestamptz not null default now()
+);
+
+insert into "profiles" (user_id, display_name)
+select id, username from "users"
+on conflict do nothing;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+drop table if exists "username_history";
+alter table "users" drop column if exists username_changed_at;
\ No newline at end of file
@@ -0,0 +1,14 @@
+-- Add up migration script here
+alter table "users" add column if not exists username_changed_at timestamptz;
+
+create table if not exists "username_history"
+(
+    id uuid primary key default gen_random_uuid(),
+    user_id uuid not null references "users" (id) on delete cascade,
+    old_username text not null,
+    new_username text not null,
+    changed_at timestamptz not null default now()
+);
+
+create index if not exists "username_history_user_id_idx" on "username_history" (user_id);
+create index if not exists "username_history_old_username_idx" on "username_history" (lower(old_username));
@@ -0,0 +1,5 @@
+-- Add down migration script here
+-- Renamed accounts keep their new usernames, see "username_history"
+alter table "users" drop constraint if exists "users_username_length";
+drop index if exists "users_username_lower_idx";
+alter table "users" add constraint "users_username_key" unique (username);
@@ -0,0 +1,71 @@
+-- Add up migration script here
+
+-- Usernames become unique regardless of case and limited to 1..32 characters.
+--
+-- Rows violating the new rules are renamed before the constraints are added:
+--   * empty or overlong usernames become `user_<first 8 hex chars of id>`;
+--   * within each group of usernames that differ only by case, the account
+--     whose username is already lower case is kept (the smallest id
+--     otherwise), the rest become `<username>_<first 8 hex chars of id>`.
+-- A new username that is already taken regardless of case gets another
+-- 8 hex chars derived from the id instead.
+-- Every rename is recorded in "username_history" and resets
+-- username_changed_at, so affected players can pick a new name right away.
+--
+-- To preview the affected accounts before deploying, run:
+--   select lower(username), array_agg(username) from "users"
+--   group by lower(username) having count(*) > 1;
+--   select id, username from "users" where char_length(username) not between 1 and 32;
+
+-- First `prefix || '_' || <8 hex chars>` nobody has regardless of case, the
+-- chars are taken from the id first and from hashes of it after that
+create or replace function pg_temp.free_username(prefix text, id uuid) returns text as $$
+declare
+    hex text := replace(id::text, '-', '');
+    candidate text;
+    attempt int := 0;
+begin
+    loop
+        candidate := prefix || '_' || case
+            when attempt = 0 then left(hex, 8)
+            else left(md5(hex || attempt), 8)
+        end;
+
+        exit when not exists (select 1 from "users" where lower(username) = lower(candidate));
+
+        attempt := attempt + 1;
+    end loop;
+
+    return candidate;
+end
+$$ language plpgsql;
+
+-- Renamed one at a time, so each rename sees the ones before it
+create or replace function pg_temp.rename_user(user_id uuid, new_username text) returns void as $$
+    insert into "username_history" (user_id, old_username, new_username)
+    select id, username, new_username from "users" where id = user_id;
+
+    update "users" set username = new_username, username_changed_at = null where id = user_id;
+$$ language sql;
+
+select pg_temp.rename_user(id, pg_temp.free_username('user', id))
+from "users"
+where char_length(username) not between 1 and 32
+order by id;
+
+with ranked as (
+    select id, username,
+           row_number() over (
+               partition by lower(username)
+               order by (username = lower(username)) desc, id
+           ) as rank
+    from "users"
+)
+select pg_temp.rename_user(id, pg_temp.free_username(left(username, 23), id))
+from ranked
+where rank > 1
+order by id;
+
+alter table "users" drop constraint if exists "users_username_key";
+create unique index if not exists "users_username_lower_idx" on "users" (lower(username));
+alter table "users" add constraint "users_username_length" check (char_length(username) between 1 and 32);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+drop table if exists "audit_events";
+drop index if exists "users_deleted_at_idx";
+alter table "users" drop column if exists deleted_at;
\ No newline at end of file
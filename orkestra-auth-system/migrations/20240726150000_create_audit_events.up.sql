@@ -0,0 +1,15 @@
+-- Add up migration script here
+alter table "users" add column if not exists deleted_at timestamptz;
+
+create index if not exists "users_deleted_at_idx" on "users" (deleted_at) where deleted_at is not null;
+
+create table if not exists "audit_events"
+(
+    id uuid primary key default gen_random_uuid(),
+    user_id uuid references "users" (id) on delete set null,
+    event text not null,
+    details jsonb not null default '{}'::jsonb,
+    created_at timestamptz not null default now()
+);
+
+create index if not exists "audit_events_user_id_idx" on "audit_events" (user_id);
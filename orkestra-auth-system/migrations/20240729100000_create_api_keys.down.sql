@@ -0,0 +1,4 @@
+-- Add down migration script here
+drop table if exists "api_keys";
+alter table "users" drop constraint if exists "users_role_check";
+alter table "users" drop column if exists role;
\ No newline at end of file
@@ -0,0 +1,16 @@
+-- Add up migration script here
+alter table "users" add column if not exists role text not null default 'player';
+alter table "users" add constraint "users_role_check" check (role in ('player', 'moderator', 'admin'));
+
+create table if not exists "api_keys"
+(
+    id uuid primary key default gen_random_uuid(),
+    name text not null,
+    key_prefix text not null,
+    key_hash text unique not null,
+    scopes text[] not null default '{}',
+    created_by uuid references "users" (id) on delete set null,
+    created_at timestamptz not null default now(),
+    last_used_at timestamptz,
+    revoked_at timestamptz
+);
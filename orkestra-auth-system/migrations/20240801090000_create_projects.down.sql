@@ -0,0 +1,13 @@
+-- Add down migration script here
+alter table "tokens" drop column if exists project_id;
+
+alter table "identities" drop constraint if exists "identities_project_provider_uid_key";
+alter table "identities" drop column if exists project_id;
+alter table "identities" add constraint "identities_provider_provider_uid_key"
+    unique (provider, provider_uid);
+
+drop index if exists "users_project_username_lower_idx";
+alter table "users" drop column if exists project_id;
+create unique index if not exists "users_username_lower_idx" on "users" (lower(username));
+
+drop table if exists "projects";
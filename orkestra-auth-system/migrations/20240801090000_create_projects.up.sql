@@ -0,0 +1,42 @@
+-- Add up migration script here
+
+-- Users, identities and tokens become scoped to a game project. Existing rows
+-- are moved to the "default" project, whose VK credentials are taken from
+-- VK_GAME_ID / VK_GAS_SECRET on startup when those are set.
+
+create table if not exists "projects"
+(
+    id uuid primary key default gen_random_uuid(),
+    slug text unique not null,
+    name text not null,
+    vk_game_id text,
+    vk_gas_secret text,
+    created_at timestamptz not null default now(),
+    constraint "projects_slug_format" check (slug ~ '^[a-z0-9][a-z0-9-]{1,31}$')
+);
+
+insert into "projects" (slug, name) values ('default', 'Default')
+on conflict do nothing;
+
+alter table "users" add column if not exists project_id uuid references "projects" (id);
+update "users" set project_id = (select id from "projects" where slug = 'default')
+where project_id is null;
+alter table "users" alter column project_id set not null;
+
+drop index if exists "users_username_lower_idx";
+create unique index if not exists "users_project_username_lower_idx"
+    on "users" (project_id, lower(username));
+
+alter table "identities" add column if not exists project_id uuid references "projects" (id);
+update "identities" i set project_id = u.project_id from "users" u
+where u.id = i.user_id and i.project_id is null;
+alter table "identities" alter column project_id set not null;
+
+alter table "identities" drop constraint if exists "identities_provider_provider_uid_key";
+alter table "identities" add constraint "identities_project_provider_uid_key"
+    unique (project_id, provider, provider_uid);
+
+alter table "tokens" add column if not exists project_id uuid references "projects" (id);
+update "tokens" t set project_id = u.project_id from "users" u
+where u.id = t.user_id and t.project_id is null;
+alter table "tokens" alter column project_id set not null;
@@ -0,0 +1,4 @@
+-- Add down migration script here
+drop table if exists "blocks";
+drop table if exists "friendships";
+drop table if exists "friend_requests";
@@ -0,0 +1,37 @@
+-- Add up migration script here
+create table if not exists "friend_requests"
+(
+    id uuid primary key default gen_random_uuid(),
+    sender_id uuid not null references "users" (id) on delete cascade,
+    receiver_id uuid not null references "users" (id) on delete cascade,
+    created_at timestamptz not null default now(),
+    unique (sender_id, receiver_id),
+    check (sender_id <> receiver_id)
+);
+
+create index if not exists "friend_requests_receiver_id_idx" on "friend_requests" (receiver_id, created_at desc);
+create index if not exists "friend_requests_sender_id_idx" on "friend_requests" (sender_id, created_at desc);
+
+-- Every friendship is stored in both directions, so listing is a single index scan
+create table if not exists "friendships"
+(
+    user_id uuid not null references "users" (id) on delete cascade,
+    friend_id uuid not null references "users" (id) on delete cascade,
+    created_at timestamptz not null default now(),
+    primary key (user_id, friend_id),
+    check (user_id <> friend_id)
+);
+
+create index if not exists "friendships_user_id_created_at_idx" on "friendships" (user_id, created_at desc);
+
+create table if not exists "blocks"
+(
+    user_id uuid not null references "users" (id) on delete cascade,
+    blocked_id uuid not null references "users" (id) on delete cascade,
+    created_at timestamptz not null default now(),
+    primary key (user_id, blocked_id),
+    check (user_id <> blocked_id)
+);
+
+create index if not exists "blocks_user_id_created_at_idx" on "blocks" (user_id, created_at desc);
+create index if not exists "blocks_blocked_id_idx" on "blocks" (blocked_id);
@@ -0,0 +1,14 @@
+-- Add up migration script here
+create table if not exists "saves"
+(
+    user_id uuid not null references "users" (id) on delete cascade,
+    key text not null,
+    value jsonb not null,
+    size_bytes integer not null,
+    version bigint not null default 1,
+    visibility text not null default 'private',
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    primary key (user_id, key),
+    constraint "saves_visibility_check" check (visibility in ('private', 'server'))
+);
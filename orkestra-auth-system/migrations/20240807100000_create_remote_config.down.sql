@@ -0,0 +1,3 @@
+-- Add down migration script here
+drop table if exists "remote_config_active";
+drop table if exists "remote_config_versions";
@@ -0,0 +1,20 @@
+-- Add up migration script here
+create table if not exists "remote_config_versions"
+(
+    project_id uuid not null references "projects" (id) on delete cascade,
+    version integer not null,
+    document jsonb not null,
+    comment text,
+    created_by uuid references "users" (id) on delete set null,
+    created_at timestamptz not null default now(),
+    primary key (project_id, version)
+);
+
+-- Version served to clients, publishing and rolling back only move this pointer
+create table if not exists "remote_config_active"
+(
+    project_id uuid primary key references "projects" (id) on delete cascade,
+    version integer not null,
+    activated_at timestamptz not null default now(),
+    foreign key (project_id, version) references "remote_config_versions" (project_id, version)
+);
@@ -0,0 +1,10 @@
+-- Add up migration script here
+create table if not exists "client_versions"
+(
+    project_id uuid not null references "projects" (id) on delete cascade,
+    platform text not null,
+    min_version text not null,
+    latest_version text not null,
+    updated_at timestamptz not null default now(),
+    primary key (project_id, platform)
+);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+drop table if exists "bans";
+drop table if exists "reports";
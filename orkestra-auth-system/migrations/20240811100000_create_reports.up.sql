@@ -0,0 +1,40 @@
+-- Add up migration script here
+create table if not exists "reports"
+(
+    id uuid primary key default gen_random_uuid(),
+    project_id uuid not null references "projects" (id) on delete cascade,
+    reporter_id uuid not null references "users" (id) on delete cascade,
+    target_id uuid not null references "users" (id) on delete cascade,
+    session_id text,
+    category text not null check (category in ('cheating', 'abuse', 'spam', 'inappropriate_name', 'other')),
+    text text not null default '',
+    status text not null default 'open' check (status in ('open', 'in_review', 'actioned', 'dismissed')),
+    resolution_note text,
+    handled_by uuid references "users" (id) on delete set null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    resolved_at timestamptz,
+    check (reporter_id <> target_id)
+);
+
+create index if not exists "reports_project_status_created_at_idx" on "reports" (project_id, status, created_at);
+create index if not exists "reports_target_id_idx" on "reports" (target_id);
+
+-- A player can't pile up reports against the same player while one is still unresolved
+create unique index if not exists "reports_unresolved_pair_idx" on "reports" (reporter_id, target_id)
+    where status in ('open', 'in_review');
+
+create table if not exists "bans"
+(
+    id uuid primary key default gen_random_uuid(),
+    project_id uuid not null references "projects" (id) on delete cascade,
+    user_id uuid not null references "users" (id) on delete cascade,
+    report_id uuid references "reports" (id) on delete set null,
+    reason text not null,
+    issued_by uuid references "users" (id) on delete set null,
+    created_at timestamptz not null default now(),
+    -- Null for permanent bans
+    expires_at timestamptz
+);
+
+create index if not exists "bans_user_id_created_at_idx" on "bans" (user_id, created_at desc);
@@ -0,0 +1,15 @@
+-- Add up migration script here
+create table if not exists "notifications"
+(
+    id uuid primary key default gen_random_uuid(),
+    user_id uuid not null references "users" (id) on delete cascade,
+    kind text not null,
+    payload jsonb not null default '{}'::jsonb,
+    -- Service that posted the notification, null for the auth system itself
+    sender text,
+    created_at timestamptz not null default now(),
+    read_at timestamptz
+);
+
+create index if not exists "notifications_user_id_created_at_idx" on "notifications" (user_id, created_at desc);
+create index if not exists "notifications_user_id_unread_idx" on "notifications" (user_id) where read_at is null;
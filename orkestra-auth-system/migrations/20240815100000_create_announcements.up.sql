@@ -0,0 +1,22 @@
+-- Add up migration script here
+create table if not exists "announcements"
+(
+    id uuid primary key default gen_random_uuid(),
+    project_id uuid not null references "projects" (id) on delete cascade,
+    priority integer not null default 0,
+    starts_at timestamptz not null,
+    ends_at timestamptz not null,
+    -- Null shows the announcement to every locale
+    locales text[],
+    min_client_version text,
+    max_client_version text,
+    default_locale text not null,
+    -- Locale to {"title", "body"}
+    texts jsonb not null,
+    created_by uuid references "users" (id) on delete set null,
+    created_at timestamptz not null default now(),
+    updated_at timestamptz not null default now(),
+    check (ends_at > starts_at)
+);
+
+create index if not exists "announcements_project_window_idx" on "announcements" (project_id, ends_at, starts_at);
@@ -0,0 +1,16 @@
+-- Add up migration script here
+create table if not exists "idempotency_keys"
+(
+    scope text not null,
+    key text not null,
+    request_hash text not null,
+    -- Null while the first request is still being handled
+    status_code integer,
+    content_type text,
+    response_body bytea,
+    created_at timestamptz not null default now(),
+    expires_at timestamptz not null,
+    primary key (scope, key)
+);
+
+create index if not exists "idempotency_keys_expires_at_idx" on "idempotency_keys" (expires_at);
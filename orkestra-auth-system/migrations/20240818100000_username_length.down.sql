@@ -0,0 +1,4 @@
+-- Add down migration script here
+-- Renamed accounts keep their new usernames, see "username_history"
+alter table "users" drop constraint if exists "users_username_length";
+alter table "users" add constraint "users_username_length" check (char_length(username) between 1 and 32);
@@ -0,0 +1,49 @@
+-- Add up migration script here
+
+-- Usernames are limited to 3..20 characters, the same as signup and rename
+-- enforce. Usernames outside of it become `user_<first 8 hex chars of id>`,
+-- or `user_` with another 8 hex chars derived from the id when that one is
+-- taken regardless of case. The rename is recorded in "username_history" and
+-- resets username_changed_at, so affected players can pick a new name right away.
+--
+-- To preview the affected accounts before deploying, run:
+--   select id, username from "users" where char_length(username) not between 3 and 20;
+
+-- First `user_<8 hex chars>` nobody has regardless of case, the chars are
+-- taken from the id first and from hashes of it after that
+create or replace function pg_temp.free_username(id uuid) returns text as $$
+declare
+    hex text := replace(id::text, '-', '');
+    candidate text;
+    attempt int := 0;
+begin
+    loop
+        candidate := 'user_' || case
+            when attempt = 0 then left(hex, 8)
+            else left(md5(hex || attempt), 8)
+        end;
+
+        exit when not exists (select 1 from "users" where lower(username) = lower(candidate));
+
+        attempt := attempt + 1;
+    end loop;
+
+    return candidate;
+end
+$$ language plpgsql;
+
+-- Renamed one at a time, so each rename sees the ones before it
+create or replace function pg_temp.rename_user(user_id uuid, new_username text) returns void as $$
+    insert into "username_history" (user_id, old_username, new_username)
+    select id, username, new_username from "users" where id = user_id;
+
+    update "users" set username = new_username, username_changed_at = null where id = user_id;
+$$ language sql;
+
+select pg_temp.rename_user(id, pg_temp.free_username(id))
+from "users"
+where char_length(username) not between 3 and 20
+order by id;
+
+alter table "users" drop constraint if exists "users_username_length";
+alter table "users" add constraint "users_username_length" check (char_length(username) between 3 and 20);
@@ -5,9 +5,12 @@ use shared::{
     config::AppConfig,
     context::Context,
     database::Database,
+    idempotency::IdempotencyPurger,
     integrations::vk::{api::VkService, router::vk_integration},
     logger::Logger,
+    project,
     router::v1,
+    services::{account_purger::AccountPurger, presence::PresenceStore},
 };
 use tracing::{info, info_span, Instrument};
 
@@ -25,11 +28,20 @@ async fn main() -> Result<()> {
 
     let database = Database::new(&config).in_current_span().await?;
     database.migrate().in_current_span().await?;
+    project::sync_default_credentials(&database, &config)
+        .in_current_span()
+        .await?;
 
-    let context = Context::new(database);
+    AccountPurger::new(&config, database.clone()).spawn();
+    IdempotencyPurger::new(&config, database.clone()).spawn();
 
-    let vk_service = VkService::new(&config.vk_game_id, &config.vk_gas_secret);
-    let vk_integration = vk_integration(vk_service);
+    let presence = PresenceStore::new(&config);
+    presence.spawn_sweeper();
+
+    let context = Context::new(&config, database, presence);
+
+    let vk_service = VkService::new();
+    let vk_integration = vk_integration(vk_service, context.clone());
 
     let v1 = v1(context);
 
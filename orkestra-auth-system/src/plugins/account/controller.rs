@@ -0,0 +1,76 @@
+use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::account::use_case,
+    shared::{
+        auth::extractor::AuthUser,
+        context::Context,
+        utils::{internal_error_json, not_found_json, ok, ok_json},
+    },
+};
+
+use super::error::AccountError;
+
+pub async fn export_data(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+) -> impl IntoResponse {
+    let span = info_span!("export_data");
+    let _guard = span.enter();
+
+    info!(event = "Request to export user data", user_id = %user.id);
+
+    let result = use_case::export_data(context.database(), user.id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(archive) => {
+            info!(event = "User data exported");
+
+            ok_json(archive)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn delete_account(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+) -> impl IntoResponse {
+    let span = info_span!("delete_account");
+    let _guard = span.enter();
+
+    info!(event = "Request to delete account", user_id = %user.id);
+
+    let result = use_case::delete_account(
+        context.database(),
+        context.account_deletion_grace(),
+        user.id,
+    )
+    .in_current_span()
+    .await;
+
+    match result {
+        Ok(response) => {
+            info!(event = "Account scheduled for deletion", purge_at = %response.purge_at);
+
+            ok(response)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: AccountError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        AccountError::UnknownUser => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        AccountError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+use time::OffsetDateTime;
+
+#[derive(Debug, Serialize)]
+pub struct DeleteAccountResponse {
+    #[serde(with = "time::serde::rfc3339")]
+    pub deleted_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub purge_at: OffsetDateTime,
+}
@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AccountError {
+    #[error("Unknown user")]
+    UnknownUser,
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,12 @@
+use axum::{
+    routing::{delete, get},
+    Router,
+};
+
+use super::controller::{delete_account, export_data};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/me", delete(delete_account))
+        .route("/me/export", get(export_data))
+}
@@ -0,0 +1,178 @@
+use sqlx::types::Uuid;
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+use tracing::{warn, Instrument};
+
+use crate::shared::{
+    audit::{self, AuditEvent},
+    database::Database,
+};
+
+use super::{dto::DeleteAccountResponse, error::AccountError};
+
+/// Every query yields a single JSON value with the user's rows from one table.
+/// Secrets (password and token hashes) are stripped.
+const EXPORT_SECTIONS: &[(&str, &str)] = &[
+    (
+        "user",
+        "SELECT (SELECT to_jsonb(u) - 'password' FROM users u WHERE u.id = $1);",
+    ),
+    (
+        "profile",
+        "SELECT (SELECT to_jsonb(p) FROM profiles p WHERE p.user_id = $1);",
+    ),
+    (
+        "identities",
+        "SELECT coalesce(jsonb_agg(to_jsonb(i) ORDER BY i.created_at), '[]'::jsonb) \
+        FROM identities i WHERE i.user_id = $1;",
+    ),
+    (
+        "username_history",
+        "SELECT coalesce(jsonb_agg(to_jsonb(h) ORDER BY h.changed_at), '[]'::jsonb) \
+        FROM username_history h WHERE h.user_id = $1;",
+    ),
+    (
+        "audit_events",
+        "SELECT coalesce(jsonb_agg(to_jsonb(a) ORDER BY a.created_at), '[]'::jsonb) \
+        FROM audit_events a WHERE a.user_id = $1;",
+    ),
+    (
+        "friends",
+        "SELECT coalesce(jsonb_agg(to_jsonb(f) ORDER BY f.created_at), '[]'::jsonb) \
+        FROM friendships f WHERE f.user_id = $1;",
+    ),
+    (
+        "friend_requests",
+        "SELECT coalesce(jsonb_agg(to_jsonb(r) ORDER BY r.created_at), '[]'::jsonb) \
+        FROM friend_requests r WHERE r.sender_id = $1 OR r.receiver_id = $1;",
+    ),
+    (
+        "blocks",
+        "SELECT coalesce(jsonb_agg(to_jsonb(b) ORDER BY b.created_at), '[]'::jsonb) \
+        FROM blocks b WHERE b.user_id = $1;",
+    ),
+    (
+        "saves",
+        "SELECT coalesce(jsonb_agg(to_jsonb(s) ORDER BY s.key), '[]'::jsonb) \
+        FROM saves s WHERE s.user_id = $1;",
+    ),
+    (
+        "reports",
+        "SELECT coalesce(jsonb_agg(to_jsonb(r) ORDER BY r.created_at), '[]'::jsonb) \
+        FROM reports r WHERE r.reporter_id = $1;",
+    ),
+    (
+        "bans",
+        "SELECT coalesce(jsonb_agg(to_jsonb(b) ORDER BY b.created_at), '[]'::jsonb) \
+        FROM bans b WHERE b.user_id = $1;",
+    ),
+    (
+        "notifications",
+        "SELECT coalesce(jsonb_agg(to_jsonb(n) ORDER BY n.created_at), '[]'::jsonb) \
+        FROM notifications n WHERE n.user_id = $1;",
+    ),
+    (
+        "sessions",
+        "SELECT coalesce(jsonb_agg(to_jsonb(t) - 'token_hash' ORDER BY t.created_at), '[]'::jsonb) \
+        FROM tokens t WHERE t.user_id = $1;",
+    ),
+];
+
+pub async fn export_data(database: &Database, id: Uuid) -> Result<serde_json::Value, AccountError> {
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    // All sections have to come from the same snapshot
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ READ ONLY;")
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    let mut archive = serde_json::Map::new();
+
+    let exported_at = OffsetDateTime::now_utc().format(&Rfc3339).ok();
+    archive.insert("exported_at".to_string(), serde_json::json!(exported_at));
+
+    for (section, query) in EXPORT_SECTIONS {
+        let value: Option<serde_json::Value> = sqlx::query_scalar(query)
+            .bind(id)
+            .fetch_one(&mut *transaction)
+            .in_current_span()
+            .await?;
+
+        archive.insert(section.to_string(), value.unwrap_or_default());
+    }
+
+    transaction.commit().in_current_span().await?;
+
+    if archive.get("user").map_or(true, serde_json::Value::is_null) {
+        return Err(AccountError::UnknownUser);
+    }
+
+    if let Err(err) = audit::record(
+        database.as_ref(),
+        id,
+        AuditEvent::DataExported,
+        serde_json::json!({}),
+    )
+    .in_current_span()
+    .await
+    {
+        warn!(event = "Couldn't record audit event", error = %err);
+    }
+
+    Ok(serde_json::Value::Object(archive))
+}
+
+/// Soft-deletes the account: it can't be used anymore, its tokens are revoked
+/// and audit rows are detached from it. The account purger removes the rest
+/// once the grace period is over.
+pub async fn delete_account(
+    database: &Database,
+    grace: Duration,
+    id: Uuid,
+) -> Result<DeleteAccountResponse, AccountError> {
+    const DELETE_QUERY: &str = "UPDATE users SET deleted_at = now() \
+        WHERE id = $1 AND deleted_at IS NULL RETURNING deleted_at;";
+    const REVOKE_TOKENS_QUERY: &str =
+        "UPDATE tokens SET revoked_at = now() WHERE user_id = $1 AND revoked_at IS NULL;";
+    const ANONYMIZE_AUDIT_QUERY: &str =
+        "UPDATE audit_events SET user_id = NULL, details = '{}'::jsonb WHERE user_id = $1;";
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    let Some(deleted_at): Option<OffsetDateTime> = sqlx::query_scalar(DELETE_QUERY)
+        .bind(id)
+        .fetch_optional(&mut *transaction)
+        .in_current_span()
+        .await?
+    else {
+        return Err(AccountError::UnknownUser);
+    };
+
+    sqlx::query(REVOKE_TOKENS_QUERY)
+        .bind(id)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    audit::record(
+        &mut *transaction,
+        id,
+        AuditEvent::DeletionRequested,
+        serde_json::json!({}),
+    )
+    .in_current_span()
+    .await?;
+
+    sqlx::query(ANONYMIZE_AUDIT_QUERY)
+        .bind(id)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(DeleteAccountResponse {
+        deleted_at,
+        purge_at: deleted_at + grace,
+    })
+}
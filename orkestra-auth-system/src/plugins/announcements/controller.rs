@@ -0,0 +1,242 @@
+use axum::{
+    extract::{Path, Query},
+    http::{
+        header::{ACCEPT_LANGUAGE, CACHE_CONTROL, ETAG, IF_NONE_MATCH, VARY},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::announcements::use_case,
+    shared::{
+        auth::extractor::AdminCaller,
+        client_version::ClientVersion,
+        context::Context,
+        pagination::PageQuery,
+        project::Project,
+        utils::{bad_request_json, created, internal_error_json, just_ok, not_found_json, ok},
+    },
+};
+
+use super::{
+    dto::{AnnouncementData, AnnouncementPath, LocaleQuery},
+    error::AnnouncementError,
+    models::{normalize_locale, valid_locale},
+};
+
+/// Announcements don't depend on the user, so shared caches may keep them for a minute
+const CACHE_CONTROL_VALUE: &str = "public, max-age=60";
+const VARY_VALUE: &str = "Accept-Language, X-Client-Version, X-Project";
+
+pub async fn client_announcements(
+    Extension(context): Extension<Context>,
+    project: Project,
+    client_version: Option<ClientVersion>,
+    Query(query): Query<LocaleQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let span = info_span!("client_announcements");
+    let _guard = span.enter();
+
+    let locale = query
+        .locale
+        .or_else(|| {
+            headers
+                .get(ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split([',', ';']).next())
+                .map(str::to_string)
+        })
+        .map(|locale| normalize_locale(&locale))
+        .filter(|locale| valid_locale(locale));
+
+    info!(
+        event = "Request announcements",
+        project = project.slug,
+        locale = ?locale,
+        client_version = ?client_version.as_ref().map(ToString::to_string),
+    );
+
+    let result = use_case::client_announcements(
+        context.database(),
+        project.id,
+        locale.as_deref(),
+        client_version.as_ref(),
+    )
+    .in_current_span()
+    .await;
+
+    let (announcements, etag) = match result {
+        Ok(result) => result,
+        Err(err) => return error_response(err).into_response(),
+    };
+
+    let cache_headers = [
+        (ETAG, etag.clone()),
+        (CACHE_CONTROL, CACHE_CONTROL_VALUE.to_string()),
+        (VARY, VARY_VALUE.to_string()),
+    ];
+
+    let not_modified = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        });
+
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    (
+        cache_headers,
+        ok(serde_json::json!({
+            "announcements": announcements
+        })),
+    )
+        .into_response()
+}
+
+pub async fn list_announcements(
+    Extension(context): Extension<Context>,
+    _admin: AdminCaller,
+    project: Project,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_announcements");
+    let _guard = span.enter();
+
+    info!(event = "Request announcements list", project = project.slug);
+
+    let result = use_case::list_announcements(context.database(), project.id, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(announcements) => ok(announcements),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn get_announcement(
+    Extension(context): Extension<Context>,
+    _admin: AdminCaller,
+    project: Project,
+    Path(AnnouncementPath { id }): Path<AnnouncementPath>,
+) -> impl IntoResponse {
+    let span = info_span!("get_announcement");
+    let _guard = span.enter();
+
+    info!(event = "Request announcement", announcement_id = %id);
+
+    let result = use_case::get_announcement(context.database(), project.id, id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(announcement) => ok(announcement),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn create_announcement(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    project: Project,
+    Json(request): Json<AnnouncementData>,
+) -> impl IntoResponse {
+    let span = info_span!("create_announcement");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to create announcement",
+        project = project.slug,
+        admin = admin.actor(),
+    );
+
+    let result =
+        use_case::create_announcement(context.database(), project.id, admin.user_id(), request)
+            .in_current_span()
+            .await;
+
+    match result {
+        Ok(announcement) => {
+            info!(event = "Announcement created", announcement_id = %announcement.id);
+
+            created(announcement)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn update_announcement(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    project: Project,
+    Path(AnnouncementPath { id }): Path<AnnouncementPath>,
+    Json(request): Json<AnnouncementData>,
+) -> impl IntoResponse {
+    let span = info_span!("update_announcement");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to update announcement",
+        announcement_id = %id,
+        admin = admin.actor(),
+    );
+
+    let result = use_case::update_announcement(context.database(), project.id, id, request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(announcement) => ok(announcement),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn delete_announcement(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    project: Project,
+    Path(AnnouncementPath { id }): Path<AnnouncementPath>,
+) -> impl IntoResponse {
+    let span = info_span!("delete_announcement");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to delete announcement",
+        announcement_id = %id,
+        admin = admin.actor(),
+    );
+
+    let result = use_case::delete_announcement(context.database(), project.id, id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => just_ok(),
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: AnnouncementError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        AnnouncementError::NotFound => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        AnnouncementError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        AnnouncementError::Invalid(_) => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
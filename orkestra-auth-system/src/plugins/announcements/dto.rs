@@ -0,0 +1,38 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+use crate::shared::client_version::ClientVersion;
+
+use super::models::AnnouncementText;
+
+#[derive(Debug, Deserialize)]
+pub struct AnnouncementPath {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LocaleQuery {
+    /// Takes precedence over `Accept-Language`
+    pub locale: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnnouncementData {
+    #[serde(default)]
+    pub priority: i32,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub starts_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub ends_at: OffsetDateTime,
+
+    pub locales: Option<Vec<String>>,
+    pub min_client_version: Option<ClientVersion>,
+    pub max_client_version: Option<ClientVersion>,
+    pub default_locale: String,
+    pub texts: BTreeMap<String, AnnouncementText>,
+}
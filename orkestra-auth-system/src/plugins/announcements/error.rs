@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AnnouncementError {
+    #[error("Announcement not found")]
+    NotFound,
+
+    #[error("Invalid announcement: {0}")]
+    Invalid(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,80 @@
+mod controller;
+mod dto;
+mod error;
+mod models;
+mod use_case;
+
+pub mod router;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use sqlx::types::{Json, Uuid};
+    use time::OffsetDateTime;
+
+    use super::models::{valid_locale, Announcement, AnnouncementText};
+
+    fn announcement(locales: Option<&[&str]>, min_version: Option<&str>) -> Announcement {
+        let text = |title: &str| AnnouncementText {
+            title: title.to_string(),
+            body: String::new(),
+        };
+
+        Announcement {
+            id: Uuid::from_u128(1),
+            priority: 0,
+            starts_at: OffsetDateTime::UNIX_EPOCH,
+            ends_at: OffsetDateTime::UNIX_EPOCH,
+            locales: locales.map(|l| l.iter().map(ToString::to_string).collect()),
+            min_client_version: min_version.map(str::to_string),
+            max_client_version: None,
+            default_locale: "en".to_string(),
+            texts: Json(BTreeMap::from([
+                ("en".to_string(), text("Maintenance")),
+                ("pt".to_string(), text("Manutenção")),
+                ("pt-br".to_string(), text("Manutenção BR")),
+            ])),
+            created_by: None,
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            updated_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn localization_falls_back_to_language_then_default() {
+        let announcement = announcement(None, None);
+        let title = |locale| announcement.localize(locale).unwrap().title;
+
+        assert_eq!(title(Some("pt-br")), "Manutenção BR");
+        assert_eq!(title(Some("pt-pt")), "Manutenção");
+        assert_eq!(title(Some("de")), "Maintenance");
+        assert_eq!(title(None), "Maintenance");
+    }
+
+    #[test]
+    fn targeting() {
+        let ru_only = announcement(Some(&["ru"]), None);
+        assert!(ru_only.targets(Some("ru-ru"), None));
+        assert!(!ru_only.targets(Some("en"), None));
+        assert!(!ru_only.targets(None, None));
+
+        let new_clients = announcement(None, Some("2.0"));
+        assert!(new_clients.targets(None, Some(&"2.0.1".parse().unwrap())));
+        assert!(!new_clients.targets(None, Some(&"1.9".parse().unwrap())));
+        assert!(!new_clients.targets(None, None));
+    }
+
+    #[test]
+    fn locale_validation() {
+        assert!(valid_locale("en"));
+        assert!(valid_locale("pt-br"));
+        assert!(valid_locale("zh-hant"));
+        assert!(valid_locale("es-419"));
+
+        assert!(!valid_locale("e"));
+        assert!(!valid_locale("EN"));
+        assert!(!valid_locale("en-"));
+        assert!(!valid_locale("en-us-x"));
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    types::{Json, Uuid},
+    FromRow,
+};
+use time::OffsetDateTime;
+
+use crate::shared::client_version::ClientVersion;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnnouncementText {
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct Announcement {
+    pub id: Uuid,
+    pub priority: i32,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub starts_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub ends_at: OffsetDateTime,
+
+    pub locales: Option<Vec<String>>,
+    pub min_client_version: Option<String>,
+    pub max_client_version: Option<String>,
+    pub default_locale: String,
+    pub texts: Json<BTreeMap<String, AnnouncementText>>,
+    pub created_by: Option<Uuid>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+/// Announcement as shown to a client, in the best matching locale
+#[derive(Debug, Serialize)]
+pub struct ClientAnnouncement {
+    pub id: Uuid,
+    pub priority: i32,
+    pub locale: String,
+    pub title: String,
+    pub body: String,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub starts_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub ends_at: OffsetDateTime,
+}
+
+impl Announcement {
+    /// Whether a client with this locale and version should see it. Version
+    /// bounds hide the announcement from clients not telling their version.
+    pub fn targets(&self, locale: Option<&str>, version: Option<&ClientVersion>) -> bool {
+        let locale_matches = self.locales.as_ref().map_or(true, |locales| {
+            locale.is_some_and(|locale| {
+                locales
+                    .iter()
+                    .any(|target| target == locale || target == language(locale))
+            })
+        });
+
+        let bound = |bound: &Option<String>, ok: fn(&ClientVersion, &ClientVersion) -> bool| {
+            bound.as_deref().map_or(true, |bound| {
+                match (version, bound.parse::<ClientVersion>()) {
+                    (Some(version), Ok(bound)) => ok(version, &bound),
+                    _ => false,
+                }
+            })
+        };
+
+        locale_matches
+            && bound(&self.min_client_version, |v, min| v >= min)
+            && bound(&self.max_client_version, |v, max| v <= max)
+    }
+
+    /// Picks the text for the locale, falling back to its language and then
+    /// to the default locale
+    pub fn localize(&self, locale: Option<&str>) -> Option<ClientAnnouncement> {
+        let candidates = locale
+            .into_iter()
+            .flat_map(|locale| [locale, language(locale)])
+            .chain([self.default_locale.as_str()]);
+
+        let (locale, text) = candidates
+            .into_iter()
+            .find_map(|locale| self.texts.get_key_value(locale))?;
+
+        Some(ClientAnnouncement {
+            id: self.id,
+            priority: self.priority,
+            locale: locale.clone(),
+            title: text.title.clone(),
+            body: text.body.clone(),
+            starts_at: self.starts_at,
+            ends_at: self.ends_at,
+        })
+    }
+}
+
+/// `pt-br` -> `pt`
+pub fn language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+/// Lower case with `-` separators, so `pt_BR` and `pt-br` are the same locale
+pub fn normalize_locale(locale: &str) -> String {
+    locale.trim().replace('_', "-").to_lowercase()
+}
+
+/// `en`, `pt-br`, `zh-hant`: a 2..3 letter language with an optional subtag
+pub fn valid_locale(locale: &str) -> bool {
+    let mut parts = locale.split('-');
+
+    let language_ok = parts
+        .next()
+        .is_some_and(|l| (2..=3).contains(&l.len()) && l.chars().all(|c| c.is_ascii_lowercase()));
+
+    let subtag_ok = match parts.next() {
+        None => true,
+        Some(subtag) => {
+            (2..=8).contains(&subtag.len())
+                && subtag
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        }
+    };
+
+    language_ok && subtag_ok && parts.next().is_none()
+}
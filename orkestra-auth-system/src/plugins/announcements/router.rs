@@ -0,0 +1,21 @@
+use axum::{routing::get, Router};
+
+use super::controller::{
+    client_announcements, create_announcement, delete_announcement, get_announcement,
+    list_announcements, update_announcement,
+};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/announcements", get(client_announcements))
+        .route(
+            "/admin/announcements",
+            get(list_announcements).post(create_announcement),
+        )
+        .route(
+            "/admin/announcements/:id",
+            get(get_announcement)
+                .put(update_announcement)
+                .delete(delete_announcement),
+        )
+}
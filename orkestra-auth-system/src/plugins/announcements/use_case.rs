@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+
+use sha2::{Digest, Sha256};
+use sqlx::types::{Json, Uuid};
+use tracing::Instrument;
+
+use crate::shared::{
+    client_version::ClientVersion,
+    database::Database,
+    pagination::{Page, PageQuery},
+};
+
+use super::{
+    dto::AnnouncementData,
+    error::AnnouncementError,
+    models::{normalize_locale, valid_locale, Announcement, ClientAnnouncement},
+};
+
+const TITLE_MAX_LEN: usize = 200;
+const BODY_MAX_LEN: usize = 4000;
+const MAX_LOCALES: usize = 50;
+
+/// Announcements running right now that target the client, highest priority
+/// first, with the ETag of the list
+pub async fn client_announcements(
+    database: &Database,
+    project_id: Uuid,
+    locale: Option<&str>,
+    version: Option<&ClientVersion>,
+) -> Result<(Vec<ClientAnnouncement>, String), AnnouncementError> {
+    const ACTIVE_QUERY: &str = "SELECT id, priority, starts_at, ends_at, locales, \
+        min_client_version, max_client_version, default_locale, texts, created_by, \
+        created_at, updated_at FROM announcements \
+        WHERE project_id = $1 AND starts_at <= now() AND ends_at > now() \
+        ORDER BY priority DESC, starts_at DESC, id;";
+
+    let rows: Vec<Announcement> = sqlx::query_as(ACTIVE_QUERY)
+        .bind(project_id)
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    let announcements: Vec<ClientAnnouncement> = rows
+        .iter()
+        .filter(|announcement| announcement.targets(locale, version))
+        .filter_map(|announcement| announcement.localize(locale))
+        .collect();
+
+    let body = serde_json::to_string(&announcements).unwrap_or_default();
+    let etag = format!(
+        "\"{}\"",
+        &hex::encode(Sha256::digest(body.as_bytes()))[..32]
+    );
+
+    Ok((announcements, etag))
+}
+
+/// All announcements of the project including finished ones, latest first
+pub async fn list_announcements(
+    database: &Database,
+    project_id: Uuid,
+    page: &PageQuery,
+) -> Result<Page<Announcement>, AnnouncementError> {
+    const LIST_QUERY: &str = "SELECT id, priority, starts_at, ends_at, locales, \
+        min_client_version, max_client_version, default_locale, texts, created_by, \
+        created_at, updated_at FROM announcements WHERE project_id = $1 \
+        ORDER BY starts_at DESC, id LIMIT $2 OFFSET $3;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(project_id)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+pub async fn get_announcement(
+    database: &Database,
+    project_id: Uuid,
+    id: Uuid,
+) -> Result<Announcement, AnnouncementError> {
+    const GET_QUERY: &str = "SELECT id, priority, starts_at, ends_at, locales, \
+        min_client_version, max_client_version, default_locale, texts, created_by, \
+        created_at, updated_at FROM announcements WHERE project_id = $1 AND id = $2;";
+
+    sqlx::query_as(GET_QUERY)
+        .bind(project_id)
+        .bind(id)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?
+        .ok_or(AnnouncementError::NotFound)
+}
+
+pub async fn create_announcement(
+    database: &Database,
+    project_id: Uuid,
+    created_by: Option<Uuid>,
+    data: AnnouncementData,
+) -> Result<Announcement, AnnouncementError> {
+    const INSERT_QUERY: &str = "INSERT INTO announcements (project_id, priority, starts_at, \
+        ends_at, locales, min_client_version, max_client_version, default_locale, texts, \
+        created_by) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10) \
+        RETURNING id, priority, starts_at, ends_at, locales, min_client_version, \
+        max_client_version, default_locale, texts, created_by, created_at, updated_at;";
+
+    let data = validate(data)?;
+
+    Ok(sqlx::query_as(INSERT_QUERY)
+        .bind(project_id)
+        .bind(data.priority)
+        .bind(data.starts_at)
+        .bind(data.ends_at)
+        .bind(data.locales)
+        .bind(data.min_client_version.map(String::from))
+        .bind(data.max_client_version.map(String::from))
+        .bind(data.default_locale)
+        .bind(Json(data.texts))
+        .bind(created_by)
+        .fetch_one(database.as_ref())
+        .in_current_span()
+        .await?)
+}
+
+/// Replaces the announcement as a whole
+pub async fn update_announcement(
+    database: &Database,
+    project_id: Uuid,
+    id: Uuid,
+    data: AnnouncementData,
+) -> Result<Announcement, AnnouncementError> {
+    const UPDATE_QUERY: &str = "UPDATE announcements SET priority = $3, starts_at = $4, \
+        ends_at = $5, locales = $6, min_client_version = $7, max_client_version = $8, \
+        default_locale = $9, texts = $10, updated_at = now() \
+        WHERE project_id = $1 AND id = $2 \
+        RETURNING id, priority, starts_at, ends_at, locales, min_client_version, \
+        max_client_version, default_locale, texts, created_by, created_at, updated_at;";
+
+    let data = validate(data)?;
+
+    sqlx::query_as(UPDATE_QUERY)
+        .bind(project_id)
+        .bind(id)
+        .bind(data.priority)
+        .bind(data.starts_at)
+        .bind(data.ends_at)
+        .bind(data.locales)
+        .bind(data.min_client_version.map(String::from))
+        .bind(data.max_client_version.map(String::from))
+        .bind(data.default_locale)
+        .bind(Json(data.texts))
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?
+        .ok_or(AnnouncementError::NotFound)
+}
+
+pub async fn delete_announcement(
+    database: &Database,
+    project_id: Uuid,
+    id: Uuid,
+) -> Result<(), AnnouncementError> {
+    const DELETE_QUERY: &str = "DELETE FROM announcements WHERE project_id = $1 AND id = $2;";
+
+    let result = sqlx::query(DELETE_QUERY)
+        .bind(project_id)
+        .bind(id)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AnnouncementError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Checks the announcement and normalizes its locales
+pub fn validate(data: AnnouncementData) -> Result<AnnouncementData, AnnouncementError> {
+    let invalid = |reason: &str| Err(AnnouncementError::Invalid(reason.to_string()));
+
+    if data.ends_at <= data.starts_at {
+        return invalid("ends_at has to be after starts_at");
+    }
+
+    if let (Some(min), Some(max)) = (&data.min_client_version, &data.max_client_version) {
+        if min > max {
+            return invalid("min_client_version is above max_client_version");
+        }
+    }
+
+    if data.texts.is_empty() || data.texts.len() > MAX_LOCALES {
+        return invalid("texts need 1 to 50 locales");
+    }
+
+    let mut texts = BTreeMap::new();
+
+    for (locale, mut text) in data.texts {
+        let locale = normalize_locale(&locale);
+
+        if !valid_locale(&locale) {
+            return Err(AnnouncementError::Invalid(format!(
+                "invalid locale `{locale}`"
+            )));
+        }
+
+        text.title = text.title.trim().to_string();
+        text.body = text.body.trim().to_string();
+
+        if text.title.is_empty() || text.title.chars().count() > TITLE_MAX_LEN {
+            return Err(AnnouncementError::Invalid(format!(
+                "title for `{locale}` has to be 1 to {TITLE_MAX_LEN} characters"
+            )));
+        }
+
+        if text.body.chars().count() > BODY_MAX_LEN {
+            return Err(AnnouncementError::Invalid(format!(
+                "body for `{locale}` is longer than {BODY_MAX_LEN} characters"
+            )));
+        }
+
+        if texts.insert(locale.clone(), text).is_some() {
+            return Err(AnnouncementError::Invalid(format!(
+                "locale `{locale}` is given twice"
+            )));
+        }
+    }
+
+    let default_locale = normalize_locale(&data.default_locale);
+
+    if !texts.contains_key(&default_locale) {
+        return invalid("default_locale has no text");
+    }
+
+    let locales = match data.locales {
+        Some(locales) => {
+            let locales: Vec<String> = locales.iter().map(|l| normalize_locale(l)).collect();
+
+            if locales.is_empty() || locales.len() > MAX_LOCALES {
+                return invalid("locales need 1 to 50 entries, leave it out to target everyone");
+            }
+
+            if let Some(locale) = locales.iter().find(|locale| !valid_locale(locale)) {
+                return Err(AnnouncementError::Invalid(format!(
+                    "invalid locale `{locale}`"
+                )));
+            }
+
+            Some(locales)
+        }
+        None => None,
+    };
+
+    Ok(AnnouncementData {
+        locales,
+        default_locale,
+        texts,
+        ..data
+    })
+}
@@ -0,0 +1,110 @@
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::api_keys::use_case,
+    shared::{
+        auth::extractor::{AdminCaller, ServiceCaller},
+        context::Context,
+        utils::{bad_request_json, created, internal_error_json, just_ok, not_found_json, ok},
+    },
+};
+
+use super::{
+    dto::{ApiKeyPath, CreateApiKeyData},
+    error::ApiKeyError,
+};
+
+pub async fn create_api_key(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    Json(request): Json<CreateApiKeyData>,
+) -> impl IntoResponse {
+    let span = info_span!("create_api_key");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to create api key",
+        name = request.name,
+        scopes = ?request.scopes,
+        admin = admin.actor(),
+    );
+
+    let result = use_case::create_api_key(context.database(), admin.user_id(), request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(response) => {
+            info!(event = "Api key created", id = %response.api_key.id);
+
+            created(response)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn list_api_keys(
+    Extension(context): Extension<Context>,
+    _admin: AdminCaller,
+) -> impl IntoResponse {
+    let span = info_span!("list_api_keys");
+    let _guard = span.enter();
+
+    info!(event = "Request to list api keys");
+
+    let result = use_case::list_api_keys(context.database())
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(api_keys) => ok(serde_json::json!({
+            "api_keys": api_keys
+        })),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn revoke_api_key(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    Path(ApiKeyPath { id }): Path<ApiKeyPath>,
+) -> impl IntoResponse {
+    let span = info_span!("revoke_api_key");
+    let _guard = span.enter();
+
+    info!(event = "Request to revoke api key", id = %id, admin = admin.actor());
+
+    let result = use_case::revoke_api_key(context.database(), id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => {
+            info!(event = "Api key revoked", id = %id);
+
+            just_ok()
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn get_service_identity(ServiceCaller(identity): ServiceCaller) -> impl IntoResponse {
+    ok(identity)
+}
+
+fn error_response(err: ApiKeyError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        ApiKeyError::NotFound(_) => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        ApiKeyError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        _ => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
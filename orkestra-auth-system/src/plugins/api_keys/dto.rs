@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use sqlx::types::Uuid;
+
+use super::models::ApiKey;
+
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyPath {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyData {
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreatedApiKeyResponse {
+    /// The only time the plain key is returned, it's stored hashed
+    pub key: String,
+
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+}
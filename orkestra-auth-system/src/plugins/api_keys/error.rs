@@ -0,0 +1,17 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Error, Debug)]
+pub enum ApiKeyError {
+    #[error("Invalid api key name")]
+    InvalidName,
+
+    #[error("Invalid scope: {0}")]
+    InvalidScope(String),
+
+    #[error("Api key not found: {0}")]
+    NotFound(Uuid),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
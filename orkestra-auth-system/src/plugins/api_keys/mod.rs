@@ -0,0 +1,26 @@
+mod controller;
+mod dto;
+mod error;
+mod models;
+mod use_case;
+
+pub mod router;
+
+#[cfg(test)]
+mod tests {
+    use super::use_case::valid_scope;
+
+    #[test]
+    fn scope_validation() {
+        assert!(valid_scope("admin"));
+        assert!(valid_scope("presence:write"));
+        assert!(valid_scope("cloud_saves:read"));
+
+        assert!(!valid_scope(""));
+        assert!(!valid_scope("Admin"));
+        assert!(!valid_scope("presence:"));
+        assert!(!valid_scope(":write"));
+        assert!(!valid_scope("a:b:c"));
+        assert!(!valid_scope("sessions write"));
+    }
+}
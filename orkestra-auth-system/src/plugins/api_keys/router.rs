@@ -0,0 +1,13 @@
+use axum::{
+    routing::{delete, get},
+    Router,
+};
+
+use super::controller::{create_api_key, get_service_identity, list_api_keys, revoke_api_key};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/admin/api_keys", get(list_api_keys).post(create_api_key))
+        .route("/admin/api_keys/:id", delete(revoke_api_key))
+        .route("/service/identity", get(get_service_identity))
+}
@@ -0,0 +1,94 @@
+use sqlx::types::Uuid;
+use tracing::Instrument;
+
+use crate::shared::{auth::api_key, database::Database};
+
+use super::{
+    dto::{CreateApiKeyData, CreatedApiKeyResponse},
+    error::ApiKeyError,
+    models::ApiKey,
+};
+
+const NAME_MAX_LEN: usize = 64;
+const SCOPE_MAX_LEN: usize = 64;
+
+pub async fn create_api_key(
+    database: &Database,
+    created_by: Option<Uuid>,
+    data: CreateApiKeyData,
+) -> Result<CreatedApiKeyResponse, ApiKeyError> {
+    const INSERT_QUERY: &str =
+        "INSERT INTO api_keys (name, key_prefix, key_hash, scopes, created_by) \
+        VALUES ($1, $2, $3, $4, $5) \
+        RETURNING id, name, key_prefix, scopes, created_by, created_at, last_used_at, revoked_at;";
+
+    let name = data.name.trim();
+
+    if name.is_empty() || name.chars().count() > NAME_MAX_LEN {
+        return Err(ApiKeyError::InvalidName);
+    }
+
+    if let Some(scope) = data.scopes.iter().find(|scope| !valid_scope(scope)) {
+        return Err(ApiKeyError::InvalidScope(scope.clone()));
+    }
+
+    let mut scopes = data.scopes;
+    scopes.sort();
+    scopes.dedup();
+
+    let generated = api_key::generate();
+
+    let api_key: ApiKey = sqlx::query_as(INSERT_QUERY)
+        .bind(name)
+        .bind(&generated.prefix)
+        .bind(&generated.hash)
+        .bind(&scopes)
+        .bind(created_by)
+        .fetch_one(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(CreatedApiKeyResponse {
+        key: generated.key,
+        api_key,
+    })
+}
+
+pub async fn list_api_keys(database: &Database) -> Result<Vec<ApiKey>, ApiKeyError> {
+    const LIST_QUERY: &str = "SELECT id, name, key_prefix, scopes, created_by, created_at, \
+        last_used_at, revoked_at FROM api_keys ORDER BY created_at DESC;";
+
+    Ok(sqlx::query_as(LIST_QUERY)
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?)
+}
+
+pub async fn revoke_api_key(database: &Database, id: Uuid) -> Result<(), ApiKeyError> {
+    const REVOKE_QUERY: &str =
+        "UPDATE api_keys SET revoked_at = coalesce(revoked_at, now()) WHERE id = $1;";
+
+    let result = sqlx::query(REVOKE_QUERY)
+        .bind(id)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiKeyError::NotFound(id));
+    }
+
+    Ok(())
+}
+
+/// Scopes look like `resource` or `resource:action`, in lower snake case
+pub fn valid_scope(scope: &str) -> bool {
+    let valid_part =
+        |part: &str| !part.is_empty() && part.chars().all(|c| matches!(c, 'a'..='z' | '_'));
+
+    scope.len() <= SCOPE_MAX_LEN
+        && match scope.split_once(':') {
+            Some((resource, action)) => valid_part(resource) && valid_part(action),
+            None => valid_part(scope),
+        }
+}
@@ -0,0 +1,60 @@
+use axum::{response::IntoResponse, Extension, Json};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::change_username::{error::ChangeUsernameError, use_case},
+    shared::{
+        auth::extractor::AuthUser,
+        context::Context,
+        utils::{bad_request_json, internal_error_json, ok},
+    },
+};
+
+use super::dto::{ChangeUsernameData, ChangeUsernameResponse};
+
+pub async fn change_username(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Json(request): Json<ChangeUsernameData>,
+) -> impl IntoResponse {
+    let span = info_span!("change_username");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to change username",
+        user_id = %user.id,
+        username = request.username,
+    );
+
+    let result = use_case::change_username(
+        context.database(),
+        context.username_policy(),
+        context.username_change_cooldown(),
+        user.id,
+        request,
+    )
+    .in_current_span()
+    .await;
+
+    match result {
+        Ok(username) => {
+            info!(event = "Username changed");
+
+            ok(ChangeUsernameResponse { username })
+        }
+        Err(ChangeUsernameError::Database(err)) => {
+            error!(event = "Database error", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
+        Err(err) => {
+            error!(event = %err);
+
+            bad_request_json(serde_json::json!({
+                "error": err.to_string()
+            }))
+        }
+    }
+}
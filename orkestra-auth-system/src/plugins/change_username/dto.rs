@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize)]
+pub struct ChangeUsernameData {
+    pub username: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangeUsernameResponse {
+    pub username: String,
+}
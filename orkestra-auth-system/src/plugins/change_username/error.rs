@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+use crate::shared::username::UsernameError;
+
+#[derive(Error, Debug)]
+pub enum ChangeUsernameError {
+    #[error("Unknown user")]
+    UnknownUser,
+
+    #[error("Invalid username: {0}")]
+    InvalidUsername(#[from] UsernameError),
+
+    #[error("Username is the same as the current one")]
+    SameUsername,
+
+    #[error("Username can be changed again in {0} seconds")]
+    Cooldown(i64),
+
+    #[error("User already exists")]
+    AlreadyExists,
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
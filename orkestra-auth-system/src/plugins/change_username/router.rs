@@ -0,0 +1,7 @@
+use axum::{routing::post, Router};
+
+use super::controller::change_username;
+
+pub fn service() -> Router {
+    Router::new().route("/me/username", post(change_username))
+}
@@ -0,0 +1,91 @@
+use sqlx::types::Uuid;
+use time::{Duration, OffsetDateTime};
+use tracing::Instrument;
+
+use crate::shared::{
+    audit::{self, AuditEvent},
+    database::Database,
+    username::UsernamePolicy,
+};
+
+use super::{dto::ChangeUsernameData, error::ChangeUsernameError};
+
+pub async fn change_username(
+    database: &Database,
+    policy: &UsernamePolicy,
+    cooldown: Duration,
+    id: Uuid,
+    data: ChangeUsernameData,
+) -> Result<String, ChangeUsernameError> {
+    const USER_QUERY: &str = "SELECT username, username_changed_at FROM users \
+        WHERE id = $1 AND deleted_at IS NULL FOR UPDATE;";
+    const UPDATE_QUERY: &str =
+        "UPDATE users SET username = $1, username_changed_at = now() WHERE id = $2;";
+    const HISTORY_QUERY: &str =
+        "INSERT INTO username_history (user_id, old_username, new_username) VALUES ($1, $2, $3);";
+
+    policy.check(&data.username)?;
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    let Some((username, changed_at)): Option<(String, Option<OffsetDateTime>)> =
+        sqlx::query_as(USER_QUERY)
+            .bind(id)
+            .fetch_optional(&mut *transaction)
+            .in_current_span()
+            .await?
+    else {
+        return Err(ChangeUsernameError::UnknownUser);
+    };
+
+    if username == data.username {
+        return Err(ChangeUsernameError::SameUsername);
+    }
+
+    if let Some(changed_at) = changed_at {
+        let remaining = changed_at + cooldown - OffsetDateTime::now_utc();
+
+        if remaining.is_positive() {
+            return Err(ChangeUsernameError::Cooldown(remaining.whole_seconds()));
+        }
+    }
+
+    let updated = sqlx::query(UPDATE_QUERY)
+        .bind(&data.username)
+        .bind(id)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await;
+
+    match updated {
+        Ok(_) => {}
+        Err(sqlx::Error::Database(err)) if err.is_unique_violation() => {
+            return Err(ChangeUsernameError::AlreadyExists);
+        }
+        Err(err) => return Err(err.into()),
+    }
+
+    sqlx::query(HISTORY_QUERY)
+        .bind(id)
+        .bind(&username)
+        .bind(&data.username)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    audit::record(
+        &mut *transaction,
+        id,
+        AuditEvent::UsernameChanged,
+        serde_json::json!({
+            "old_username": username,
+            "new_username": data.username,
+        }),
+    )
+    .in_current_span()
+    .await?;
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(data.username)
+}
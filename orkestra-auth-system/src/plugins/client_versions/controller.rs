@@ -0,0 +1,113 @@
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::client_versions::use_case,
+    shared::{
+        auth::extractor::AdminCaller,
+        client_gate,
+        context::Context,
+        project::Project,
+        utils::{bad_request_json, internal_error_json, just_ok, not_found_json, ok},
+    },
+};
+
+use super::{
+    dto::{PlatformPath, SetPolicyData},
+    error::ClientVersionsError,
+};
+
+pub async fn list_policies(
+    Extension(context): Extension<Context>,
+    project: Project,
+) -> impl IntoResponse {
+    let span = info_span!("list_client_versions");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request client version policies",
+        project = project.slug
+    );
+
+    let result = client_gate::policies(context.database(), project.id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(policies) => ok(serde_json::json!({
+            "platforms": policies
+        })),
+        Err(err) => error_response(err.into()),
+    }
+}
+
+pub async fn set_policy(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    project: Project,
+    Path(PlatformPath { platform }): Path<PlatformPath>,
+    Json(request): Json<SetPolicyData>,
+) -> impl IntoResponse {
+    let span = info_span!("set_client_version");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to set client version policy",
+        project = project.slug,
+        platform = platform,
+        min_version = %request.min_version,
+        latest_version = %request.latest_version,
+        admin = admin.actor(),
+    );
+
+    let result = use_case::set_policy(context.database(), project.id, platform, request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(policy) => ok(policy),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn delete_policy(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    project: Project,
+    Path(PlatformPath { platform }): Path<PlatformPath>,
+) -> impl IntoResponse {
+    let span = info_span!("delete_client_version");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to delete client version policy",
+        project = project.slug,
+        platform = platform,
+        admin = admin.actor(),
+    );
+
+    let result = use_case::delete_policy(context.database(), project.id, &platform)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => just_ok(),
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: ClientVersionsError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        ClientVersionsError::NotFound(_) => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        ClientVersionsError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        _ => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
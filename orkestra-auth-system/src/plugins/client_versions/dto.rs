@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+use crate::shared::client_version::ClientVersion;
+
+#[derive(Debug, Deserialize)]
+pub struct PlatformPath {
+    pub platform: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetPolicyData {
+    pub min_version: ClientVersion,
+    pub latest_version: ClientVersion,
+}
@@ -0,0 +1,16 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ClientVersionsError {
+    #[error("Invalid platform name")]
+    InvalidPlatform,
+
+    #[error("Minimum version is above the latest version")]
+    MinAboveLatest,
+
+    #[error("No version policy for platform: {0}")]
+    NotFound(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
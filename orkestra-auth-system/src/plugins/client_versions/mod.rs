@@ -0,0 +1,6 @@
+mod controller;
+mod dto;
+mod error;
+mod use_case;
+
+pub mod router;
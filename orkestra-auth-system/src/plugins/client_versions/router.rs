@@ -0,0 +1,15 @@
+use axum::{
+    routing::{get, put},
+    Router,
+};
+
+use super::controller::{delete_policy, list_policies, set_policy};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/client_versions", get(list_policies))
+        .route(
+            "/admin/client_versions/:platform",
+            put(set_policy).delete(delete_policy),
+        )
+}
@@ -0,0 +1,75 @@
+use sqlx::types::Uuid;
+use tracing::Instrument;
+
+use crate::shared::{client_gate::ClientVersionPolicy, database::Database};
+
+use super::{dto::SetPolicyData, error::ClientVersionsError};
+
+const PLATFORM_MAX_LEN: usize = 32;
+
+pub async fn set_policy(
+    database: &Database,
+    project_id: Uuid,
+    platform: String,
+    data: SetPolicyData,
+) -> Result<ClientVersionPolicy, ClientVersionsError> {
+    const UPSERT_QUERY: &str =
+        "INSERT INTO client_versions (project_id, platform, min_version, latest_version) \
+        VALUES ($1, $2, $3, $4) ON CONFLICT (project_id, platform) DO UPDATE \
+        SET min_version = excluded.min_version, latest_version = excluded.latest_version, \
+        updated_at = now();";
+
+    if !valid_platform(&platform) {
+        return Err(ClientVersionsError::InvalidPlatform);
+    }
+
+    if data.min_version > data.latest_version {
+        return Err(ClientVersionsError::MinAboveLatest);
+    }
+
+    sqlx::query(UPSERT_QUERY)
+        .bind(project_id)
+        .bind(&platform)
+        .bind(data.min_version.to_string())
+        .bind(data.latest_version.to_string())
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(ClientVersionPolicy {
+        platform,
+        min_version: data.min_version,
+        latest_version: data.latest_version,
+    })
+}
+
+pub async fn delete_policy(
+    database: &Database,
+    project_id: Uuid,
+    platform: &str,
+) -> Result<(), ClientVersionsError> {
+    const DELETE_QUERY: &str =
+        "DELETE FROM client_versions WHERE project_id = $1 AND platform = $2;";
+
+    let result = sqlx::query(DELETE_QUERY)
+        .bind(project_id)
+        .bind(platform)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ClientVersionsError::NotFound(platform.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Platforms are lower case latin letters, digits, `_` and `-`
+fn valid_platform(platform: &str) -> bool {
+    !platform.is_empty()
+        && platform.len() <= PLATFORM_MAX_LEN
+        && platform
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-'))
+}
@@ -0,0 +1,290 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::friends::use_case,
+    shared::{
+        auth::extractor::AuthUser,
+        context::Context,
+        pagination::PageQuery,
+        utils::{
+            bad_request_json, conflict_json, created, forbidden_json, internal_error_json, just_ok,
+            not_found_json, ok,
+        },
+    },
+};
+
+use super::{
+    dto::{RequestPath, SendRequestData, UserPath},
+    error::FriendsError,
+};
+
+pub async fn list_friends(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_friends");
+    let _guard = span.enter();
+
+    info!(event = "Request friends list", user_id = %user.id);
+
+    let result = use_case::list_friends(context.database(), user.id, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(friends) => ok(friends),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn list_incoming_requests(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_incoming_requests");
+    let _guard = span.enter();
+
+    info!(event = "Request incoming friend requests", user_id = %user.id);
+
+    let result = use_case::list_incoming_requests(context.database(), user.id, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(requests) => ok(requests),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn list_outgoing_requests(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_outgoing_requests");
+    let _guard = span.enter();
+
+    info!(event = "Request outgoing friend requests", user_id = %user.id);
+
+    let result = use_case::list_outgoing_requests(context.database(), user.id, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(requests) => ok(requests),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn send_request(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Json(request): Json<SendRequestData>,
+) -> impl IntoResponse {
+    let span = info_span!("send_friend_request");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to send friend request",
+        user_id = %user.id,
+        target_id = %request.user_id,
+    );
+
+    let result = use_case::send_request(context.database(), user, request.user_id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(response) => {
+            info!(event = "Friend request sent", response = ?response);
+
+            created(response)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn accept_request(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(RequestPath { id }): Path<RequestPath>,
+) -> impl IntoResponse {
+    let span = info_span!("accept_friend_request");
+    let _guard = span.enter();
+
+    info!(event = "Request to accept friend request", user_id = %user.id, request_id = %id);
+
+    let result = use_case::accept_request(context.database(), user.id, id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(friend_id) => {
+            info!(event = "Friend request accepted", friend_id = %friend_id);
+
+            ok(serde_json::json!({
+                "user_id": friend_id
+            }))
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn decline_request(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(RequestPath { id }): Path<RequestPath>,
+) -> impl IntoResponse {
+    let span = info_span!("decline_friend_request");
+    let _guard = span.enter();
+
+    info!(event = "Request to decline friend request", user_id = %user.id, request_id = %id);
+
+    let result = use_case::decline_request(context.database(), user.id, id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => just_ok(),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn cancel_request(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(RequestPath { id }): Path<RequestPath>,
+) -> impl IntoResponse {
+    let span = info_span!("cancel_friend_request");
+    let _guard = span.enter();
+
+    info!(event = "Request to cancel friend request", user_id = %user.id, request_id = %id);
+
+    let result = use_case::cancel_request(context.database(), user.id, id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => just_ok(),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn remove_friend(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(UserPath { user_id }): Path<UserPath>,
+) -> impl IntoResponse {
+    let span = info_span!("remove_friend");
+    let _guard = span.enter();
+
+    info!(event = "Request to remove friend", user_id = %user.id, friend_id = %user_id);
+
+    let result = use_case::remove_friend(context.database(), user.id, user_id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => just_ok(),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn list_blocks(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_blocks");
+    let _guard = span.enter();
+
+    info!(event = "Request blocked users", user_id = %user.id);
+
+    let result = use_case::list_blocks(context.database(), user.id, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(blocks) => ok(blocks),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn block_user(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(UserPath { user_id }): Path<UserPath>,
+) -> impl IntoResponse {
+    let span = info_span!("block_user");
+    let _guard = span.enter();
+
+    info!(event = "Request to block user", user_id = %user.id, target_id = %user_id);
+
+    let result = use_case::block_user(context.database(), user, user_id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => {
+            info!(event = "User blocked");
+
+            just_ok()
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn unblock_user(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(UserPath { user_id }): Path<UserPath>,
+) -> impl IntoResponse {
+    let span = info_span!("unblock_user");
+    let _guard = span.enter();
+
+    info!(event = "Request to unblock user", user_id = %user.id, target_id = %user_id);
+
+    let result = use_case::unblock_user(context.database(), user.id, user_id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => just_ok(),
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: FriendsError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        FriendsError::UnknownUser
+        | FriendsError::RequestNotFound
+        | FriendsError::NotFriends
+        | FriendsError::NotBlocked => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        FriendsError::AlreadyFriends | FriendsError::AlreadyRequested => {
+            conflict_json(serde_json::json!({
+                "error": err.to_string()
+            }))
+        }
+        FriendsError::Blocked => forbidden_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        FriendsError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        FriendsError::SelfTarget => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct SendRequestData {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserPath {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequestPath {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SendRequestResponse {
+    Pending {
+        request_id: Uuid,
+    },
+
+    /// The other user had already asked us, so the request turned into a friendship
+    Accepted {
+        user_id: Uuid,
+    },
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum FriendsError {
+    #[error("User not found")]
+    UnknownUser,
+
+    #[error("You can't do that to yourself")]
+    SelfTarget,
+
+    #[error("User is not accepting friend requests")]
+    Blocked,
+
+    #[error("Already friends")]
+    AlreadyFriends,
+
+    #[error("Friend request is already sent")]
+    AlreadyRequested,
+
+    #[error("Friend request not found")]
+    RequestNotFound,
+
+    #[error("User is not your friend")]
+    NotFriends,
+
+    #[error("User is not blocked")]
+    NotBlocked,
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,7 @@
+mod controller;
+mod dto;
+mod error;
+mod models;
+mod use_case;
+
+pub mod router;
@@ -0,0 +1,36 @@
+use serde::Serialize;
+use sqlx::{types::Uuid, FromRow};
+use time::OffsetDateTime;
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct Friend {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub since: OffsetDateTime,
+}
+
+/// Request as seen by one side, `user_id` is the other party
+#[derive(Debug, FromRow, Serialize)]
+pub struct FriendRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct BlockedUser {
+    pub user_id: Uuid,
+    pub username: String,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
@@ -0,0 +1,23 @@
+use axum::{
+    routing::{delete, get, post, put},
+    Router,
+};
+
+use super::controller::{
+    accept_request, block_user, cancel_request, decline_request, list_blocks, list_friends,
+    list_incoming_requests, list_outgoing_requests, remove_friend, send_request, unblock_user,
+};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/me/friends", get(list_friends))
+        .route("/me/friends/:user_id", delete(remove_friend))
+        .route("/me/friends/requests", post(send_request))
+        .route("/me/friends/requests/incoming", get(list_incoming_requests))
+        .route("/me/friends/requests/outgoing", get(list_outgoing_requests))
+        .route("/me/friends/requests/:id", delete(cancel_request))
+        .route("/me/friends/requests/:id/accept", post(accept_request))
+        .route("/me/friends/requests/:id/decline", post(decline_request))
+        .route("/me/blocks", get(list_blocks))
+        .route("/me/blocks/:user_id", put(block_user).delete(unblock_user))
+}
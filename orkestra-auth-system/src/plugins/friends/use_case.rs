@@ -0,0 +1,377 @@
+use sqlx::{types::Uuid, Postgres, Transaction};
+use tracing::Instrument;
+
+use crate::shared::{
+    auth::extractor::AuthUser,
+    database::Database,
+    notification::{self, NotificationKind},
+    pagination::{Page, PageQuery},
+};
+
+use super::{
+    dto::SendRequestResponse,
+    error::FriendsError,
+    models::{BlockedUser, Friend, FriendRequest},
+};
+
+pub async fn list_friends(
+    database: &Database,
+    user_id: Uuid,
+    page: &PageQuery,
+) -> Result<Page<Friend>, FriendsError> {
+    const LIST_QUERY: &str = "SELECT f.friend_id AS user_id, u.username, p.display_name, \
+        p.avatar_url, f.created_at AS since FROM friendships f \
+        JOIN users u ON u.id = f.friend_id LEFT JOIN profiles p ON p.user_id = f.friend_id \
+        WHERE f.user_id = $1 AND u.deleted_at IS NULL \
+        ORDER BY f.created_at DESC, f.friend_id LIMIT $2 OFFSET $3;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(user_id)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+pub async fn list_incoming_requests(
+    database: &Database,
+    user_id: Uuid,
+    page: &PageQuery,
+) -> Result<Page<FriendRequest>, FriendsError> {
+    const LIST_QUERY: &str = "SELECT r.id, r.sender_id AS user_id, u.username, p.display_name, \
+        p.avatar_url, r.created_at FROM friend_requests r \
+        JOIN users u ON u.id = r.sender_id LEFT JOIN profiles p ON p.user_id = r.sender_id \
+        WHERE r.receiver_id = $1 AND u.deleted_at IS NULL \
+        ORDER BY r.created_at DESC, r.id LIMIT $2 OFFSET $3;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(user_id)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+pub async fn list_outgoing_requests(
+    database: &Database,
+    user_id: Uuid,
+    page: &PageQuery,
+) -> Result<Page<FriendRequest>, FriendsError> {
+    const LIST_QUERY: &str = "SELECT r.id, r.receiver_id AS user_id, u.username, \
+        p.display_name, p.avatar_url, r.created_at FROM friend_requests r \
+        JOIN users u ON u.id = r.receiver_id LEFT JOIN profiles p ON p.user_id = r.receiver_id \
+        WHERE r.sender_id = $1 AND u.deleted_at IS NULL \
+        ORDER BY r.created_at DESC, r.id LIMIT $2 OFFSET $3;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(user_id)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+pub async fn list_blocks(
+    database: &Database,
+    user_id: Uuid,
+    page: &PageQuery,
+) -> Result<Page<BlockedUser>, FriendsError> {
+    const LIST_QUERY: &str = "SELECT b.blocked_id AS user_id, u.username, b.created_at \
+        FROM blocks b JOIN users u ON u.id = b.blocked_id \
+        WHERE b.user_id = $1 ORDER BY b.created_at DESC, b.blocked_id LIMIT $2 OFFSET $3;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(user_id)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+/// Sends a friend request, or accepts the one the target has already sent us
+pub async fn send_request(
+    database: &Database,
+    user: AuthUser,
+    target_id: Uuid,
+) -> Result<SendRequestResponse, FriendsError> {
+    const BLOCKED_QUERY: &str = "SELECT EXISTS (SELECT 1 FROM blocks \
+        WHERE (user_id = $1 AND blocked_id = $2) OR (user_id = $2 AND blocked_id = $1));";
+    const FRIENDS_QUERY: &str =
+        "SELECT EXISTS (SELECT 1 FROM friendships WHERE user_id = $1 AND friend_id = $2);";
+    const TAKE_INCOMING_QUERY: &str =
+        "DELETE FROM friend_requests WHERE sender_id = $2 AND receiver_id = $1 RETURNING id;";
+    const INSERT_REQUEST_QUERY: &str =
+        "INSERT INTO friend_requests (sender_id, receiver_id) VALUES ($1, $2) \
+        ON CONFLICT DO NOTHING RETURNING id;";
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    lock_pair(&mut transaction, user, target_id).await?;
+
+    let blocked: bool = sqlx::query_scalar(BLOCKED_QUERY)
+        .bind(user.id)
+        .bind(target_id)
+        .fetch_one(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    if blocked {
+        return Err(FriendsError::Blocked);
+    }
+
+    let friends: bool = sqlx::query_scalar(FRIENDS_QUERY)
+        .bind(user.id)
+        .bind(target_id)
+        .fetch_one(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    if friends {
+        return Err(FriendsError::AlreadyFriends);
+    }
+
+    let incoming: Option<Uuid> = sqlx::query_scalar(TAKE_INCOMING_QUERY)
+        .bind(user.id)
+        .bind(target_id)
+        .fetch_optional(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    if let Some(request_id) = incoming {
+        befriend(&mut transaction, user.id, target_id).await?;
+
+        notification::deliver(
+            &mut *transaction,
+            target_id,
+            NotificationKind::FriendRequestAccepted,
+            serde_json::json!({ "request_id": request_id, "user_id": user.id }),
+        )
+        .in_current_span()
+        .await?;
+
+        transaction.commit().in_current_span().await?;
+
+        return Ok(SendRequestResponse::Accepted { user_id: target_id });
+    }
+
+    let Some(request_id): Option<Uuid> = sqlx::query_scalar(INSERT_REQUEST_QUERY)
+        .bind(user.id)
+        .bind(target_id)
+        .fetch_optional(&mut *transaction)
+        .in_current_span()
+        .await?
+    else {
+        return Err(FriendsError::AlreadyRequested);
+    };
+
+    notification::deliver(
+        &mut *transaction,
+        target_id,
+        NotificationKind::FriendRequest,
+        serde_json::json!({ "request_id": request_id, "user_id": user.id }),
+    )
+    .in_current_span()
+    .await?;
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(SendRequestResponse::Pending { request_id })
+}
+
+/// Turns an incoming request into a friendship, returns the new friend id
+pub async fn accept_request(
+    database: &Database,
+    user_id: Uuid,
+    request_id: Uuid,
+) -> Result<Uuid, FriendsError> {
+    const TAKE_QUERY: &str =
+        "DELETE FROM friend_requests WHERE id = $1 AND receiver_id = $2 RETURNING sender_id;";
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    let Some(sender_id): Option<Uuid> = sqlx::query_scalar(TAKE_QUERY)
+        .bind(request_id)
+        .bind(user_id)
+        .fetch_optional(&mut *transaction)
+        .in_current_span()
+        .await?
+    else {
+        return Err(FriendsError::RequestNotFound);
+    };
+
+    befriend(&mut transaction, user_id, sender_id).await?;
+
+    notification::deliver(
+        &mut *transaction,
+        sender_id,
+        NotificationKind::FriendRequestAccepted,
+        serde_json::json!({ "request_id": request_id, "user_id": user_id }),
+    )
+    .in_current_span()
+    .await?;
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(sender_id)
+}
+
+pub async fn decline_request(
+    database: &Database,
+    user_id: Uuid,
+    request_id: Uuid,
+) -> Result<(), FriendsError> {
+    const DELETE_QUERY: &str = "DELETE FROM friend_requests WHERE id = $1 AND receiver_id = $2;";
+
+    delete_one(database, DELETE_QUERY, request_id, user_id)
+        .await?
+        .then_some(())
+        .ok_or(FriendsError::RequestNotFound)
+}
+
+pub async fn cancel_request(
+    database: &Database,
+    user_id: Uuid,
+    request_id: Uuid,
+) -> Result<(), FriendsError> {
+    const DELETE_QUERY: &str = "DELETE FROM friend_requests WHERE id = $1 AND sender_id = $2;";
+
+    delete_one(database, DELETE_QUERY, request_id, user_id)
+        .await?
+        .then_some(())
+        .ok_or(FriendsError::RequestNotFound)
+}
+
+pub async fn remove_friend(
+    database: &Database,
+    user_id: Uuid,
+    friend_id: Uuid,
+) -> Result<(), FriendsError> {
+    const DELETE_QUERY: &str = "DELETE FROM friendships \
+        WHERE (user_id = $1 AND friend_id = $2) OR (user_id = $2 AND friend_id = $1);";
+
+    delete_one(database, DELETE_QUERY, user_id, friend_id)
+        .await?
+        .then_some(())
+        .ok_or(FriendsError::NotFriends)
+}
+
+/// Blocks the user, dropping the friendship and pending requests between both of them
+pub async fn block_user(
+    database: &Database,
+    user: AuthUser,
+    target_id: Uuid,
+) -> Result<(), FriendsError> {
+    const INSERT_QUERY: &str =
+        "INSERT INTO blocks (user_id, blocked_id) VALUES ($1, $2) ON CONFLICT DO NOTHING;";
+    const DELETE_FRIENDSHIP_QUERY: &str = "DELETE FROM friendships \
+        WHERE (user_id = $1 AND friend_id = $2) OR (user_id = $2 AND friend_id = $1);";
+    const DELETE_REQUESTS_QUERY: &str = "DELETE FROM friend_requests \
+        WHERE (sender_id = $1 AND receiver_id = $2) OR (sender_id = $2 AND receiver_id = $1);";
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    lock_pair(&mut transaction, user, target_id).await?;
+
+    for query in [INSERT_QUERY, DELETE_FRIENDSHIP_QUERY, DELETE_REQUESTS_QUERY] {
+        sqlx::query(query)
+            .bind(user.id)
+            .bind(target_id)
+            .execute(&mut *transaction)
+            .in_current_span()
+            .await?;
+    }
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(())
+}
+
+pub async fn unblock_user(
+    database: &Database,
+    user_id: Uuid,
+    target_id: Uuid,
+) -> Result<(), FriendsError> {
+    const DELETE_QUERY: &str = "DELETE FROM blocks WHERE user_id = $1 AND blocked_id = $2;";
+
+    delete_one(database, DELETE_QUERY, user_id, target_id)
+        .await?
+        .then_some(())
+        .ok_or(FriendsError::NotBlocked)
+}
+
+/// Checks the target is a live user of the same project and locks both rows,
+/// so concurrent requests between the same pair are applied one by one
+async fn lock_pair(
+    transaction: &mut Transaction<'_, Postgres>,
+    user: AuthUser,
+    target_id: Uuid,
+) -> Result<(), FriendsError> {
+    const LOCK_QUERY: &str = "SELECT id FROM users \
+        WHERE id IN ($1, $2) AND project_id = $3 AND deleted_at IS NULL \
+        ORDER BY id FOR NO KEY UPDATE;";
+
+    if user.id == target_id {
+        return Err(FriendsError::SelfTarget);
+    }
+
+    let locked: Vec<Uuid> = sqlx::query_scalar(LOCK_QUERY)
+        .bind(user.id)
+        .bind(target_id)
+        .bind(user.project_id)
+        .fetch_all(&mut **transaction)
+        .in_current_span()
+        .await?;
+
+    if !locked.contains(&target_id) {
+        return Err(FriendsError::UnknownUser);
+    }
+
+    Ok(())
+}
+
+async fn befriend(
+    transaction: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    friend_id: Uuid,
+) -> Result<(), FriendsError> {
+    const INSERT_QUERY: &str = "INSERT INTO friendships (user_id, friend_id) \
+        VALUES ($1, $2), ($2, $1) ON CONFLICT DO NOTHING;";
+
+    sqlx::query(INSERT_QUERY)
+        .bind(user_id)
+        .bind(friend_id)
+        .execute(&mut **transaction)
+        .in_current_span()
+        .await?;
+
+    Ok(())
+}
+
+/// Runs a delete bound to two ids, returns whether anything was deleted
+async fn delete_one(
+    database: &Database,
+    query: &'static str,
+    first: Uuid,
+    second: Uuid,
+) -> Result<bool, FriendsError> {
+    let result = sqlx::query(query)
+        .bind(first)
+        .bind(second)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -1,12 +1,15 @@
 use axum::{response::IntoResponse, Extension, Json};
 
-use tracing::{error, info, info_span, Instrument};
+use tracing::{error, info, info_span, warn, Instrument};
 
 use crate::{
-    plugins::login::use_case,
+    plugins::login::{error::LoginError, use_case},
     shared::{
+        ban::banned_json,
+        client_gate::SupportedClient,
         context::Context,
-        utils::{bad_request_json, just_ok},
+        project::Project,
+        utils::{bad_request_json, internal_error_json, ok},
     },
 };
 
@@ -14,22 +17,42 @@ use super::dto::LoginData;
 
 pub async fn login(
     Extension(context): Extension<Context>,
+    project: Project,
+    client: SupportedClient,
     Json(request): Json<LoginData>,
 ) -> impl IntoResponse {
-    let span = info_span!("signup");
+    let span = info_span!("login");
     let _guard = span.enter();
 
-    info!(event = "Request to login user", username = request.username,);
+    info!(
+        event = "Request to login user",
+        project = project.slug,
+        username = request.username,
+        client_platform = ?client.platform,
+        client_version = ?client.version.as_ref().map(ToString::to_string),
+    );
 
-    let result = use_case::login(context.database(), request)
+    let result = use_case::login(context.database(), &project, context.token_ttl(), request)
         .in_current_span()
         .await;
 
     match result {
-        Ok(_) => {
+        Ok(token) => {
             info!(event = "Successfully login");
 
-            just_ok()
+            ok(token)
+        }
+        Err(LoginError::Banned(ban)) => {
+            warn!(event = "Banned user tried to login");
+
+            banned_json(&ban)
+        }
+        Err(LoginError::Database(err)) => {
+            error!(event = "Database error", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
         }
         Err(err) => {
             error!(event = %err);
@@ -1,9 +1,15 @@
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq, Eq)]
+use crate::shared::ban::ActiveBan;
+
+#[derive(Error, Debug)]
 pub enum LoginError {
     #[error("Unknown user")]
     UnknownUser,
     #[error("Wrong password")]
     WrongPassword,
+    #[error("Account is banned")]
+    Banned(ActiveBan),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
 }
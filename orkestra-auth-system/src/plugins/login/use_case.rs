@@ -3,26 +3,43 @@ use pbkdf2::{
     Pbkdf2,
 };
 use sqlx::types::Uuid;
-use tracing::Instrument;
+use time::Duration;
+use tracing::{warn, Instrument};
 
-use crate::shared::database::Database;
+use crate::shared::{
+    audit::{self, AuditEvent},
+    auth::token::{self, IssuedToken},
+    ban,
+    database::Database,
+    project::Project,
+};
 
 use super::{dto::LoginData, error::LoginError};
 
-pub async fn login(database: &Database, data: LoginData) -> Result<(), LoginError> {
-    const LOGIN_QUERY: &str = "SELECT id, password FROM users WHERE users.username = $1;";
+pub async fn login(
+    database: &Database,
+    project: &Project,
+    token_ttl: Duration,
+    data: LoginData,
+) -> Result<IssuedToken, LoginError> {
+    const LOGIN_QUERY: &str = "SELECT id, password FROM users \
+        WHERE users.project_id = $1 AND lower(users.username) = lower($2) \
+        AND users.deleted_at IS NULL;";
 
-    let Some((_, password)): Option<(Uuid, String)> = sqlx::query_as(LOGIN_QUERY)
+    let Some((id, password)): Option<(Uuid, String)> = sqlx::query_as(LOGIN_QUERY)
+        .bind(project.id)
         .bind(&data.username)
         .fetch_optional(database.as_ref())
         .in_current_span()
-        .await
-        .unwrap()
+        .await?
     else {
         return Err(LoginError::UnknownUser);
     };
 
-    let parsed_hash = PasswordHash::new(&password).unwrap();
+    // Accounts created through an external provider have no local password
+    let Ok(parsed_hash) = PasswordHash::new(&password) else {
+        return Err(LoginError::WrongPassword);
+    };
 
     if Pbkdf2
         .verify_password(data.password.as_bytes(), &parsed_hash)
@@ -31,5 +48,28 @@ pub async fn login(database: &Database, data: LoginData) -> Result<(), LoginErro
         return Err(LoginError::WrongPassword);
     }
 
-    Ok(())
+    if let Some(ban) = ban::active_ban(database.as_ref(), id)
+        .in_current_span()
+        .await?
+    {
+        return Err(LoginError::Banned(ban));
+    }
+
+    let token = token::issue(database, project.id, id, token_ttl)
+        .in_current_span()
+        .await?;
+
+    if let Err(err) = audit::record(
+        database.as_ref(),
+        id,
+        AuditEvent::Login,
+        serde_json::json!({}),
+    )
+    .in_current_span()
+    .await
+    {
+        warn!(event = "Couldn't record audit event", error = %err);
+    }
+
+    Ok(token)
 }
@@ -1,2 +1,15 @@
+pub mod account;
+pub mod announcements;
+pub mod api_keys;
+pub mod change_username;
+pub mod client_versions;
+pub mod friends;
 pub mod login;
+pub mod notifications;
+pub mod presence;
+pub mod profile;
+pub mod projects;
+pub mod remote_config;
+pub mod reports;
+pub mod saves;
 pub mod signup;
@@ -0,0 +1,174 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::notifications::use_case,
+    shared::{
+        auth::extractor::{AuthUser, ServiceCaller},
+        context::Context,
+        pagination::PageQuery,
+        utils::{
+            bad_request_json, created, forbidden_json, internal_error_json, just_ok,
+            not_found_json, ok,
+        },
+    },
+};
+
+use super::{
+    dto::{ListFilter, MarkReadData, NotificationPath, PostNotificationData},
+    error::NotificationError,
+};
+
+/// Scope services need to post notifications to players
+const NOTIFICATIONS_WRITE_SCOPE: &str = "notifications:write";
+
+pub async fn list_notifications(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Query(filter): Query<ListFilter>,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_notifications");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request notifications",
+        user_id = %user.id,
+        unread_only = filter.unread_only,
+    );
+
+    let result =
+        use_case::list_notifications(context.database(), user.id, filter.unread_only, &page)
+            .in_current_span()
+            .await;
+
+    match result {
+        Ok(notifications) => ok(notifications),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn unread_count(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+) -> impl IntoResponse {
+    let span = info_span!("unread_notifications_count");
+    let _guard = span.enter();
+
+    info!(event = "Request unread notifications count", user_id = %user.id);
+
+    let result = use_case::unread_count(context.database(), user.id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(unread) => ok(serde_json::json!({
+            "unread": unread
+        })),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn mark_read(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Json(request): Json<MarkReadData>,
+) -> impl IntoResponse {
+    let span = info_span!("mark_notifications_read");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to mark notifications read",
+        user_id = %user.id,
+        count = ?request.ids.as_ref().map(Vec::len),
+    );
+
+    let result = use_case::mark_read(context.database(), user.id, request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(updated) => ok(serde_json::json!({
+            "updated": updated
+        })),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn delete_notification(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(NotificationPath { id }): Path<NotificationPath>,
+) -> impl IntoResponse {
+    let span = info_span!("delete_notification");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to delete notification",
+        user_id = %user.id,
+        notification_id = %id,
+    );
+
+    let result = use_case::delete_notification(context.database(), user.id, id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => just_ok(),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn post_notification(
+    Extension(context): Extension<Context>,
+    ServiceCaller(service): ServiceCaller,
+    Json(request): Json<PostNotificationData>,
+) -> impl IntoResponse {
+    let span = info_span!("post_notification");
+    let _guard = span.enter();
+
+    if !service.has_scope(NOTIFICATIONS_WRITE_SCOPE) {
+        return forbidden_json(serde_json::json!({
+            "error": "Api key lacks the notifications:write scope"
+        }));
+    }
+
+    info!(
+        event = "Service posts notification",
+        service = service.name,
+        kind = request.kind,
+        recipients = request.user_ids.len(),
+    );
+
+    let result = use_case::post_notification(context.database(), &service.name, request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(delivered) => created(serde_json::json!({
+            "delivered": delivered
+        })),
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: NotificationError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        NotificationError::NotFound => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        NotificationError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        NotificationError::Invalid(_) => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use sqlx::types::Uuid;
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationPath {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListFilter {
+    #[serde(default)]
+    pub unread_only: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarkReadData {
+    /// Every unread notification when absent
+    pub ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostNotificationData {
+    pub user_ids: Vec<Uuid>,
+    pub kind: String,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
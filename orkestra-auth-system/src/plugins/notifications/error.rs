@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NotificationError {
+    #[error("Notification not found")]
+    NotFound,
+
+    #[error("Invalid notification: {0}")]
+    Invalid(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
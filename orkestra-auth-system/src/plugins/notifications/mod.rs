@@ -0,0 +1,23 @@
+mod controller;
+mod dto;
+mod error;
+mod models;
+mod use_case;
+
+pub mod router;
+
+#[cfg(test)]
+mod tests {
+    use super::use_case::valid_kind;
+
+    #[test]
+    fn kind_validation() {
+        assert!(valid_kind("session_invite"));
+        assert!(valid_kind("event.started"));
+        assert!(valid_kind("shop:offer-2"));
+
+        assert!(!valid_kind(""));
+        assert!(!valid_kind("Session invite"));
+        assert!(!valid_kind(&"k".repeat(65)));
+    }
+}
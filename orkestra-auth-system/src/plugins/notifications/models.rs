@@ -0,0 +1,17 @@
+use serde::Serialize;
+use sqlx::{types::Uuid, FromRow};
+use time::OffsetDateTime;
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct Notification {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub sender: Option<String>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub read_at: Option<OffsetDateTime>,
+}
@@ -0,0 +1,17 @@
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+
+use super::controller::{
+    delete_notification, list_notifications, mark_read, post_notification, unread_count,
+};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/me/notifications", get(list_notifications))
+        .route("/me/notifications/unread_count", get(unread_count))
+        .route("/me/notifications/read", post(mark_read))
+        .route("/me/notifications/:id", delete(delete_notification))
+        .route("/service/notifications", post(post_notification))
+}
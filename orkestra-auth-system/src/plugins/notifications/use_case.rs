@@ -0,0 +1,154 @@
+use sqlx::types::Uuid;
+use tracing::Instrument;
+
+use crate::shared::{
+    database::Database,
+    pagination::{Page, PageQuery},
+};
+
+use super::{
+    dto::{MarkReadData, PostNotificationData},
+    error::NotificationError,
+    models::Notification,
+};
+
+const KIND_MAX_LEN: usize = 64;
+const PAYLOAD_MAX_BYTES: usize = 8 * 1024;
+const MAX_IDS: usize = 100;
+
+/// Newest notifications first
+pub async fn list_notifications(
+    database: &Database,
+    user_id: Uuid,
+    unread_only: bool,
+    page: &PageQuery,
+) -> Result<Page<Notification>, NotificationError> {
+    const LIST_QUERY: &str = "SELECT id, kind, payload, sender, created_at, read_at \
+        FROM notifications WHERE user_id = $1 AND ($2 = false OR read_at IS NULL) \
+        ORDER BY created_at DESC, id LIMIT $3 OFFSET $4;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(user_id)
+        .bind(unread_only)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+pub async fn unread_count(database: &Database, user_id: Uuid) -> Result<i64, NotificationError> {
+    const COUNT_QUERY: &str =
+        "SELECT count(*) FROM notifications WHERE user_id = $1 AND read_at IS NULL;";
+
+    Ok(sqlx::query_scalar(COUNT_QUERY)
+        .bind(user_id)
+        .fetch_one(database.as_ref())
+        .in_current_span()
+        .await?)
+}
+
+/// Marks the given notifications, or all of them, read. Returns how many changed
+pub async fn mark_read(
+    database: &Database,
+    user_id: Uuid,
+    data: MarkReadData,
+) -> Result<u64, NotificationError> {
+    const MARK_QUERY: &str = "UPDATE notifications SET read_at = now() \
+        WHERE user_id = $1 AND read_at IS NULL AND ($2::uuid[] IS NULL OR id = ANY($2));";
+
+    if data.ids.as_ref().is_some_and(|ids| ids.len() > MAX_IDS) {
+        return Err(NotificationError::Invalid(format!(
+            "at most {MAX_IDS} ids at once"
+        )));
+    }
+
+    let result = sqlx::query(MARK_QUERY)
+        .bind(user_id)
+        .bind(data.ids)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+pub async fn delete_notification(
+    database: &Database,
+    user_id: Uuid,
+    id: Uuid,
+) -> Result<(), NotificationError> {
+    const DELETE_QUERY: &str = "DELETE FROM notifications WHERE id = $1 AND user_id = $2;";
+
+    let result = sqlx::query(DELETE_QUERY)
+        .bind(id)
+        .bind(user_id)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(NotificationError::NotFound);
+    }
+
+    Ok(())
+}
+
+/// Delivers a service notification, unknown and deleted users are skipped.
+/// Returns how many users got it
+pub async fn post_notification(
+    database: &Database,
+    sender: &str,
+    data: PostNotificationData,
+) -> Result<u64, NotificationError> {
+    const INSERT_QUERY: &str = "INSERT INTO notifications (user_id, kind, payload, sender) \
+        SELECT id, $2, $3, $4 FROM users WHERE id = ANY($1) AND deleted_at IS NULL;";
+
+    if !valid_kind(&data.kind) {
+        return Err(NotificationError::Invalid("invalid kind".to_string()));
+    }
+
+    if data.user_ids.is_empty() || data.user_ids.len() > MAX_IDS {
+        return Err(NotificationError::Invalid(format!(
+            "has to go to 1 to {MAX_IDS} users"
+        )));
+    }
+
+    let payload = match data.payload {
+        serde_json::Value::Null => serde_json::json!({}),
+        payload @ serde_json::Value::Object(_) => payload,
+        _ => {
+            return Err(NotificationError::Invalid(
+                "payload has to be an object".to_string(),
+            ))
+        }
+    };
+
+    if payload.to_string().len() > PAYLOAD_MAX_BYTES {
+        return Err(NotificationError::Invalid(format!(
+            "payload is larger than {PAYLOAD_MAX_BYTES} bytes"
+        )));
+    }
+
+    let result = sqlx::query(INSERT_QUERY)
+        .bind(&data.user_ids)
+        .bind(&data.kind)
+        .bind(payload)
+        .bind(sender)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Kinds are 1..64 lower case latin letters, digits, `_`, `-`, `.` and `:`
+pub fn valid_kind(kind: &str) -> bool {
+    !kind.is_empty()
+        && kind.len() <= KIND_MAX_LEN
+        && kind
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.' | ':'))
+}
@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::presence::use_case,
+    shared::{
+        auth::extractor::{AuthUser, ServiceCaller},
+        context::Context,
+        utils::{bad_request_json, forbidden_json, internal_error_json, just_ok, ok},
+    },
+};
+
+use super::{
+    dto::{HeartbeatData, SessionData, UserPath},
+    error::PresenceError,
+};
+
+/// Scope the server manager needs to report players joining and leaving sessions
+const PRESENCE_WRITE_SCOPE: &str = "presence:write";
+
+pub async fn heartbeat(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Json(request): Json<HeartbeatData>,
+) -> impl IntoResponse {
+    let span = info_span!("presence_heartbeat");
+    let _guard = span.enter();
+
+    info!(event = "Presence heartbeat", user_id = %user.id, status = ?request.status);
+
+    match use_case::heartbeat(context.presence(), user.id, request) {
+        Ok(response) => ok(response),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn go_offline(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+) -> impl IntoResponse {
+    let span = info_span!("presence_offline");
+    let _guard = span.enter();
+
+    info!(event = "Request to go offline", user_id = %user.id);
+
+    context.presence().remove(user.id);
+
+    just_ok()
+}
+
+pub async fn friends_presence(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+) -> impl IntoResponse {
+    let span = info_span!("friends_presence");
+    let _guard = span.enter();
+
+    info!(event = "Request friends presence", user_id = %user.id);
+
+    let result = use_case::friends_presence(context.database(), context.presence(), user.id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(presence) => ok(serde_json::json!({
+            "presence": presence
+        })),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn set_session(
+    Extension(context): Extension<Context>,
+    ServiceCaller(service): ServiceCaller,
+    Path(UserPath { user_id }): Path<UserPath>,
+    Json(request): Json<SessionData>,
+) -> impl IntoResponse {
+    let span = info_span!("presence_set_session");
+    let _guard = span.enter();
+
+    if !service.has_scope(PRESENCE_WRITE_SCOPE) {
+        return forbidden_json(serde_json::json!({
+            "error": "Api key lacks the presence:write scope"
+        }));
+    }
+
+    info!(
+        event = "Player joined session",
+        service = service.name,
+        user_id = %user_id,
+        session_id = request.session_id,
+    );
+
+    match use_case::set_session(context.presence(), user_id, request.session_id) {
+        Ok(_) => just_ok(),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn clear_session(
+    Extension(context): Extension<Context>,
+    ServiceCaller(service): ServiceCaller,
+    Path(UserPath { user_id }): Path<UserPath>,
+    Query(request): Query<SessionData>,
+) -> impl IntoResponse {
+    let span = info_span!("presence_clear_session");
+    let _guard = span.enter();
+
+    if !service.has_scope(PRESENCE_WRITE_SCOPE) {
+        return forbidden_json(serde_json::json!({
+            "error": "Api key lacks the presence:write scope"
+        }));
+    }
+
+    info!(
+        event = "Player left session",
+        service = service.name,
+        user_id = %user_id,
+        session_id = request.session_id,
+    );
+
+    context
+        .presence()
+        .clear_session(user_id, &request.session_id);
+
+    just_ok()
+}
+
+fn error_response(err: PresenceError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        PresenceError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        _ => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+
+use crate::shared::services::presence::{Activity, Presence};
+
+#[derive(Debug, Deserialize)]
+pub struct HeartbeatData {
+    pub status: Activity,
+    pub lobby_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HeartbeatResponse {
+    pub presence: Presence,
+
+    /// The entry expires unless the next heartbeat comes before
+    pub expires_in_secs: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserPath {
+    pub user_id: Uuid,
+}
+
+/// Body of the set request and query of the clear request
+#[derive(Debug, Deserialize)]
+pub struct SessionData {
+    pub session_id: String,
+}
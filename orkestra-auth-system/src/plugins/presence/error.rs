@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PresenceError {
+    #[error("Lobby id is too long")]
+    InvalidLobbyId,
+
+    #[error("Invalid session id")]
+    InvalidSessionId,
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
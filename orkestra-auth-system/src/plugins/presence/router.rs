@@ -0,0 +1,16 @@
+use axum::{
+    routing::{get, put},
+    Router,
+};
+
+use super::controller::{clear_session, friends_presence, go_offline, heartbeat, set_session};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/me/presence", put(heartbeat).delete(go_offline))
+        .route("/me/friends/presence", get(friends_presence))
+        .route(
+            "/service/presence/:user_id/session",
+            put(set_session).delete(clear_session),
+        )
+}
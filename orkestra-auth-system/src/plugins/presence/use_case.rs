@@ -0,0 +1,64 @@
+use sqlx::types::Uuid;
+use tracing::Instrument;
+
+use crate::shared::{
+    database::Database,
+    services::presence::{Presence, PresenceStore},
+};
+
+use super::{
+    dto::{HeartbeatData, HeartbeatResponse},
+    error::PresenceError,
+};
+
+const ID_MAX_LEN: usize = 64;
+
+pub fn heartbeat(
+    presence: &PresenceStore,
+    user_id: Uuid,
+    data: HeartbeatData,
+) -> Result<HeartbeatResponse, PresenceError> {
+    if data
+        .lobby_id
+        .as_ref()
+        .is_some_and(|id| id.len() > ID_MAX_LEN)
+    {
+        return Err(PresenceError::InvalidLobbyId);
+    }
+
+    Ok(HeartbeatResponse {
+        presence: presence.heartbeat(user_id, data.status, data.lobby_id),
+        expires_in_secs: presence.ttl().as_secs(),
+    })
+}
+
+/// Presence of the user's friends who are online
+pub async fn friends_presence(
+    database: &Database,
+    presence: &PresenceStore,
+    user_id: Uuid,
+) -> Result<Vec<Presence>, PresenceError> {
+    const FRIENDS_QUERY: &str = "SELECT friend_id FROM friendships WHERE user_id = $1;";
+
+    let friend_ids: Vec<Uuid> = sqlx::query_scalar(FRIENDS_QUERY)
+        .bind(user_id)
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(presence.get_many(&friend_ids))
+}
+
+pub fn set_session(
+    presence: &PresenceStore,
+    user_id: Uuid,
+    session_id: String,
+) -> Result<(), PresenceError> {
+    if session_id.is_empty() || session_id.len() > ID_MAX_LEN {
+        return Err(PresenceError::InvalidSessionId);
+    }
+
+    presence.set_session(user_id, session_id);
+
+    Ok(())
+}
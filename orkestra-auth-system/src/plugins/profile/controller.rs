@@ -0,0 +1,93 @@
+use axum::{extract::Path, response::IntoResponse, Extension, Json};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::profile::use_case,
+    shared::{
+        auth::extractor::AuthUser,
+        context::Context,
+        project::Project,
+        utils::{bad_request_json, internal_error_json, not_found_json, ok},
+    },
+};
+
+use super::{
+    dto::{ProfileResponse, PublicProfileResponse, UpdateProfileData, UserPath},
+    error::ProfileError,
+};
+
+pub async fn get_me(Extension(context): Extension<Context>, user: AuthUser) -> impl IntoResponse {
+    let span = info_span!("get_me");
+    let _guard = span.enter();
+
+    info!(event = "Request own profile", user_id = %user.id);
+
+    let result = use_case::get_profile(context.database(), user.project_id, user.id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(profile) => ok(ProfileResponse::from(profile)),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn update_me(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Json(request): Json<UpdateProfileData>,
+) -> impl IntoResponse {
+    let span = info_span!("update_me");
+    let _guard = span.enter();
+
+    info!(event = "Request to update profile", user_id = %user.id);
+
+    let result = use_case::update_profile(context.database(), user.project_id, user.id, request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(profile) => {
+            info!(event = "Profile updated");
+
+            ok(ProfileResponse::from(profile))
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn get_user(
+    Extension(context): Extension<Context>,
+    project: Project,
+    Path(UserPath { id }): Path<UserPath>,
+) -> impl IntoResponse {
+    let span = info_span!("get_user");
+    let _guard = span.enter();
+
+    info!(event = "Request public profile", user_id = %id);
+
+    let result = use_case::get_profile(context.database(), project.id, id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(profile) => ok(PublicProfileResponse::from(profile)),
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: ProfileError) -> (axum::http::StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        ProfileError::NotFound => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        ProfileError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        _ => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+use super::models::Profile;
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProfileData {
+    pub display_name: Option<String>,
+
+    #[serde(default, deserialize_with = "nullable")]
+    pub avatar_url: Option<Option<String>>,
+
+    #[serde(default, deserialize_with = "nullable")]
+    pub locale: Option<Option<String>>,
+
+    #[serde(default, deserialize_with = "nullable")]
+    pub country: Option<Option<String>>,
+
+    pub custom: Option<serde_json::Value>,
+}
+
+/// Named so the route also matches under `/projects/:project`
+#[derive(Debug, Deserialize)]
+pub struct UserPath {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProfileResponse {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+    pub locale: Option<String>,
+    pub country: Option<String>,
+    pub custom: serde_json::Value,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicProfileResponse {
+    pub id: Uuid,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+    pub country: Option<String>,
+    pub custom: serde_json::Value,
+}
+
+impl From<Profile> for ProfileResponse {
+    fn from(value: Profile) -> Self {
+        Self {
+            id: value.user_id,
+            username: value.username,
+            display_name: value.display_name,
+            avatar_url: value.avatar_url,
+            locale: value.locale,
+            country: value.country,
+            custom: value.custom,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+impl From<Profile> for PublicProfileResponse {
+    fn from(value: Profile) -> Self {
+        Self {
+            id: value.user_id,
+            display_name: value.display_name,
+            avatar_url: value.avatar_url,
+            country: value.country,
+            custom: value.custom,
+        }
+    }
+}
+
+// Distinguishes an explicit `null` (clear the field) from a missing field (keep it)
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProfileError {
+    #[error("User not found")]
+    NotFound,
+
+    #[error("Invalid display name")]
+    InvalidDisplayName,
+
+    #[error("Invalid avatar url")]
+    InvalidAvatarUrl,
+
+    #[error("Invalid locale")]
+    InvalidLocale,
+
+    #[error("Invalid country code")]
+    InvalidCountry,
+
+    #[error("Custom fields must be a JSON object")]
+    InvalidCustomFields,
+
+    #[error("Custom fields exceed {0} bytes")]
+    CustomFieldsTooLarge(usize),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
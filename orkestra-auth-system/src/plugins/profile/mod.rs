@@ -0,0 +1,57 @@
+mod controller;
+mod dto;
+mod error;
+mod models;
+mod use_case;
+
+pub mod router;
+
+#[cfg(test)]
+mod tests {
+    use super::use_case::{valid_avatar_url, valid_country, valid_display_name, valid_locale};
+
+    #[test]
+    fn display_name_validation() {
+        assert!(valid_display_name("Captain Jack"));
+        assert!(valid_display_name("Мореход"));
+
+        assert!(!valid_display_name(""));
+        assert!(!valid_display_name("   "));
+        assert!(!valid_display_name(" padded"));
+        assert!(!valid_display_name("line\nbreak"));
+        assert!(!valid_display_name(&"a".repeat(33)));
+    }
+
+    #[test]
+    fn avatar_url_validation() {
+        assert!(valid_avatar_url("https://cdn.example.com/avatar.png"));
+        assert!(valid_avatar_url("http://example.com/a.jpg"));
+
+        assert!(!valid_avatar_url("ftp://example.com/a.jpg"));
+        assert!(!valid_avatar_url("javascript:alert(1)"));
+        assert!(!valid_avatar_url("https://"));
+        assert!(!valid_avatar_url("https://example.com/a b.png"));
+    }
+
+    #[test]
+    fn locale_validation() {
+        assert!(valid_locale("en"));
+        assert!(valid_locale("ru-RU"));
+        assert!(valid_locale("zh-Hans"));
+
+        assert!(!valid_locale(""));
+        assert!(!valid_locale("english"));
+        assert!(!valid_locale("en_US"));
+        assert!(!valid_locale("EN-us"));
+    }
+
+    #[test]
+    fn country_validation() {
+        assert!(valid_country("RU"));
+        assert!(valid_country("US"));
+
+        assert!(!valid_country("ru"));
+        assert!(!valid_country("RUS"));
+        assert!(!valid_country(""));
+    }
+}
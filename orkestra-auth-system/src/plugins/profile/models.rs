@@ -0,0 +1,14 @@
+use sqlx::{types::Uuid, FromRow};
+use time::OffsetDateTime;
+
+#[derive(Debug, FromRow)]
+pub struct Profile {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: String,
+    pub avatar_url: Option<String>,
+    pub locale: Option<String>,
+    pub country: Option<String>,
+    pub custom: serde_json::Value,
+    pub updated_at: OffsetDateTime,
+}
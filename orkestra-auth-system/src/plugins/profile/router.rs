@@ -0,0 +1,9 @@
+use axum::{routing::get, Router};
+
+use super::controller::{get_me, get_user, update_me};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/me", get(get_me).patch(update_me))
+        .route("/users/:id", get(get_user))
+}
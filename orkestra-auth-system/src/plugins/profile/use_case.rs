@@ -0,0 +1,197 @@
+use sqlx::{types::Uuid, Postgres, QueryBuilder};
+use tracing::{warn, Instrument};
+
+use crate::shared::{
+    audit::{self, AuditEvent},
+    database::Database,
+};
+
+use super::{dto::UpdateProfileData, error::ProfileError, models::Profile};
+
+const DISPLAY_NAME_MAX_LEN: usize = 32;
+const AVATAR_URL_MAX_LEN: usize = 512;
+const CUSTOM_FIELDS_MAX_SIZE: usize = 4096;
+
+pub async fn get_profile(
+    database: &Database,
+    project_id: Uuid,
+    id: Uuid,
+) -> Result<Profile, ProfileError> {
+    const PROFILE_QUERY: &str = "SELECT p.user_id, u.username, p.display_name, p.avatar_url, \
+        p.locale, p.country, p.custom, p.updated_at \
+        FROM profiles p JOIN users u ON u.id = p.user_id \
+        WHERE p.user_id = $1 AND u.project_id = $2 AND u.deleted_at IS NULL;";
+
+    sqlx::query_as(PROFILE_QUERY)
+        .bind(id)
+        .bind(project_id)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?
+        .ok_or(ProfileError::NotFound)
+}
+
+pub async fn update_profile(
+    database: &Database,
+    project_id: Uuid,
+    id: Uuid,
+    data: UpdateProfileData,
+) -> Result<Profile, ProfileError> {
+    validate(&data)?;
+
+    let fields = updated_fields(&data);
+
+    let mut query = QueryBuilder::<Postgres>::new("UPDATE profiles SET updated_at = now()");
+
+    if let Some(display_name) = data.display_name {
+        query.push(", display_name = ").push_bind(display_name);
+    }
+
+    if let Some(avatar_url) = data.avatar_url {
+        query.push(", avatar_url = ").push_bind(avatar_url);
+    }
+
+    if let Some(locale) = data.locale {
+        query.push(", locale = ").push_bind(locale);
+    }
+
+    if let Some(country) = data.country {
+        query.push(", country = ").push_bind(country);
+    }
+
+    if let Some(custom) = data.custom {
+        query.push(", custom = ").push_bind(custom);
+    }
+
+    query.push(" WHERE user_id = ").push_bind(id);
+
+    let result = query
+        .build()
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ProfileError::NotFound);
+    }
+
+    if let Err(err) = audit::record(
+        database.as_ref(),
+        id,
+        AuditEvent::ProfileUpdated,
+        serde_json::json!({ "fields": fields }),
+    )
+    .in_current_span()
+    .await
+    {
+        warn!(event = "Couldn't record audit event", error = %err);
+    }
+
+    get_profile(database, project_id, id)
+        .in_current_span()
+        .await
+}
+
+fn updated_fields(data: &UpdateProfileData) -> Vec<&'static str> {
+    [
+        ("display_name", data.display_name.is_some()),
+        ("avatar_url", data.avatar_url.is_some()),
+        ("locale", data.locale.is_some()),
+        ("country", data.country.is_some()),
+        ("custom", data.custom.is_some()),
+    ]
+    .into_iter()
+    .filter_map(|(field, updated)| updated.then_some(field))
+    .collect()
+}
+
+fn validate(data: &UpdateProfileData) -> Result<(), ProfileError> {
+    if let Some(display_name) = &data.display_name {
+        if !valid_display_name(display_name) {
+            return Err(ProfileError::InvalidDisplayName);
+        }
+    }
+
+    if let Some(Some(avatar_url)) = &data.avatar_url {
+        if !valid_avatar_url(avatar_url) {
+            return Err(ProfileError::InvalidAvatarUrl);
+        }
+    }
+
+    if let Some(Some(locale)) = &data.locale {
+        if !valid_locale(locale) {
+            return Err(ProfileError::InvalidLocale);
+        }
+    }
+
+    if let Some(Some(country)) = &data.country {
+        if !valid_country(country) {
+            return Err(ProfileError::InvalidCountry);
+        }
+    }
+
+    if let Some(custom) = &data.custom {
+        if !custom.is_object() {
+            return Err(ProfileError::InvalidCustomFields);
+        }
+
+        if custom.to_string().len() > CUSTOM_FIELDS_MAX_SIZE {
+            return Err(ProfileError::CustomFieldsTooLarge(CUSTOM_FIELDS_MAX_SIZE));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn valid_display_name(display_name: &str) -> bool {
+    let len = display_name.chars().count();
+
+    (1..=DISPLAY_NAME_MAX_LEN).contains(&len)
+        && display_name.trim() == display_name
+        && !display_name.chars().any(char::is_control)
+}
+
+pub fn valid_avatar_url(url: &str) -> bool {
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return false;
+    };
+
+    url.len() <= AVATAR_URL_MAX_LEN
+        && !rest.is_empty()
+        && !rest.starts_with('/')
+        && url.chars().all(|c| c.is_ascii_graphic())
+}
+
+/// Accepts a language subtag optionally followed by a region or script subtag,
+/// e.g. `en`, `ru-RU`, `zh-Hans`
+pub fn valid_locale(locale: &str) -> bool {
+    let mut parts = locale.split('-');
+
+    let Some(language) = parts.next() else {
+        return false;
+    };
+
+    let valid_language =
+        (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase());
+
+    let valid_subtag = match parts.next() {
+        None => true,
+        Some(subtag) if subtag.len() == 2 => subtag.chars().all(|c| c.is_ascii_uppercase()),
+        Some(subtag) if subtag.len() == 4 => {
+            let mut chars = subtag.chars();
+
+            chars.next().is_some_and(|c| c.is_ascii_uppercase())
+                && chars.all(|c| c.is_ascii_lowercase())
+        }
+        Some(_) => false,
+    };
+
+    valid_language && valid_subtag && parts.next().is_none()
+}
+
+pub fn valid_country(country: &str) -> bool {
+    country.len() == 2 && country.chars().all(|c| c.is_ascii_uppercase())
+}
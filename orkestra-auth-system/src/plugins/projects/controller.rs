@@ -0,0 +1,110 @@
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::projects::use_case,
+    shared::{
+        auth::extractor::AdminCaller,
+        context::Context,
+        utils::{bad_request_json, created, internal_error_json, not_found_json, ok},
+    },
+};
+
+use super::{
+    dto::{CreateProjectData, ProjectPath, UpdateProjectData},
+    error::ProjectError,
+};
+
+pub async fn create_project(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    Json(request): Json<CreateProjectData>,
+) -> impl IntoResponse {
+    let span = info_span!("create_project");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to create project",
+        slug = request.slug,
+        admin = admin.actor(),
+    );
+
+    let result = use_case::create_project(context.database(), request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(project) => {
+            info!(event = "Project created", id = %project.id);
+
+            created(project)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn list_projects(
+    Extension(context): Extension<Context>,
+    _admin: AdminCaller,
+) -> impl IntoResponse {
+    let span = info_span!("list_projects");
+    let _guard = span.enter();
+
+    info!(event = "Request to list projects");
+
+    let result = use_case::list_projects(context.database())
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(projects) => ok(serde_json::json!({
+            "projects": projects
+        })),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn update_project(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    Path(ProjectPath { slug }): Path<ProjectPath>,
+    Json(request): Json<UpdateProjectData>,
+) -> impl IntoResponse {
+    let span = info_span!("update_project");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to update project",
+        slug = slug,
+        admin = admin.actor()
+    );
+
+    let result = use_case::update_project(context.database(), &slug, request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(project) => {
+            info!(event = "Project updated", id = %project.id);
+
+            ok(project)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: ProjectError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        ProjectError::NotFound(_) => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        ProjectError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        _ => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
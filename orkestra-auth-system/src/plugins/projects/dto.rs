@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+
+use crate::shared::project::Project;
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectPath {
+    pub slug: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateProjectData {
+    pub slug: String,
+    pub name: String,
+    pub vk_game_id: Option<String>,
+    pub vk_gas_secret: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateProjectData {
+    pub name: Option<String>,
+    pub vk_game_id: Option<String>,
+    pub vk_gas_secret: Option<String>,
+}
+
+/// Project view without provider secrets
+#[derive(Debug, Serialize)]
+pub struct ProjectResponse {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub has_vk_credentials: bool,
+}
+
+impl From<Project> for ProjectResponse {
+    fn from(project: Project) -> Self {
+        Self {
+            has_vk_credentials: project.vk_credentials().is_some(),
+            id: project.id,
+            slug: project.slug,
+            name: project.name,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ProjectError {
+    #[error("Invalid project slug")]
+    InvalidSlug,
+
+    #[error("Invalid project name")]
+    InvalidName,
+
+    #[error("VK game id and secret must be set together")]
+    IncompleteVkCredentials,
+
+    #[error("Project already exists")]
+    AlreadyExists,
+
+    #[error("Project not found: {0}")]
+    NotFound(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,25 @@
+mod controller;
+mod dto;
+mod error;
+mod use_case;
+
+pub mod router;
+
+#[cfg(test)]
+mod tests {
+    use super::use_case::valid_slug;
+
+    #[test]
+    fn slug_validation() {
+        assert!(valid_slug("default"));
+        assert!(valid_slug("space-race"));
+        assert!(valid_slug("game2"));
+
+        assert!(!valid_slug(""));
+        assert!(!valid_slug("a"));
+        assert!(!valid_slug("-game"));
+        assert!(!valid_slug("Game"));
+        assert!(!valid_slug("space_race"));
+        assert!(!valid_slug(&"a".repeat(33)));
+    }
+}
@@ -0,0 +1,12 @@
+use axum::{
+    routing::{get, patch},
+    Router,
+};
+
+use super::controller::{create_project, list_projects, update_project};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/admin/projects", get(list_projects).post(create_project))
+        .route("/admin/projects/:slug", patch(update_project))
+}
@@ -0,0 +1,104 @@
+use tracing::Instrument;
+
+use crate::shared::{database::Database, project::Project};
+
+use super::{
+    dto::{CreateProjectData, ProjectResponse, UpdateProjectData},
+    error::ProjectError,
+};
+
+const SLUG_MIN_LEN: usize = 2;
+const SLUG_MAX_LEN: usize = 32;
+const NAME_MAX_LEN: usize = 64;
+
+pub async fn create_project(
+    database: &Database,
+    data: CreateProjectData,
+) -> Result<ProjectResponse, ProjectError> {
+    const INSERT_QUERY: &str =
+        "INSERT INTO projects (slug, name, vk_game_id, vk_gas_secret) VALUES ($1, $2, $3, $4) \
+        ON CONFLICT DO NOTHING RETURNING id, slug, name, vk_game_id, vk_gas_secret;";
+
+    if !valid_slug(&data.slug) {
+        return Err(ProjectError::InvalidSlug);
+    }
+
+    let name = valid_name(&data.name)?;
+
+    if data.vk_game_id.is_some() != data.vk_gas_secret.is_some() {
+        return Err(ProjectError::IncompleteVkCredentials);
+    }
+
+    let project: Option<Project> = sqlx::query_as(INSERT_QUERY)
+        .bind(&data.slug)
+        .bind(name)
+        .bind(&data.vk_game_id)
+        .bind(&data.vk_gas_secret)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    project
+        .map(ProjectResponse::from)
+        .ok_or(ProjectError::AlreadyExists)
+}
+
+pub async fn list_projects(database: &Database) -> Result<Vec<ProjectResponse>, ProjectError> {
+    const LIST_QUERY: &str =
+        "SELECT id, slug, name, vk_game_id, vk_gas_secret FROM projects ORDER BY created_at;";
+
+    let projects: Vec<Project> = sqlx::query_as(LIST_QUERY)
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(projects.into_iter().map(ProjectResponse::from).collect())
+}
+
+pub async fn update_project(
+    database: &Database,
+    slug: &str,
+    data: UpdateProjectData,
+) -> Result<ProjectResponse, ProjectError> {
+    const UPDATE_QUERY: &str = "UPDATE projects SET name = coalesce($2, name), \
+        vk_game_id = coalesce($3, vk_game_id), vk_gas_secret = coalesce($4, vk_gas_secret) \
+        WHERE slug = $1 RETURNING id, slug, name, vk_game_id, vk_gas_secret;";
+
+    let name = data.name.as_deref().map(valid_name).transpose()?;
+
+    if data.vk_game_id.is_some() != data.vk_gas_secret.is_some() {
+        return Err(ProjectError::IncompleteVkCredentials);
+    }
+
+    let project: Option<Project> = sqlx::query_as(UPDATE_QUERY)
+        .bind(slug)
+        .bind(name)
+        .bind(&data.vk_game_id)
+        .bind(&data.vk_gas_secret)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    project
+        .map(ProjectResponse::from)
+        .ok_or_else(|| ProjectError::NotFound(slug.to_string()))
+}
+
+/// Slugs are 2..32 lower case latin letters, digits and dashes, not starting with a dash
+pub fn valid_slug(slug: &str) -> bool {
+    (SLUG_MIN_LEN..=SLUG_MAX_LEN).contains(&slug.len())
+        && !slug.starts_with('-')
+        && slug
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '-'))
+}
+
+fn valid_name(name: &str) -> Result<&str, ProjectError> {
+    let name = name.trim();
+
+    if name.is_empty() || name.chars().count() > NAME_MAX_LEN {
+        return Err(ProjectError::InvalidName);
+    }
+
+    Ok(name)
+}
@@ -0,0 +1,210 @@
+use axum::{
+    extract::{Path, Query},
+    http::{
+        header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::remote_config::use_case,
+    shared::{
+        auth::extractor::{AdminCaller, AuthUser},
+        client_version::ClientVersion,
+        context::Context,
+        pagination::PageQuery,
+        project::Project,
+        utils::{bad_request_json, created, internal_error_json, just_ok, not_found_json, ok},
+    },
+};
+
+use super::{
+    dto::{PublishData, RollbackData, VersionPath},
+    error::RemoteConfigError,
+    models::Target,
+};
+
+/// Clients may reuse the config for a minute before revalidating with the ETag
+const CACHE_CONTROL_VALUE: &str = "private, max-age=60";
+
+pub async fn get_config(
+    Extension(context): Extension<Context>,
+    project: Project,
+    user: Option<AuthUser>,
+    client_version: Option<ClientVersion>,
+    headers: HeaderMap,
+) -> Response {
+    let span = info_span!("get_config");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request remote config",
+        project = project.slug,
+        user_id = ?user.map(|user| user.id),
+        client_version = ?client_version.as_ref().map(ToString::to_string),
+    );
+
+    let target = Target {
+        user_id: user.map(|user| user.id),
+        role: user.map(|user| user.role),
+        client_version,
+    };
+
+    let result = use_case::client_config(context.database(), project.id, &target)
+        .in_current_span()
+        .await;
+
+    let (config, etag) = match result {
+        Ok(result) => result,
+        Err(err) => return error_response(err).into_response(),
+    };
+
+    let cache_headers = [
+        (ETAG, etag.clone()),
+        (CACHE_CONTROL, CACHE_CONTROL_VALUE.to_string()),
+    ];
+
+    let not_modified = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value
+                .split(',')
+                .any(|tag| tag.trim() == etag || tag.trim() == "*")
+        });
+
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    (cache_headers, ok(config)).into_response()
+}
+
+pub async fn list_versions(
+    Extension(context): Extension<Context>,
+    _admin: AdminCaller,
+    project: Project,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_config_versions");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request remote config versions",
+        project = project.slug
+    );
+
+    let result = use_case::list_versions(context.database(), project.id, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(versions) => ok(versions),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn get_version(
+    Extension(context): Extension<Context>,
+    _admin: AdminCaller,
+    project: Project,
+    Path(VersionPath { version }): Path<VersionPath>,
+) -> impl IntoResponse {
+    let span = info_span!("get_config_version");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request remote config version",
+        project = project.slug,
+        version = version
+    );
+
+    let result = use_case::get_version(context.database(), project.id, version)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(version) => ok(version),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn publish(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    project: Project,
+    Json(request): Json<PublishData>,
+) -> impl IntoResponse {
+    let span = info_span!("publish_config");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to publish remote config",
+        project = project.slug,
+        admin = admin.actor(),
+    );
+
+    let result = use_case::publish(context.database(), project.id, admin.user_id(), request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(version) => {
+            info!(event = "Remote config published", version = version);
+
+            created(serde_json::json!({
+                "version": version
+            }))
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn rollback(
+    Extension(context): Extension<Context>,
+    admin: AdminCaller,
+    project: Project,
+    Json(request): Json<RollbackData>,
+) -> impl IntoResponse {
+    let span = info_span!("rollback_config");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to roll back remote config",
+        project = project.slug,
+        version = request.version,
+        admin = admin.actor(),
+    );
+
+    let result = use_case::rollback(context.database(), project.id, request.version)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => {
+            info!(event = "Remote config rolled back");
+
+            just_ok()
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: RemoteConfigError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        RemoteConfigError::VersionNotFound(_) => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        RemoteConfigError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        RemoteConfigError::InvalidDocument(_) => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
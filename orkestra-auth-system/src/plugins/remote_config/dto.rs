@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+#[derive(Debug, Deserialize)]
+pub struct PublishData {
+    pub document: Value,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RollbackData {
+    pub version: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VersionPath {
+    pub version: i32,
+}
+
+/// Config evaluated for the calling client, version 0 means nothing is published
+#[derive(Debug, Serialize)]
+pub struct ClientConfigResponse {
+    pub version: i32,
+    pub values: Map<String, Value>,
+}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RemoteConfigError {
+    #[error("Invalid config document: {0}")]
+    InvalidDocument(String),
+
+    #[error("Config version not found: {0}")]
+    VersionNotFound(i32),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
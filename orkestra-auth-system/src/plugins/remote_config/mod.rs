@@ -0,0 +1,90 @@
+mod controller;
+mod dto;
+mod error;
+mod models;
+mod use_case;
+
+pub mod router;
+
+#[cfg(test)]
+mod tests {
+    use sqlx::types::Uuid;
+
+    use crate::shared::auth::role::Role;
+
+    use super::{
+        models::{rollout_bucket, ConfigDocument, Target},
+        use_case::parse_document,
+    };
+
+    fn document() -> ConfigDocument {
+        parse_document(serde_json::json!({
+            "values": { "speed": 10, "new_shop": false },
+            "rules": [
+                {
+                    "name": "fast_admins",
+                    "when": { "roles": ["admin"] },
+                    "values": { "speed": 20 }
+                },
+                {
+                    "name": "shop_for_new_clients",
+                    "when": { "min_client_version": "1.2" },
+                    "values": { "new_shop": true }
+                }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn rules_override_defaults() {
+        let document = document();
+
+        let anonymous = document.evaluate(&Target::default());
+        assert_eq!(anonymous["speed"], 10);
+        assert_eq!(anonymous["new_shop"], false);
+
+        let admin = document.evaluate(&Target {
+            role: Some(Role::Admin),
+            client_version: Some("1.2.1".parse().unwrap()),
+            ..Default::default()
+        });
+        assert_eq!(admin["speed"], 20);
+        assert_eq!(admin["new_shop"], true);
+
+        let old_player = document.evaluate(&Target {
+            role: Some(Role::Player),
+            client_version: Some("1.1.9".parse().unwrap()),
+            ..Default::default()
+        });
+        assert_eq!(old_player["speed"], 10);
+        assert_eq!(old_player["new_shop"], false);
+    }
+
+    #[test]
+    fn rollout_is_stable_and_bounded() {
+        let user_id = Uuid::from_u128(42);
+
+        assert_eq!(
+            rollout_bucket("rule", user_id),
+            rollout_bucket("rule", user_id)
+        );
+        assert!((0..1000).all(|i| rollout_bucket("rule", Uuid::from_u128(i)) < 100));
+    }
+
+    #[test]
+    fn invalid_documents_are_rejected() {
+        assert!(parse_document(serde_json::json!({ "value": {} })).is_err());
+        assert!(parse_document(serde_json::json!({
+            "rules": [{ "name": "a", "when": { "percentage": 101 }, "values": {} }]
+        }))
+        .is_err());
+        assert!(parse_document(serde_json::json!({
+            "rules": [
+                { "name": "a", "values": {} },
+                { "name": "a", "values": {} }
+            ]
+        }))
+        .is_err());
+    }
+}
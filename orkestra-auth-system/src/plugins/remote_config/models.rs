@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use sqlx::{types::Uuid, FromRow};
+use time::OffsetDateTime;
+
+use crate::shared::{auth::role::Role, client_version::ClientVersion};
+
+/// Published config: default values plus rules overriding them for matching
+/// clients. Rules are applied in order, later ones win.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigDocument {
+    #[serde(default)]
+    pub values: Map<String, Value>,
+    #[serde(default)]
+    pub rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    /// Also salts the percentage rollout, so rules roll out to different players
+    pub name: String,
+    #[serde(default)]
+    pub when: Conditions,
+    pub values: Map<String, Value>,
+}
+
+/// All set conditions have to match
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Conditions {
+    pub roles: Option<Vec<Role>>,
+    pub min_client_version: Option<ClientVersion>,
+    pub max_client_version: Option<ClientVersion>,
+    /// Share of players getting the rule, 0..100
+    pub percentage: Option<u8>,
+}
+
+/// Who the config is evaluated for, anonymous clients only match rules
+/// without role and percentage conditions
+#[derive(Debug, Clone, Default)]
+pub struct Target {
+    pub user_id: Option<Uuid>,
+    pub role: Option<Role>,
+    pub client_version: Option<ClientVersion>,
+}
+
+impl ConfigDocument {
+    pub fn evaluate(&self, target: &Target) -> Map<String, Value> {
+        let mut values = self.values.clone();
+
+        for rule in self.rules.iter().filter(|rule| rule.matches(target)) {
+            values.extend(rule.values.clone());
+        }
+
+        values
+    }
+}
+
+impl Rule {
+    fn matches(&self, target: &Target) -> bool {
+        let when = &self.when;
+
+        let role = when.roles.as_ref().map_or(true, |roles| {
+            target.role.is_some_and(|r| roles.contains(&r))
+        });
+
+        let min_version = when.min_client_version.as_ref().map_or(true, |min| {
+            target.client_version.as_ref().is_some_and(|v| v >= min)
+        });
+
+        let max_version = when.max_client_version.as_ref().map_or(true, |max| {
+            target.client_version.as_ref().is_some_and(|v| v <= max)
+        });
+
+        let percentage = when.percentage.map_or(true, |percentage| {
+            target
+                .user_id
+                .is_some_and(|user_id| rollout_bucket(&self.name, user_id) < percentage)
+        });
+
+        role && min_version && max_version && percentage
+    }
+}
+
+/// Stable 0..100 bucket of the player for the rule
+pub fn rollout_bucket(rule: &str, user_id: Uuid) -> u8 {
+    let digest = Sha256::digest(format!("{rule}:{user_id}").as_bytes());
+    let value = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+
+    (value % 100) as u8
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct ConfigVersion {
+    pub version: i32,
+    pub document: serde_json::Value,
+    pub comment: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub active: bool,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
@@ -0,0 +1,17 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use super::controller::{get_config, get_version, list_versions, publish, rollback};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/config", get(get_config))
+        .route(
+            "/admin/remote_config/versions",
+            get(list_versions).post(publish),
+        )
+        .route("/admin/remote_config/versions/:version", get(get_version))
+        .route("/admin/remote_config/rollback", post(rollback))
+}
@@ -0,0 +1,248 @@
+use std::collections::HashSet;
+
+use sha2::{Digest, Sha256};
+use sqlx::types::Uuid;
+use tracing::Instrument;
+
+use crate::shared::{
+    database::Database,
+    pagination::{Page, PageQuery},
+};
+
+use super::{
+    dto::{ClientConfigResponse, PublishData},
+    error::RemoteConfigError,
+    models::{ConfigDocument, ConfigVersion, Target},
+};
+
+const DOCUMENT_MAX_BYTES: usize = 256 * 1024;
+const COMMENT_MAX_LEN: usize = 256;
+
+/// Evaluates the active config for the client, returns it with its ETag
+pub async fn client_config(
+    database: &Database,
+    project_id: Uuid,
+    target: &Target,
+) -> Result<(ClientConfigResponse, String), RemoteConfigError> {
+    const ACTIVE_QUERY: &str = "SELECT v.version, v.document FROM remote_config_active a \
+        JOIN remote_config_versions v ON v.project_id = a.project_id AND v.version = a.version \
+        WHERE a.project_id = $1;";
+
+    let active: Option<(i32, serde_json::Value)> = sqlx::query_as(ACTIVE_QUERY)
+        .bind(project_id)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    let response = match active {
+        Some((version, document)) => ClientConfigResponse {
+            version,
+            values: parse_document(document)?.evaluate(target),
+        },
+        None => ClientConfigResponse {
+            version: 0,
+            values: Default::default(),
+        },
+    };
+
+    let etag = etag(&response);
+
+    Ok((response, etag))
+}
+
+pub async fn list_versions(
+    database: &Database,
+    project_id: Uuid,
+    page: &PageQuery,
+) -> Result<Page<ConfigVersion>, RemoteConfigError> {
+    const LIST_QUERY: &str = "SELECT v.version, v.document, v.comment, v.created_by, \
+        v.created_at, a.version IS NOT NULL AS active FROM remote_config_versions v \
+        LEFT JOIN remote_config_active a ON a.project_id = v.project_id AND a.version = v.version \
+        WHERE v.project_id = $1 ORDER BY v.version DESC LIMIT $2 OFFSET $3;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(project_id)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+pub async fn get_version(
+    database: &Database,
+    project_id: Uuid,
+    version: i32,
+) -> Result<ConfigVersion, RemoteConfigError> {
+    const VERSION_QUERY: &str = "SELECT v.version, v.document, v.comment, v.created_by, \
+        v.created_at, a.version IS NOT NULL AS active FROM remote_config_versions v \
+        LEFT JOIN remote_config_active a ON a.project_id = v.project_id AND a.version = v.version \
+        WHERE v.project_id = $1 AND v.version = $2;";
+
+    sqlx::query_as(VERSION_QUERY)
+        .bind(project_id)
+        .bind(version)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?
+        .ok_or(RemoteConfigError::VersionNotFound(version))
+}
+
+/// Stores the document as the next version and makes it active
+pub async fn publish(
+    database: &Database,
+    project_id: Uuid,
+    created_by: Option<Uuid>,
+    data: PublishData,
+) -> Result<i32, RemoteConfigError> {
+    const LOCK_PROJECT_QUERY: &str = "SELECT id FROM projects WHERE id = $1 FOR NO KEY UPDATE;";
+    const INSERT_QUERY: &str =
+        "INSERT INTO remote_config_versions (project_id, version, document, comment, created_by) \
+        SELECT $1, coalesce(max(version), 0) + 1, $2, $3, $4 \
+        FROM remote_config_versions WHERE project_id = $1 RETURNING version;";
+
+    if data.document.to_string().len() > DOCUMENT_MAX_BYTES {
+        return Err(RemoteConfigError::InvalidDocument(format!(
+            "larger than {DOCUMENT_MAX_BYTES} bytes"
+        )));
+    }
+
+    let comment = data
+        .comment
+        .map(|comment| comment.trim().to_string())
+        .filter(|comment| !comment.is_empty());
+
+    if comment
+        .as_ref()
+        .is_some_and(|comment| comment.chars().count() > COMMENT_MAX_LEN)
+    {
+        return Err(RemoteConfigError::InvalidDocument(
+            "comment is too long".to_string(),
+        ));
+    }
+
+    // Stored normalized, so defaults are visible when the version is inspected
+    let document = serde_json::to_value(parse_document(data.document)?)
+        .map_err(|err| RemoteConfigError::InvalidDocument(err.to_string()))?;
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    // Concurrent publishes would otherwise pick the same version number
+    sqlx::query(LOCK_PROJECT_QUERY)
+        .bind(project_id)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    let version: i32 = sqlx::query_scalar(INSERT_QUERY)
+        .bind(project_id)
+        .bind(document)
+        .bind(comment)
+        .bind(created_by)
+        .fetch_one(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    activate(&mut transaction, project_id, version).await?;
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(version)
+}
+
+/// Makes an already published version active again
+pub async fn rollback(
+    database: &Database,
+    project_id: Uuid,
+    version: i32,
+) -> Result<(), RemoteConfigError> {
+    const EXISTS_QUERY: &str = "SELECT EXISTS (SELECT 1 FROM remote_config_versions \
+        WHERE project_id = $1 AND version = $2);";
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    let exists: bool = sqlx::query_scalar(EXISTS_QUERY)
+        .bind(project_id)
+        .bind(version)
+        .fetch_one(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    if !exists {
+        return Err(RemoteConfigError::VersionNotFound(version));
+    }
+
+    activate(&mut transaction, project_id, version).await?;
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(())
+}
+
+async fn activate(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    project_id: Uuid,
+    version: i32,
+) -> Result<(), RemoteConfigError> {
+    const ACTIVATE_QUERY: &str =
+        "INSERT INTO remote_config_active (project_id, version) VALUES ($1, $2) \
+        ON CONFLICT (project_id) DO UPDATE SET version = excluded.version, activated_at = now();";
+
+    sqlx::query(ACTIVATE_QUERY)
+        .bind(project_id)
+        .bind(version)
+        .execute(&mut **transaction)
+        .in_current_span()
+        .await?;
+
+    Ok(())
+}
+
+pub fn parse_document(value: serde_json::Value) -> Result<ConfigDocument, RemoteConfigError> {
+    let document: ConfigDocument = serde_json::from_value(value)
+        .map_err(|err| RemoteConfigError::InvalidDocument(err.to_string()))?;
+
+    let mut names = HashSet::new();
+
+    for rule in &document.rules {
+        let invalid = |reason: &str| {
+            RemoteConfigError::InvalidDocument(format!("rule `{}` {reason}", rule.name))
+        };
+
+        if rule.name.trim().is_empty() {
+            return Err(invalid("has an empty name"));
+        }
+
+        if !names.insert(rule.name.as_str()) {
+            return Err(invalid("is defined twice"));
+        }
+
+        if rule
+            .when
+            .percentage
+            .is_some_and(|percentage| percentage > 100)
+        {
+            return Err(invalid("has percentage above 100"));
+        }
+
+        if let (Some(min), Some(max)) =
+            (&rule.when.min_client_version, &rule.when.max_client_version)
+        {
+            if min > max {
+                return Err(invalid("has min_client_version above max_client_version"));
+            }
+        }
+    }
+
+    Ok(document)
+}
+
+/// Strong ETag of the evaluated config, differs per targeting outcome
+fn etag(response: &ClientConfigResponse) -> String {
+    let body = serde_json::to_string(&response.values).unwrap_or_default();
+    let digest = Sha256::digest(format!("{}:{body}", response.version).as_bytes());
+
+    format!("\"{}\"", &hex::encode(digest)[..32])
+}
@@ -0,0 +1,202 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::reports::use_case,
+    shared::{
+        auth::extractor::{AuthUser, ModeratorCaller},
+        context::Context,
+        pagination::PageQuery,
+        project::Project,
+        utils::{
+            bad_request_json, conflict_json, created, internal_error_json, not_found_json, ok,
+        },
+    },
+};
+
+use super::{
+    dto::{BanData, CreateReportData, ReportFilter, ReportPath, SetStatusData},
+    error::ReportError,
+};
+
+pub async fn create_report(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Json(request): Json<CreateReportData>,
+) -> impl IntoResponse {
+    let span = info_span!("create_report");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to report user",
+        reporter_id = %user.id,
+        target_id = %request.target_id,
+        category = request.category.as_str(),
+        session_id = ?request.session_id,
+    );
+
+    let result = use_case::create_report(context.database(), user.project_id, user.id, request)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(id) => {
+            info!(event = "Report created", report_id = %id);
+
+            created(serde_json::json!({
+                "id": id
+            }))
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn list_reports(
+    Extension(context): Extension<Context>,
+    moderator: ModeratorCaller,
+    project: Project,
+    Query(filter): Query<ReportFilter>,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_reports");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request moderation queue",
+        project = project.slug,
+        status = ?filter.status,
+        moderator = moderator.actor(),
+    );
+
+    let result = use_case::list_reports(context.database(), project.id, &filter, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(reports) => ok(reports),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn get_report(
+    Extension(context): Extension<Context>,
+    moderator: ModeratorCaller,
+    project: Project,
+    Path(ReportPath { id }): Path<ReportPath>,
+) -> impl IntoResponse {
+    let span = info_span!("get_report");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request report",
+        report_id = %id,
+        moderator = moderator.actor(),
+    );
+
+    let result = use_case::get_report(context.database(), project.id, id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(report) => ok(report),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn set_status(
+    Extension(context): Extension<Context>,
+    moderator: ModeratorCaller,
+    project: Project,
+    Path(ReportPath { id }): Path<ReportPath>,
+    Json(request): Json<SetStatusData>,
+) -> impl IntoResponse {
+    let span = info_span!("set_report_status");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to change report status",
+        report_id = %id,
+        status = request.status.as_str(),
+        moderator = moderator.actor(),
+    );
+
+    let result = use_case::set_status(
+        context.database(),
+        project.id,
+        id,
+        moderator.user_id(),
+        request,
+    )
+    .in_current_span()
+    .await;
+
+    match result {
+        Ok(report) => ok(report),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn ban_from_report(
+    Extension(context): Extension<Context>,
+    moderator: ModeratorCaller,
+    project: Project,
+    Path(ReportPath { id }): Path<ReportPath>,
+    Json(request): Json<BanData>,
+) -> impl IntoResponse {
+    let span = info_span!("ban_from_report");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to ban reported user",
+        report_id = %id,
+        duration_secs = ?request.duration_secs,
+        moderator = moderator.actor(),
+    );
+
+    let result = use_case::ban_from_report(
+        context.database(),
+        project.id,
+        id,
+        moderator.user_id(),
+        request,
+    )
+    .in_current_span()
+    .await;
+
+    match result {
+        Ok(ban) => {
+            info!(event = "User banned", user_id = %ban.user_id, ban_id = %ban.id);
+
+            context.presence().remove(ban.user_id);
+
+            created(ban)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: ReportError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        ReportError::UnknownUser | ReportError::NotFound => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        ReportError::AlreadyReported
+        | ReportError::AlreadyBanned
+        | ReportError::InvalidTransition(..) => conflict_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        ReportError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        ReportError::SelfTarget | ReportError::Invalid(_) => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
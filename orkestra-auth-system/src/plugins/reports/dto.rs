@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportCategory {
+    Cheating,
+    Abuse,
+    Spam,
+    InappropriateName,
+    Other,
+}
+
+impl ReportCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportCategory::Cheating => "cheating",
+            ReportCategory::Abuse => "abuse",
+            ReportCategory::Spam => "spam",
+            ReportCategory::InappropriateName => "inappropriate_name",
+            ReportCategory::Other => "other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportStatus {
+    Open,
+    InReview,
+    Actioned,
+    Dismissed,
+}
+
+impl ReportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportStatus::Open => "open",
+            ReportStatus::InReview => "in_review",
+            ReportStatus::Actioned => "actioned",
+            ReportStatus::Dismissed => "dismissed",
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, ReportStatus::Actioned | ReportStatus::Dismissed)
+    }
+
+    /// Open and in review reports move between each other or get resolved,
+    /// resolved ones are final
+    pub fn can_become(&self, next: ReportStatus) -> bool {
+        match self {
+            ReportStatus::Open => next != ReportStatus::Open,
+            ReportStatus::InReview => next != ReportStatus::InReview,
+            ReportStatus::Actioned | ReportStatus::Dismissed => false,
+        }
+    }
+}
+
+impl FromStr for ReportStatus {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(ReportStatus::Open),
+            "in_review" => Ok(ReportStatus::InReview),
+            "actioned" => Ok(ReportStatus::Actioned),
+            "dismissed" => Ok(ReportStatus::Dismissed),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportPath {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReportFilter {
+    pub status: Option<ReportStatus>,
+    pub target_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateReportData {
+    pub target_id: Uuid,
+    /// Server manager session the report comes from
+    pub session_id: Option<String>,
+    pub category: ReportCategory,
+    #[serde(default)]
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetStatusData {
+    pub status: ReportStatus,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BanData {
+    pub reason: String,
+    /// Permanent ban when absent
+    pub duration_secs: Option<i64>,
+}
@@ -0,0 +1,30 @@
+use thiserror::Error;
+
+use super::dto::ReportStatus;
+
+#[derive(Error, Debug)]
+pub enum ReportError {
+    #[error("User not found")]
+    UnknownUser,
+
+    #[error("You can't report yourself")]
+    SelfTarget,
+
+    #[error("Invalid report: {0}")]
+    Invalid(String),
+
+    #[error("You already have an unresolved report against this user")]
+    AlreadyReported,
+
+    #[error("Report not found")]
+    NotFound,
+
+    #[error("Report can't go from {} to {}", .0.as_str(), .1.as_str())]
+    InvalidTransition(ReportStatus, ReportStatus),
+
+    #[error("User is already banned")]
+    AlreadyBanned,
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
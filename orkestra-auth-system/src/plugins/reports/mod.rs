@@ -0,0 +1,41 @@
+mod controller;
+mod dto;
+mod error;
+mod models;
+mod use_case;
+
+pub mod router;
+
+#[cfg(test)]
+mod tests {
+    use super::dto::ReportStatus;
+
+    #[test]
+    fn status_transitions() {
+        use ReportStatus::*;
+
+        assert!(Open.can_become(InReview));
+        assert!(Open.can_become(Dismissed));
+        assert!(InReview.can_become(Open));
+        assert!(InReview.can_become(Actioned));
+
+        assert!(!Open.can_become(Open));
+        assert!(!Actioned.can_become(Open));
+        assert!(!Dismissed.can_become(InReview));
+        assert!(!Actioned.can_become(Dismissed));
+    }
+
+    #[test]
+    fn status_round_trip() {
+        for status in [
+            ReportStatus::Open,
+            ReportStatus::InReview,
+            ReportStatus::Actioned,
+            ReportStatus::Dismissed,
+        ] {
+            assert_eq!(status.as_str().parse::<ReportStatus>(), Ok(status));
+        }
+
+        assert!("closed".parse::<ReportStatus>().is_err());
+    }
+}
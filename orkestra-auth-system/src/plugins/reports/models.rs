@@ -0,0 +1,42 @@
+use serde::Serialize;
+use sqlx::{types::Uuid, FromRow};
+use time::OffsetDateTime;
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct Report {
+    pub id: Uuid,
+    pub reporter_id: Uuid,
+    pub reporter_username: String,
+    pub target_id: Uuid,
+    pub target_username: String,
+    pub session_id: Option<String>,
+    pub category: String,
+    pub text: String,
+    pub status: String,
+    pub resolution_note: Option<String>,
+    pub handled_by: Option<Uuid>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub resolved_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct Ban {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub report_id: Option<Uuid>,
+    pub reason: String,
+    pub issued_by: Option<Uuid>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+}
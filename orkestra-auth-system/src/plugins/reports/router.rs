@@ -0,0 +1,15 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use super::controller::{ban_from_report, create_report, get_report, list_reports, set_status};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/reports", post(create_report))
+        .route("/moderation/reports", get(list_reports))
+        .route("/moderation/reports/:id", get(get_report))
+        .route("/moderation/reports/:id/status", post(set_status))
+        .route("/moderation/reports/:id/ban", post(ban_from_report))
+}
@@ -0,0 +1,336 @@
+use sqlx::types::Uuid;
+use time::{Duration, OffsetDateTime};
+use tracing::Instrument;
+
+use crate::shared::{
+    audit::{self, AuditEvent},
+    ban,
+    database::Database,
+    notification::{self, NotificationKind},
+    pagination::{Page, PageQuery},
+};
+
+use super::{
+    dto::{BanData, CreateReportData, ReportFilter, ReportStatus, SetStatusData},
+    error::ReportError,
+    models::{Ban, Report},
+};
+
+const TEXT_MAX_LEN: usize = 1000;
+const SESSION_ID_MAX_LEN: usize = 64;
+const BAN_REASON_MAX_LEN: usize = 500;
+const BAN_MAX_SECS: i64 = 10 * 365 * 24 * 60 * 60;
+
+const UNRESOLVED_PAIR_CONSTRAINT: &str = "reports_unresolved_pair_idx";
+
+pub async fn create_report(
+    database: &Database,
+    project_id: Uuid,
+    reporter_id: Uuid,
+    data: CreateReportData,
+) -> Result<Uuid, ReportError> {
+    const TARGET_QUERY: &str = "SELECT EXISTS (SELECT 1 FROM users \
+        WHERE id = $1 AND project_id = $2 AND deleted_at IS NULL);";
+    const INSERT_QUERY: &str = "INSERT INTO reports \
+        (project_id, reporter_id, target_id, session_id, category, text) \
+        VALUES ($1, $2, $3, $4, $5, $6) RETURNING id;";
+
+    if data.target_id == reporter_id {
+        return Err(ReportError::SelfTarget);
+    }
+
+    let text = data.text.trim();
+
+    if text.chars().count() > TEXT_MAX_LEN {
+        return Err(ReportError::Invalid(format!(
+            "text is longer than {TEXT_MAX_LEN} characters"
+        )));
+    }
+
+    let session_id = data
+        .session_id
+        .map(|session_id| session_id.trim().to_string())
+        .filter(|session_id| !session_id.is_empty());
+
+    if session_id
+        .as_ref()
+        .is_some_and(|session_id| session_id.len() > SESSION_ID_MAX_LEN)
+    {
+        return Err(ReportError::Invalid("session id is too long".to_string()));
+    }
+
+    let exists: bool = sqlx::query_scalar(TARGET_QUERY)
+        .bind(data.target_id)
+        .bind(project_id)
+        .fetch_one(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    if !exists {
+        return Err(ReportError::UnknownUser);
+    }
+
+    let result = sqlx::query_scalar(INSERT_QUERY)
+        .bind(project_id)
+        .bind(reporter_id)
+        .bind(data.target_id)
+        .bind(session_id)
+        .bind(data.category.as_str())
+        .bind(text)
+        .fetch_one(database.as_ref())
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(id) => Ok(id),
+        Err(sqlx::Error::Database(err)) if err.constraint() == Some(UNRESOLVED_PAIR_CONSTRAINT) => {
+            Err(ReportError::AlreadyReported)
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Moderation queue, oldest reports first
+pub async fn list_reports(
+    database: &Database,
+    project_id: Uuid,
+    filter: &ReportFilter,
+    page: &PageQuery,
+) -> Result<Page<Report>, ReportError> {
+    const LIST_QUERY: &str = "SELECT r.id, r.reporter_id, ru.username AS reporter_username, \
+        r.target_id, tu.username AS target_username, r.session_id, r.category, r.text, \
+        r.status, r.resolution_note, r.handled_by, r.created_at, r.updated_at, r.resolved_at \
+        FROM reports r JOIN users ru ON ru.id = r.reporter_id JOIN users tu ON tu.id = r.target_id \
+        WHERE r.project_id = $1 AND ($2::text IS NULL OR r.status = $2) \
+        AND ($3::uuid IS NULL OR r.target_id = $3) \
+        ORDER BY r.created_at, r.id LIMIT $4 OFFSET $5;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(project_id)
+        .bind(filter.status.map(|status| status.as_str()))
+        .bind(filter.target_id)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+pub async fn get_report(
+    database: &Database,
+    project_id: Uuid,
+    id: Uuid,
+) -> Result<Report, ReportError> {
+    const REPORT_QUERY: &str = "SELECT r.id, r.reporter_id, ru.username AS reporter_username, \
+        r.target_id, tu.username AS target_username, r.session_id, r.category, r.text, \
+        r.status, r.resolution_note, r.handled_by, r.created_at, r.updated_at, r.resolved_at \
+        FROM reports r JOIN users ru ON ru.id = r.reporter_id JOIN users tu ON tu.id = r.target_id \
+        WHERE r.project_id = $1 AND r.id = $2;";
+
+    sqlx::query_as(REPORT_QUERY)
+        .bind(project_id)
+        .bind(id)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?
+        .ok_or(ReportError::NotFound)
+}
+
+pub async fn set_status(
+    database: &Database,
+    project_id: Uuid,
+    id: Uuid,
+    handled_by: Option<Uuid>,
+    data: SetStatusData,
+) -> Result<Report, ReportError> {
+    let note = data
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+
+    if note
+        .as_ref()
+        .is_some_and(|note| note.chars().count() > TEXT_MAX_LEN)
+    {
+        return Err(ReportError::Invalid(format!(
+            "note is longer than {TEXT_MAX_LEN} characters"
+        )));
+    }
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    lock_report(&mut transaction, project_id, id, data.status).await?;
+
+    update_status(&mut transaction, id, data.status, note, handled_by).await?;
+
+    transaction.commit().in_current_span().await?;
+
+    get_report(database, project_id, id).await
+}
+
+/// Bans the reported user, revokes their tokens and marks the report actioned
+pub async fn ban_from_report(
+    database: &Database,
+    project_id: Uuid,
+    id: Uuid,
+    issued_by: Option<Uuid>,
+    data: BanData,
+) -> Result<Ban, ReportError> {
+    const LOCK_USER_QUERY: &str = "SELECT id FROM users WHERE id = $1 FOR NO KEY UPDATE;";
+    const INSERT_QUERY: &str = "INSERT INTO bans \
+        (project_id, user_id, report_id, reason, issued_by, expires_at) \
+        VALUES ($1, $2, $3, $4, $5, $6) \
+        RETURNING id, user_id, report_id, reason, issued_by, created_at, expires_at;";
+    const REVOKE_TOKENS_QUERY: &str =
+        "UPDATE tokens SET revoked_at = now() WHERE user_id = $1 AND revoked_at IS NULL;";
+
+    let reason = data.reason.trim().to_string();
+
+    if reason.is_empty() || reason.chars().count() > BAN_REASON_MAX_LEN {
+        return Err(ReportError::Invalid(format!(
+            "reason has to be 1 to {BAN_REASON_MAX_LEN} characters"
+        )));
+    }
+
+    if data
+        .duration_secs
+        .is_some_and(|secs| !(1..=BAN_MAX_SECS).contains(&secs))
+    {
+        return Err(ReportError::Invalid(format!(
+            "duration has to be 1 to {BAN_MAX_SECS} seconds"
+        )));
+    }
+
+    let expires_at = data
+        .duration_secs
+        .map(|secs| OffsetDateTime::now_utc() + Duration::seconds(secs));
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    let target_id = lock_report(&mut transaction, project_id, id, ReportStatus::Actioned).await?;
+
+    // Serializes bans of the same user, so two moderators can't both ban them
+    sqlx::query(LOCK_USER_QUERY)
+        .bind(target_id)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    if ban::active_ban(&mut *transaction, target_id)
+        .in_current_span()
+        .await?
+        .is_some()
+    {
+        return Err(ReportError::AlreadyBanned);
+    }
+
+    let ban: Ban = sqlx::query_as(INSERT_QUERY)
+        .bind(project_id)
+        .bind(target_id)
+        .bind(id)
+        .bind(&reason)
+        .bind(issued_by)
+        .bind(expires_at)
+        .fetch_one(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    sqlx::query(REVOKE_TOKENS_QUERY)
+        .bind(target_id)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    update_status(
+        &mut transaction,
+        id,
+        ReportStatus::Actioned,
+        Some(reason),
+        issued_by,
+    )
+    .await?;
+
+    audit::record(
+        &mut *transaction,
+        target_id,
+        AuditEvent::Banned,
+        serde_json::json!({
+            "ban_id": ban.id,
+            "report_id": id,
+        }),
+    )
+    .in_current_span()
+    .await?;
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(ban)
+}
+
+/// Locks the report and checks it may move to `next`, returns the reported user
+async fn lock_report(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    project_id: Uuid,
+    id: Uuid,
+    next: ReportStatus,
+) -> Result<Uuid, ReportError> {
+    const LOCK_QUERY: &str =
+        "SELECT status, target_id FROM reports WHERE id = $1 AND project_id = $2 FOR UPDATE;";
+
+    let Some((status, target_id)): Option<(String, Uuid)> = sqlx::query_as(LOCK_QUERY)
+        .bind(id)
+        .bind(project_id)
+        .fetch_optional(&mut **transaction)
+        .in_current_span()
+        .await?
+    else {
+        return Err(ReportError::NotFound);
+    };
+
+    let status: ReportStatus = status.parse().unwrap_or(ReportStatus::Open);
+
+    if !status.can_become(next) {
+        return Err(ReportError::InvalidTransition(status, next));
+    }
+
+    Ok(target_id)
+}
+
+/// Updates the status, reporters hear about their report getting resolved
+async fn update_status(
+    transaction: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: Uuid,
+    status: ReportStatus,
+    note: Option<String>,
+    handled_by: Option<Uuid>,
+) -> Result<(), ReportError> {
+    const UPDATE_QUERY: &str = "UPDATE reports SET status = $2, \
+        resolution_note = coalesce($3, resolution_note), handled_by = coalesce($4, handled_by), \
+        updated_at = now(), resolved_at = CASE WHEN $5 THEN now() END WHERE id = $1 \
+        RETURNING reporter_id;";
+
+    let reporter_id: Uuid = sqlx::query_scalar(UPDATE_QUERY)
+        .bind(id)
+        .bind(status.as_str())
+        .bind(note)
+        .bind(handled_by)
+        .bind(status.is_resolved())
+        .fetch_one(&mut **transaction)
+        .in_current_span()
+        .await?;
+
+    if status.is_resolved() {
+        notification::deliver(
+            &mut **transaction,
+            reporter_id,
+            NotificationKind::ReportResolved,
+            serde_json::json!({ "report_id": id, "status": status }),
+        )
+        .in_current_span()
+        .await?;
+    }
+
+    Ok(())
+}
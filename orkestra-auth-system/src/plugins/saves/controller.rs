@@ -0,0 +1,212 @@
+use axum::{
+    extract::{Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::saves::use_case,
+    shared::{
+        auth::extractor::{AuthUser, ServiceCaller},
+        context::Context,
+        pagination::PageQuery,
+        utils::{
+            bad_request_json, conflict_json, forbidden_json, internal_error_json, just_ok,
+            not_found_json, ok,
+        },
+    },
+};
+
+use super::{
+    dto::{DeleteSaveQuery, KeyPath, UserKeyPath, UserPath, WriteSaveData},
+    error::SaveError,
+};
+
+/// Scope game servers need to read saves shared with them
+const SAVES_READ_SCOPE: &str = "saves:read";
+
+pub async fn list_saves(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_saves");
+    let _guard = span.enter();
+
+    info!(event = "Request saves list", user_id = %user.id);
+
+    let result = use_case::list_saves(context.database(), user.id, false, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(saves) => ok(saves),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn get_save(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(KeyPath { key }): Path<KeyPath>,
+) -> impl IntoResponse {
+    let span = info_span!("get_save");
+    let _guard = span.enter();
+
+    info!(event = "Request save", user_id = %user.id, key = key);
+
+    let result = use_case::get_save(context.database(), user.id, &key, false)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(save) => ok(save),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn write_save(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(KeyPath { key }): Path<KeyPath>,
+    Json(request): Json<WriteSaveData>,
+) -> impl IntoResponse {
+    let span = info_span!("write_save");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to write save",
+        user_id = %user.id,
+        key = key,
+        expected_version = ?request.expected_version,
+    );
+
+    let result = use_case::write_save(
+        context.database(),
+        context.save_limits(),
+        user.id,
+        &key,
+        request,
+    )
+    .in_current_span()
+    .await;
+
+    match result {
+        Ok(save) => {
+            info!(event = "Save written", version = save.version);
+
+            ok(save)
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn delete_save(
+    Extension(context): Extension<Context>,
+    user: AuthUser,
+    Path(KeyPath { key }): Path<KeyPath>,
+    Query(request): Query<DeleteSaveQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("delete_save");
+    let _guard = span.enter();
+
+    info!(
+        event = "Request to delete save",
+        user_id = %user.id,
+        key = key,
+        expected_version = ?request.expected_version,
+    );
+
+    let result = use_case::delete_save(context.database(), user.id, &key, request.expected_version)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(_) => {
+            info!(event = "Save deleted");
+
+            just_ok()
+        }
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn list_user_saves(
+    Extension(context): Extension<Context>,
+    ServiceCaller(service): ServiceCaller,
+    Path(UserPath { user_id }): Path<UserPath>,
+    Query(page): Query<PageQuery>,
+) -> impl IntoResponse {
+    let span = info_span!("list_user_saves");
+    let _guard = span.enter();
+
+    if !service.has_scope(SAVES_READ_SCOPE) {
+        return forbidden_json(serde_json::json!({
+            "error": "Api key lacks the saves:read scope"
+        }));
+    }
+
+    info!(event = "Service requests saves list", service = service.name, user_id = %user_id);
+
+    let result = use_case::list_saves(context.database(), user_id, true, &page)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(saves) => ok(saves),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn get_user_save(
+    Extension(context): Extension<Context>,
+    ServiceCaller(service): ServiceCaller,
+    Path(UserKeyPath { user_id, key }): Path<UserKeyPath>,
+) -> impl IntoResponse {
+    let span = info_span!("get_user_save");
+    let _guard = span.enter();
+
+    if !service.has_scope(SAVES_READ_SCOPE) {
+        return forbidden_json(serde_json::json!({
+            "error": "Api key lacks the saves:read scope"
+        }));
+    }
+
+    info!(
+        event = "Service requests save",
+        service = service.name,
+        user_id = %user_id,
+        key = key,
+    );
+
+    let result = use_case::get_save(context.database(), user_id, &key, true)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(save) => ok(save),
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: SaveError) -> (StatusCode, Json<serde_json::Value>) {
+    error!(event = %err);
+
+    match err {
+        SaveError::NotFound => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        SaveError::VersionConflict(current_version) => conflict_json(serde_json::json!({
+            "error": err.to_string(),
+            "current_version": current_version
+        })),
+        SaveError::Database(_) => internal_error_json(serde_json::json!({
+            "error": "Internal error"
+        })),
+        _ => bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
@@ -0,0 +1,51 @@
+use serde::Deserialize;
+use sqlx::types::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// Only the owner can read the save
+    Private,
+    /// Game servers holding the `saves:read` scope can read it too
+    Server,
+}
+
+impl Visibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Visibility::Private => "private",
+            Visibility::Server => "server",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KeyPath {
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserPath {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UserKeyPath {
+    pub user_id: Uuid,
+    pub key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WriteSaveData {
+    pub value: serde_json::Value,
+    pub visibility: Option<Visibility>,
+
+    /// Version the write is based on, `0` when the save must not exist yet.
+    /// Without it the write overwrites whatever is stored
+    pub expected_version: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteSaveQuery {
+    pub expected_version: Option<i64>,
+}
@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SaveError {
+    #[error("Invalid save key")]
+    InvalidKey,
+
+    #[error("Save value is larger than {0} bytes")]
+    ValueTooLarge(usize),
+
+    #[error("No more than {0} saves are allowed")]
+    TooManyKeys(i64),
+
+    #[error("Save not found")]
+    NotFound,
+
+    #[error("Save version conflict, current version is {0}")]
+    VersionConflict(i64),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
@@ -0,0 +1,24 @@
+mod controller;
+mod dto;
+mod error;
+mod models;
+mod use_case;
+
+pub mod router;
+
+#[cfg(test)]
+mod tests {
+    use super::use_case::valid_key;
+
+    #[test]
+    fn key_validation() {
+        assert!(valid_key("slot_1"));
+        assert!(valid_key("settings.audio"));
+        assert!(valid_key("Campaign-2"));
+
+        assert!(!valid_key(""));
+        assert!(!valid_key("slot 1"));
+        assert!(!valid_key("slots/1"));
+        assert!(!valid_key(&"k".repeat(65)));
+    }
+}
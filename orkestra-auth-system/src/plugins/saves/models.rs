@@ -0,0 +1,25 @@
+use serde::Serialize;
+use sqlx::FromRow;
+use time::OffsetDateTime;
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct SaveMeta {
+    pub key: String,
+    pub version: i64,
+    pub size_bytes: i32,
+    pub visibility: String,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+pub struct Save {
+    pub key: String,
+    pub value: serde_json::Value,
+    pub version: i64,
+    pub visibility: String,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
@@ -0,0 +1,16 @@
+use axum::{routing::get, Router};
+
+use super::controller::{
+    delete_save, get_save, get_user_save, list_saves, list_user_saves, write_save,
+};
+
+pub fn service() -> Router {
+    Router::new()
+        .route("/me/saves", get(list_saves))
+        .route(
+            "/me/saves/:key",
+            get(get_save).put(write_save).delete(delete_save),
+        )
+        .route("/service/users/:user_id/saves", get(list_user_saves))
+        .route("/service/users/:user_id/saves/:key", get(get_user_save))
+}
@@ -0,0 +1,205 @@
+use sqlx::types::Uuid;
+use tracing::Instrument;
+
+use crate::shared::{
+    context::SaveLimits,
+    database::Database,
+    pagination::{Page, PageQuery},
+};
+
+use super::{
+    dto::{Visibility, WriteSaveData},
+    error::SaveError,
+    models::{Save, SaveMeta},
+};
+
+const KEY_MAX_LEN: usize = 64;
+
+/// Lists saves of the user, `server_only` limits it to saves visible to game servers
+pub async fn list_saves(
+    database: &Database,
+    user_id: Uuid,
+    server_only: bool,
+    page: &PageQuery,
+) -> Result<Page<SaveMeta>, SaveError> {
+    const LIST_QUERY: &str = "SELECT key, version, size_bytes, visibility, updated_at \
+        FROM saves WHERE user_id = $1 AND ($2 = false OR visibility = 'server') \
+        ORDER BY key LIMIT $3 OFFSET $4;";
+
+    let rows = sqlx::query_as(LIST_QUERY)
+        .bind(user_id)
+        .bind(server_only)
+        .bind(page.fetch_limit())
+        .bind(page.offset())
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(Page::new(rows, page))
+}
+
+pub async fn get_save(
+    database: &Database,
+    user_id: Uuid,
+    key: &str,
+    server_only: bool,
+) -> Result<Save, SaveError> {
+    const SAVE_QUERY: &str = "SELECT key, value, version, visibility, updated_at FROM saves \
+        WHERE user_id = $1 AND key = $2 AND ($3 = false OR visibility = 'server');";
+
+    if !valid_key(key) {
+        return Err(SaveError::InvalidKey);
+    }
+
+    sqlx::query_as(SAVE_QUERY)
+        .bind(user_id)
+        .bind(key)
+        .bind(server_only)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?
+        .ok_or(SaveError::NotFound)
+}
+
+/// Creates or replaces the save, checking `expected_version` against the stored one
+pub async fn write_save(
+    database: &Database,
+    limits: SaveLimits,
+    user_id: Uuid,
+    key: &str,
+    data: WriteSaveData,
+) -> Result<SaveMeta, SaveError> {
+    const LOCK_USER_QUERY: &str = "SELECT id FROM users WHERE id = $1 FOR NO KEY UPDATE;";
+    const VERSION_QUERY: &str = "SELECT version FROM saves WHERE user_id = $1 AND key = $2;";
+    const COUNT_QUERY: &str = "SELECT count(*) FROM saves WHERE user_id = $1;";
+    const INSERT_QUERY: &str = "INSERT INTO saves (user_id, key, value, size_bytes, visibility) \
+        VALUES ($1, $2, $3, $4, $5) \
+        RETURNING key, version, size_bytes, visibility, updated_at;";
+    const UPDATE_QUERY: &str = "UPDATE saves SET value = $3, size_bytes = $4, \
+        visibility = coalesce($5, visibility), version = version + 1, updated_at = now() \
+        WHERE user_id = $1 AND key = $2 \
+        RETURNING key, version, size_bytes, visibility, updated_at;";
+
+    if !valid_key(key) {
+        return Err(SaveError::InvalidKey);
+    }
+
+    let size = data.value.to_string().len();
+
+    if size > limits.max_value_bytes {
+        return Err(SaveError::ValueTooLarge(limits.max_value_bytes));
+    }
+
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    // Writes of one user are serialized, so the version check and the key
+    // quota can't race with each other
+    sqlx::query(LOCK_USER_QUERY)
+        .bind(user_id)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    let current: Option<i64> = sqlx::query_scalar(VERSION_QUERY)
+        .bind(user_id)
+        .bind(key)
+        .fetch_optional(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    if let Some(expected) = data.expected_version {
+        if current.unwrap_or(0) != expected {
+            return Err(SaveError::VersionConflict(current.unwrap_or(0)));
+        }
+    }
+
+    let visibility = data.visibility.map(|v| v.as_str());
+
+    let save: SaveMeta = match current {
+        Some(_) => {
+            sqlx::query_as(UPDATE_QUERY)
+                .bind(user_id)
+                .bind(key)
+                .bind(&data.value)
+                .bind(size as i32)
+                .bind(visibility)
+                .fetch_one(&mut *transaction)
+                .in_current_span()
+                .await?
+        }
+        None => {
+            let count: i64 = sqlx::query_scalar(COUNT_QUERY)
+                .bind(user_id)
+                .fetch_one(&mut *transaction)
+                .in_current_span()
+                .await?;
+
+            if count >= limits.max_keys {
+                return Err(SaveError::TooManyKeys(limits.max_keys));
+            }
+
+            sqlx::query_as(INSERT_QUERY)
+                .bind(user_id)
+                .bind(key)
+                .bind(&data.value)
+                .bind(size as i32)
+                .bind(visibility.unwrap_or(Visibility::Private.as_str()))
+                .fetch_one(&mut *transaction)
+                .in_current_span()
+                .await?
+        }
+    };
+
+    transaction.commit().in_current_span().await?;
+
+    Ok(save)
+}
+
+pub async fn delete_save(
+    database: &Database,
+    user_id: Uuid,
+    key: &str,
+    expected_version: Option<i64>,
+) -> Result<(), SaveError> {
+    const DELETE_QUERY: &str = "DELETE FROM saves \
+        WHERE user_id = $1 AND key = $2 AND ($3::bigint IS NULL OR version = $3) \
+        RETURNING version;";
+    const VERSION_QUERY: &str = "SELECT version FROM saves WHERE user_id = $1 AND key = $2;";
+
+    if !valid_key(key) {
+        return Err(SaveError::InvalidKey);
+    }
+
+    let deleted: Option<i64> = sqlx::query_scalar(DELETE_QUERY)
+        .bind(user_id)
+        .bind(key)
+        .bind(expected_version)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    if deleted.is_some() {
+        return Ok(());
+    }
+
+    let current: Option<i64> = sqlx::query_scalar(VERSION_QUERY)
+        .bind(user_id)
+        .bind(key)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    match current {
+        Some(current) => Err(SaveError::VersionConflict(current)),
+        None => Err(SaveError::NotFound),
+    }
+}
+
+/// Keys are 1..64 latin letters, digits, `_`, `-` and `.`
+pub fn valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= KEY_MAX_LEN
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
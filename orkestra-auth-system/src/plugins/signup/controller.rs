@@ -2,10 +2,11 @@ use axum::{response::IntoResponse, Extension, Json};
 use tracing::{error, info, info_span, Instrument};
 
 use crate::{
-    plugins::signup::use_case,
+    plugins::signup::{error::SignupError, use_case},
     shared::{
         context::Context,
-        utils::{bad_request_json, just_created},
+        project::Project,
+        utils::{bad_request_json, internal_error_json, just_created},
     },
 };
 
@@ -13,6 +14,7 @@ use super::dto::SignupData;
 
 pub async fn signup(
     Extension(context): Extension<Context>,
+    project: Project,
     Json(request): Json<SignupData>,
 ) -> impl IntoResponse {
     let span = info_span!("signup");
@@ -20,12 +22,18 @@ pub async fn signup(
 
     info!(
         event = "Request to signup user",
+        project = project.slug,
         username = request.username,
     );
 
-    let result = use_case::signup(context.database(), request)
-        .in_current_span()
-        .await;
+    let result = use_case::signup(
+        context.database(),
+        &project,
+        context.username_policy(),
+        request,
+    )
+    .in_current_span()
+    .await;
 
     match result {
         Ok(_) => {
@@ -33,6 +41,13 @@ pub async fn signup(
 
             just_created()
         }
+        Err(SignupError::Database(err)) => {
+            error!(event = "Database error", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
         Err(err) => {
             error!(event = %err);
 
@@ -1,13 +1,18 @@
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq, Eq)]
+use crate::shared::username::UsernameError;
+
+#[derive(Error, Debug)]
 pub enum SignupError {
-    #[error("Unknown user")]
-    InvalidUsername,
+    #[error("Invalid username: {0}")]
+    InvalidUsername(#[from] UsernameError),
 
     #[error("Wrong password")]
     InvalidPassword,
 
     #[error("User already exists")]
     AlreadyExists,
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
 }
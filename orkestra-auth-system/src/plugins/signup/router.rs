@@ -1,7 +1,11 @@
-use axum::{routing::post, Router};
+use axum::{middleware, routing::post, Router};
+
+use crate::shared::idempotency::idempotency;
 
 use super::controller::signup;
 
 pub fn service() -> Router {
-    Router::new().route("/signup", post(signup))
+    Router::new()
+        .route("/signup", post(signup))
+        .route_layer(middleware::from_fn_with_state("signup", idempotency))
 }
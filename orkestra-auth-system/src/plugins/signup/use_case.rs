@@ -3,17 +3,31 @@ use pbkdf2::{
     Pbkdf2,
 };
 use rand::rngs::OsRng;
+use sqlx::types::Uuid;
+use tracing::Instrument;
 
-use crate::shared::database::Database;
+use crate::shared::{
+    audit::{self, AuditEvent},
+    database::Database,
+    project::Project,
+    username::UsernamePolicy,
+};
 
 use super::{dto::SignupData, error::SignupError};
 
-pub async fn signup(database: &Database, data: SignupData) -> Result<(), SignupError> {
-    if !valid_username(&data.username) {
-        return Err(SignupError::InvalidUsername);
-    }
+pub async fn signup(
+    database: &Database,
+    project: &Project,
+    policy: &UsernamePolicy,
+    data: SignupData,
+) -> Result<(), SignupError> {
+    policy.check(&data.username)?;
 
-    const INSERT_QUERY: &str = "INSERT INTO users (username, password) VALUES ($1, $2);";
+    const INSERT_QUERY: &str =
+        "INSERT INTO users (project_id, username, password) VALUES ($1, $2, $3) \
+        ON CONFLICT DO NOTHING RETURNING id;";
+    const INSERT_PROFILE_QUERY: &str =
+        "INSERT INTO profiles (user_id, display_name) VALUES ($1, $2);";
 
     let salt = SaltString::generate(&mut OsRng);
 
@@ -21,21 +35,36 @@ pub async fn signup(database: &Database, data: SignupData) -> Result<(), SignupE
         return Err(SignupError::InvalidPassword);
     };
 
-    if sqlx::query(INSERT_QUERY)
+    let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+    let Some(id): Option<Uuid> = sqlx::query_scalar(INSERT_QUERY)
+        .bind(project.id)
         .bind(&data.username)
         .bind(password_hash.to_string())
-        .execute(database.as_ref())
-        .await
-        .is_err()
-    {
+        .fetch_optional(&mut *transaction)
+        .in_current_span()
+        .await?
+    else {
         return Err(SignupError::AlreadyExists);
-    }
+    };
 
-    Ok(())
-}
+    sqlx::query(INSERT_PROFILE_QUERY)
+        .bind(id)
+        .bind(&data.username)
+        .execute(&mut *transaction)
+        .in_current_span()
+        .await?;
+
+    audit::record(
+        &mut *transaction,
+        id,
+        AuditEvent::Signup,
+        serde_json::json!({}),
+    )
+    .in_current_span()
+    .await?;
 
-fn valid_username(username: &str) -> bool {
-    username
-        .chars()
-        .all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_'))
+    transaction.commit().in_current_span().await?;
+
+    Ok(())
 }
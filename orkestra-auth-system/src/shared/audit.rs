@@ -0,0 +1,52 @@
+use sqlx::{types::Uuid, PgExecutor};
+use tracing::Instrument;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEvent {
+    Signup,
+    Login,
+    VkLogin,
+    UsernameChanged,
+    ProfileUpdated,
+    DataExported,
+    DeletionRequested,
+    Banned,
+}
+
+impl AuditEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEvent::Signup => "signup",
+            AuditEvent::Login => "login",
+            AuditEvent::VkLogin => "vk_login",
+            AuditEvent::UsernameChanged => "username_changed",
+            AuditEvent::ProfileUpdated => "profile_updated",
+            AuditEvent::DataExported => "data_exported",
+            AuditEvent::DeletionRequested => "deletion_requested",
+            AuditEvent::Banned => "banned",
+        }
+    }
+}
+
+pub async fn record<'e, E>(
+    executor: E,
+    user_id: Uuid,
+    event: AuditEvent,
+    details: serde_json::Value,
+) -> Result<(), sqlx::Error>
+where
+    E: PgExecutor<'e>,
+{
+    const INSERT_QUERY: &str =
+        "INSERT INTO audit_events (user_id, event, details) VALUES ($1, $2, $3);";
+
+    sqlx::query(INSERT_QUERY)
+        .bind(user_id)
+        .bind(event.as_str())
+        .bind(details)
+        .execute(executor)
+        .in_current_span()
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use axum::{
+    extract::Request,
+    http::header::AUTHORIZATION,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::Serialize;
+use sqlx::types::Uuid;
+use tracing::{error, warn, Instrument};
+
+use crate::shared::{
+    context::Context,
+    database::Database,
+    utils::{internal_error_json, unauthorized_json},
+};
+
+use super::token;
+
+const KEY_PREFIX: &str = "ork_";
+
+/// Identity of a service calling with `Authorization: ApiKey <key>`
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceIdentity {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+impl ServiceIdentity {
+    pub const ADMIN_SCOPE: &'static str = "admin";
+    pub const MODERATION_SCOPE: &'static str = "moderation";
+
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+pub struct GeneratedKey {
+    pub key: String,
+    pub prefix: String,
+    pub hash: String,
+}
+
+pub fn generate() -> GeneratedKey {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    let key = format!("{KEY_PREFIX}{}", hex::encode(bytes));
+    let prefix = key[..KEY_PREFIX.len() + 8].to_string();
+    let hash = token::hash(&key);
+
+    GeneratedKey { key, prefix, hash }
+}
+
+pub async fn resolve(
+    database: &Database,
+    key: &str,
+) -> Result<Option<ServiceIdentity>, sqlx::Error> {
+    // last_used_at is only bumped once a minute, services call with every request
+    const RESOLVE_QUERY: &str = "SELECT id, name, scopes, \
+        (last_used_at IS NULL OR last_used_at < now() - interval '1 minute') \
+        FROM api_keys WHERE key_hash = $1 AND revoked_at IS NULL;";
+
+    const TOUCH_QUERY: &str = "UPDATE api_keys SET last_used_at = now() WHERE id = $1 \
+        AND (last_used_at IS NULL OR last_used_at < now() - interval '1 minute');";
+
+    let row: Option<(Uuid, String, Vec<String>, bool)> = sqlx::query_as(RESOLVE_QUERY)
+        .bind(token::hash(key))
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    let Some((id, name, scopes, stale)) = row else {
+        return Ok(None);
+    };
+
+    if stale {
+        // The key is valid either way, a missed bump isn't worth failing the request
+        if let Err(err) = sqlx::query(TOUCH_QUERY)
+            .bind(id)
+            .execute(database.as_ref())
+            .in_current_span()
+            .await
+        {
+            warn!(event = "Couldn't update api key last use", api_key_id = %id, error = %err);
+        }
+    }
+
+    Ok(Some(ServiceIdentity { id, name, scopes }))
+}
+
+/// Resolves `Authorization: ApiKey <key>` and exposes the caller as a
+/// [`ServiceIdentity`] request extension. Requests without an api key pass through.
+pub async fn api_key_middleware(
+    Extension(context): Extension<Context>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let key = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("ApiKey "))
+        .map(|key| key.trim().to_string());
+
+    let Some(key) = key else {
+        return next.run(request).await;
+    };
+
+    match resolve(context.database(), &key).in_current_span().await {
+        Ok(Some(identity)) => {
+            request.extensions_mut().insert(identity);
+
+            next.run(request).await
+        }
+        Ok(None) => {
+            warn!(event = "Rejected unknown api key");
+
+            unauthorized_json(serde_json::json!({
+                "error": "Invalid api key"
+            }))
+            .into_response()
+        }
+        Err(err) => {
+            error!(event = "Couldn't resolve api key", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+            .into_response()
+        }
+    }
+}
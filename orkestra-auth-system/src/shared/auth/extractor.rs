@@ -0,0 +1,221 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    Json,
+};
+use sqlx::types::Uuid;
+use tracing::{error, Instrument};
+
+use crate::shared::{
+    context::Context,
+    project::Project,
+    utils::{forbidden_json, internal_error_json, unauthorized_json},
+};
+
+use super::{api_key::ServiceIdentity, role::Role, token};
+
+type Rejection = (StatusCode, Json<serde_json::Value>);
+
+#[derive(Debug, Clone, Copy)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub role: Role,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(context) = parts.extensions.get::<Context>().cloned() else {
+            error!(event = "Context is not attached to the router");
+
+            return Err(internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            })));
+        };
+
+        let project = Project::from_request_parts(parts, state).await?;
+
+        let Some(token) = bearer_token(parts) else {
+            return Err(unauthorized_json(serde_json::json!({
+                "error": "Missing access token"
+            })));
+        };
+
+        match token::resolve(context.database(), project.id, token)
+            .in_current_span()
+            .await
+        {
+            Ok(Some((id, role))) => Ok(Self {
+                id,
+                project_id: project.id,
+                role,
+            }),
+            Ok(None) => Err(unauthorized_json(serde_json::json!({
+                "error": "Invalid access token"
+            }))),
+            Err(err) => {
+                error!(event = "Couldn't resolve access token", error = %err);
+
+                Err(internal_error_json(serde_json::json!({
+                    "error": "Internal error"
+                })))
+            }
+        }
+    }
+}
+
+/// Service identity attached by the api key middleware
+#[derive(Debug, Clone)]
+pub struct ServiceCaller(pub ServiceIdentity);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ServiceCaller
+where
+    S: Send + Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<ServiceIdentity>()
+            .cloned()
+            .map(Self)
+            .ok_or_else(|| {
+                unauthorized_json(serde_json::json!({
+                    "error": "Missing api key"
+                }))
+            })
+    }
+}
+
+/// Either an admin of the default project or a service holding the `admin` scope
+#[derive(Debug, Clone)]
+pub enum AdminCaller {
+    User(AuthUser),
+    Service(ServiceIdentity),
+}
+
+impl AdminCaller {
+    pub fn user_id(&self) -> Option<Uuid> {
+        match self {
+            AdminCaller::User(user) => Some(user.id),
+            AdminCaller::Service(_) => None,
+        }
+    }
+
+    /// Human readable caller description for logs
+    pub fn actor(&self) -> String {
+        match self {
+            AdminCaller::User(user) => format!("user:{}", user.id),
+            AdminCaller::Service(service) => format!("service:{}", service.name),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminCaller
+where
+    S: Send + Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(service) = parts.extensions.get::<ServiceIdentity>().cloned() {
+            return if service.has_scope(ServiceIdentity::ADMIN_SCOPE) {
+                Ok(Self::Service(service))
+            } else {
+                Err(forbidden_json(serde_json::json!({
+                    "error": "Api key lacks the admin scope"
+                })))
+            };
+        }
+
+        let user = AuthUser::from_request_parts(parts, state).await?;
+
+        let project = Project::from_request_parts(parts, state).await?;
+
+        if user.role != Role::Admin || !project.is_default() {
+            return Err(forbidden_json(serde_json::json!({
+                "error": "Admin role required"
+            })));
+        }
+
+        Ok(Self::User(user))
+    }
+}
+
+/// Moderator or admin of the request's project, or a service holding the
+/// `moderation` or `admin` scope
+#[derive(Debug, Clone)]
+pub enum ModeratorCaller {
+    User(AuthUser),
+    Service(ServiceIdentity),
+}
+
+impl ModeratorCaller {
+    pub fn user_id(&self) -> Option<Uuid> {
+        match self {
+            ModeratorCaller::User(user) => Some(user.id),
+            ModeratorCaller::Service(_) => None,
+        }
+    }
+
+    /// Human readable caller description for logs
+    pub fn actor(&self) -> String {
+        match self {
+            ModeratorCaller::User(user) => format!("user:{}", user.id),
+            ModeratorCaller::Service(service) => format!("service:{}", service.name),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ModeratorCaller
+where
+    S: Send + Sync,
+{
+    type Rejection = Rejection;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(service) = parts.extensions.get::<ServiceIdentity>().cloned() {
+            return if service.has_scope(ServiceIdentity::MODERATION_SCOPE)
+                || service.has_scope(ServiceIdentity::ADMIN_SCOPE)
+            {
+                Ok(Self::Service(service))
+            } else {
+                Err(forbidden_json(serde_json::json!({
+                    "error": "Api key lacks the moderation scope"
+                })))
+            };
+        }
+
+        let user = AuthUser::from_request_parts(parts, state).await?;
+
+        if user.role < Role::Moderator {
+            return Err(forbidden_json(serde_json::json!({
+                "error": "Moderator role required"
+            })));
+        }
+
+        Ok(Self::User(user))
+    }
+}
+
+fn bearer_token(parts: &Parts) -> Option<&str> {
+    parts
+        .headers
+        .get(AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
@@ -0,0 +1,4 @@
+pub mod api_key;
+pub mod extractor;
+pub mod role;
+pub mod token;
@@ -0,0 +1,70 @@
+use rand::{rngs::OsRng, RngCore};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::types::Uuid;
+use time::{Duration, OffsetDateTime};
+use tracing::Instrument;
+
+use crate::shared::database::Database;
+
+use super::role::Role;
+
+#[derive(Debug, Serialize)]
+pub struct IssuedToken {
+    pub token: String,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+pub async fn issue(
+    database: &Database,
+    project_id: Uuid,
+    user_id: Uuid,
+    ttl: Duration,
+) -> Result<IssuedToken, sqlx::Error> {
+    const INSERT_QUERY: &str = "INSERT INTO tokens (project_id, user_id, token_hash, expires_at) \
+        VALUES ($1, $2, $3, $4);";
+
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+
+    let token = hex::encode(bytes);
+    let expires_at = OffsetDateTime::now_utc() + ttl;
+
+    sqlx::query(INSERT_QUERY)
+        .bind(project_id)
+        .bind(user_id)
+        .bind(hash(&token))
+        .bind(expires_at)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(IssuedToken { token, expires_at })
+}
+
+/// Resolves a token issued within the project, tokens of other projects are rejected
+pub async fn resolve(
+    database: &Database,
+    project_id: Uuid,
+    token: &str,
+) -> Result<Option<(Uuid, Role)>, sqlx::Error> {
+    const RESOLVE_QUERY: &str =
+        "SELECT t.user_id, u.role FROM tokens t JOIN users u ON u.id = t.user_id \
+        WHERE t.token_hash = $1 AND t.revoked_at IS NULL AND t.expires_at > now() \
+        AND t.project_id = $2 AND u.deleted_at IS NULL;";
+
+    let row: Option<(Uuid, String)> = sqlx::query_as(RESOLVE_QUERY)
+        .bind(hash(token))
+        .bind(project_id)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(row.map(|(id, role)| (id, role.parse().unwrap_or(Role::Player))))
+}
+
+pub fn hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
@@ -0,0 +1,38 @@
+use axum::{http::StatusCode, Json};
+use serde::Serialize;
+use sqlx::{types::Uuid, FromRow, PgExecutor};
+use time::OffsetDateTime;
+use tracing::Instrument;
+
+use super::utils::forbidden_json;
+
+/// Ban currently keeping the user out, the one lasting longest if there are several
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct ActiveBan {
+    pub reason: String,
+
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+pub async fn active_ban<'e, E>(executor: E, user_id: Uuid) -> Result<Option<ActiveBan>, sqlx::Error>
+where
+    E: PgExecutor<'e>,
+{
+    const ACTIVE_BAN_QUERY: &str = "SELECT reason, expires_at FROM bans \
+        WHERE user_id = $1 AND (expires_at IS NULL OR expires_at > now()) \
+        ORDER BY expires_at DESC NULLS FIRST LIMIT 1;";
+
+    sqlx::query_as(ACTIVE_BAN_QUERY)
+        .bind(user_id)
+        .fetch_optional(executor)
+        .in_current_span()
+        .await
+}
+
+pub fn banned_json(ban: &ActiveBan) -> (StatusCode, Json<serde_json::Value>) {
+    forbidden_json(serde_json::json!({
+        "error": "Account is banned",
+        "ban": ban
+    }))
+}
@@ -0,0 +1,213 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderName, StatusCode},
+    Json,
+};
+use serde::Serialize;
+use sqlx::types::Uuid;
+use tracing::{error, warn, Instrument};
+
+use super::{
+    client_version::ClientVersion,
+    context::Context,
+    database::Database,
+    project::Project,
+    utils::{internal_error_json, upgrade_required_json},
+};
+
+/// Header clients put their platform into, e.g. `X-Client-Platform: android`
+pub static CLIENT_PLATFORM_HEADER: HeaderName = HeaderName::from_static("x-client-platform");
+
+/// Policy applied to platforms without their own one
+pub const DEFAULT_PLATFORM: &str = "default";
+
+/// Oldest client build a platform still supports and the newest one available
+#[derive(Debug, Clone, Serialize)]
+pub struct ClientVersionPolicy {
+    pub platform: String,
+    pub min_version: ClientVersion,
+    pub latest_version: ClientVersion,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateRequired {
+    pub platform: Option<String>,
+    pub client_version: Option<ClientVersion>,
+    pub min_version: ClientVersion,
+    pub latest_version: ClientVersion,
+}
+
+pub async fn policies(
+    database: &Database,
+    project_id: Uuid,
+) -> Result<Vec<ClientVersionPolicy>, sqlx::Error> {
+    const POLICIES_QUERY: &str = "SELECT platform, min_version, latest_version \
+        FROM client_versions WHERE project_id = $1 ORDER BY platform;";
+
+    let rows: Vec<(String, String, String)> = sqlx::query_as(POLICIES_QUERY)
+        .bind(project_id)
+        .fetch_all(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(platform, min_version, latest_version)| {
+            match (min_version.parse(), latest_version.parse()) {
+                (Ok(min_version), Ok(latest_version)) => Some(ClientVersionPolicy {
+                    platform,
+                    min_version,
+                    latest_version,
+                }),
+                _ => {
+                    warn!(
+                        event = "Skipping malformed client version policy",
+                        platform = platform
+                    );
+                    None
+                }
+            }
+        })
+        .collect())
+}
+
+/// Rejects clients older than the policy of their platform. Without a policy
+/// every client passes, with one a client not telling its version is too old.
+pub fn check(
+    policies: &[ClientVersionPolicy],
+    platform: Option<&str>,
+    version: Option<&ClientVersion>,
+) -> Result<(), UpdateRequired> {
+    let policy = platform
+        .and_then(|platform| policies.iter().find(|policy| policy.platform == platform))
+        .or_else(|| {
+            policies
+                .iter()
+                .find(|policy| policy.platform == DEFAULT_PLATFORM)
+        });
+
+    let Some(policy) = policy else {
+        return Ok(());
+    };
+
+    if version.is_some_and(|version| *version >= policy.min_version) {
+        return Ok(());
+    }
+
+    Err(UpdateRequired {
+        platform: platform.map(str::to_string),
+        client_version: version.cloned(),
+        min_version: policy.min_version.clone(),
+        latest_version: policy.latest_version.clone(),
+    })
+}
+
+/// Passes only clients supported by the project's version policy, rejecting
+/// others with `426 Upgrade Required`
+#[derive(Debug, Clone)]
+pub struct SupportedClient {
+    pub platform: Option<String>,
+    pub version: Option<ClientVersion>,
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for SupportedClient
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Some(context) = parts.extensions.get::<Context>().cloned() else {
+            error!(event = "Context is not attached to the router");
+
+            return Err(internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            })));
+        };
+
+        let project = Project::from_request_parts(parts, state).await?;
+
+        let platform = parts
+            .headers
+            .get(&CLIENT_PLATFORM_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_lowercase());
+
+        let version = Option::<ClientVersion>::from_request_parts(parts, state)
+            .await
+            .unwrap_or_default();
+
+        let policies = match policies(context.database(), project.id)
+            .in_current_span()
+            .await
+        {
+            Ok(policies) => policies,
+            Err(err) => {
+                error!(event = "Couldn't load client version policies", error = %err);
+
+                return Err(internal_error_json(serde_json::json!({
+                    "error": "Internal error"
+                })));
+            }
+        };
+
+        match check(&policies, platform.as_deref(), version.as_ref()) {
+            Ok(_) => Ok(Self { platform, version }),
+            Err(update) => {
+                warn!(
+                    event = "Rejected outdated client",
+                    platform = ?update.platform,
+                    client_version = ?update.client_version.as_ref().map(ToString::to_string),
+                );
+
+                Err(upgrade_required_json(serde_json::json!({
+                    "error": "Update required",
+                    "update_required": update
+                })))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, ClientVersionPolicy};
+
+    fn policy(platform: &str, min: &str, latest: &str) -> ClientVersionPolicy {
+        ClientVersionPolicy {
+            platform: platform.to_string(),
+            min_version: min.parse().unwrap(),
+            latest_version: latest.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn without_policies_everyone_passes() {
+        assert!(check(&[], None, None).is_ok());
+        assert!(check(&[], Some("android"), Some(&"0.1".parse().unwrap())).is_ok());
+    }
+
+    #[test]
+    fn platform_policy_wins_over_default() {
+        let policies = [
+            policy("default", "1.0", "1.5"),
+            policy("android", "1.2", "1.5"),
+        ];
+
+        assert!(check(&policies, Some("android"), Some(&"1.2.0".parse().unwrap())).is_ok());
+        assert!(check(&policies, Some("android"), Some(&"1.1".parse().unwrap())).is_err());
+        assert!(check(&policies, Some("windows"), Some(&"1.1".parse().unwrap())).is_ok());
+        assert!(check(&policies, None, Some(&"0.9".parse().unwrap())).is_err());
+    }
+
+    #[test]
+    fn unknown_version_is_outdated() {
+        let policies = [policy("default", "1.0", "1.0")];
+
+        let update = check(&policies, Some("ios"), None).unwrap_err();
+        assert_eq!(update.min_version.to_string(), "1.0");
+        assert_eq!(update.platform.as_deref(), Some("ios"));
+    }
+}
@@ -0,0 +1,143 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{request::Parts, HeaderName, StatusCode},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use super::utils::bad_request_json;
+
+/// Header clients put their build version into, e.g. `X-Client-Version: 1.4.2`
+pub static CLIENT_VERSION_HEADER: HeaderName = HeaderName::from_static("x-client-version");
+
+const MAX_PARTS: usize = 4;
+
+/// Dotted numeric client version, trailing zeros don't matter (`1.2` == `1.2.0`)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ClientVersion(Vec<u32>);
+
+impl FromStr for ClientVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .trim()
+            .split('.')
+            .map(str::parse)
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|_| format!("Invalid client version: {s}"))?;
+
+        if parts.len() > MAX_PARTS {
+            return Err(format!("Invalid client version: {s}"));
+        }
+
+        Ok(Self(parts))
+    }
+}
+
+impl TryFrom<String> for ClientVersion {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ClientVersion> for String {
+    fn from(value: ClientVersion) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ClientVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(u32::to_string).collect();
+
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+impl Ord for ClientVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.0.len().max(other.0.len());
+        let part = |version: &Self, i: usize| version.0.get(i).copied().unwrap_or(0);
+
+        (0..len)
+            .map(|i| part(self, i).cmp(&part(other, i)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialEq for ClientVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for ClientVersion {}
+
+impl PartialOrd for ClientVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Reads the `X-Client-Version` header, take it as `Option<ClientVersion>`
+/// where the header is optional
+#[async_trait]
+impl<S> FromRequestParts<S> for ClientVersion
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(value) = parts.headers.get(&CLIENT_VERSION_HEADER) else {
+            return Err(bad_request_json(serde_json::json!({
+                "error": "Missing client version header"
+            })));
+        };
+
+        value
+            .to_str()
+            .map_err(|_| "Invalid client version".to_string())
+            .and_then(str::parse)
+            .map_err(|err| {
+                bad_request_json(serde_json::json!({
+                    "error": err
+                }))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ClientVersion;
+
+    fn version(s: &str) -> ClientVersion {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        assert_eq!(version("1.2.3").to_string(), "1.2.3");
+        assert_eq!(version(" 10 ").to_string(), "10");
+
+        assert!("".parse::<ClientVersion>().is_err());
+        assert!("1..2".parse::<ClientVersion>().is_err());
+        assert!("1.2-beta".parse::<ClientVersion>().is_err());
+        assert!("1.2.3.4.5".parse::<ClientVersion>().is_err());
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(version("1.10") > version("1.9.9"));
+        assert!(version("2") > version("1.99"));
+        assert_eq!(version("1.2"), version("1.2.0"));
+    }
+}
@@ -10,8 +10,39 @@ pub struct AppConfig {
 
     pub port: u16,
 
-    pub vk_game_id: String,
-    pub vk_gas_secret: String,
+    /// VK credentials of the default project, other projects keep theirs in the database
+    #[serde(default)]
+    pub vk_game_id: Option<String>,
+    #[serde(default)]
+    pub vk_gas_secret: Option<String>,
+
+    #[serde(default = "default_token_ttl_secs")]
+    pub token_ttl_secs: u64,
+
+    #[serde(default = "default_username_change_cooldown_secs")]
+    pub username_change_cooldown_secs: u64,
+    #[serde(default = "default_reserved_usernames")]
+    pub reserved_usernames: Vec<String>,
+    #[serde(default)]
+    pub profane_words: Vec<String>,
+
+    #[serde(default = "default_account_deletion_grace_secs")]
+    pub account_deletion_grace_secs: u64,
+    #[serde(default = "default_account_purge_interval_secs")]
+    pub account_purge_interval_secs: u64,
+
+    #[serde(default = "default_presence_ttl_secs")]
+    pub presence_ttl_secs: u64,
+
+    #[serde(default = "default_save_max_value_bytes")]
+    pub save_max_value_bytes: usize,
+    #[serde(default = "default_save_max_keys")]
+    pub save_max_keys: i64,
+
+    #[serde(default = "default_idempotency_ttl_secs")]
+    pub idempotency_ttl_secs: u64,
+    #[serde(default = "default_idempotency_purge_interval_secs")]
+    pub idempotency_purge_interval_secs: u64,
 }
 
 impl AppConfig {
@@ -19,3 +50,56 @@ impl AppConfig {
         Ok(envy::from_env::<Self>()?)
     }
 }
+
+fn default_token_ttl_secs() -> u64 {
+    60 * 60 * 24 * 30
+}
+
+fn default_username_change_cooldown_secs() -> u64 {
+    60 * 60 * 24 * 30
+}
+
+fn default_reserved_usernames() -> Vec<String> {
+    [
+        "admin",
+        "administrator",
+        "moderator",
+        "support",
+        "system",
+        "root",
+        "vk_*",
+        // Given to accounts renamed by migrations
+        "user_*",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+fn default_account_deletion_grace_secs() -> u64 {
+    60 * 60 * 24 * 30
+}
+
+fn default_account_purge_interval_secs() -> u64 {
+    60 * 60
+}
+
+fn default_presence_ttl_secs() -> u64 {
+    90
+}
+
+fn default_save_max_value_bytes() -> usize {
+    64 * 1024
+}
+
+fn default_save_max_keys() -> i64 {
+    100
+}
+
+fn default_idempotency_ttl_secs() -> u64 {
+    60 * 60 * 24
+}
+
+fn default_idempotency_purge_interval_secs() -> u64 {
+    60 * 60
+}
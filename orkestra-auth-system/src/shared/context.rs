@@ -2,7 +2,12 @@
 
 use std::sync::Arc;
 
-use super::database::Database;
+use time::Duration;
+
+use super::{
+    config::AppConfig, database::Database, services::presence::PresenceStore,
+    username::UsernamePolicy,
+};
 
 #[derive(Clone)]
 pub struct Context {
@@ -11,18 +16,82 @@ pub struct Context {
 
 struct ContextInner {
     database: Database,
+
+    token_ttl: Duration,
+
+    username_policy: UsernamePolicy,
+    username_change_cooldown: Duration,
+
+    account_deletion_grace: Duration,
+
+    presence: PresenceStore,
+
+    save_limits: SaveLimits,
+
+    idempotency_ttl: Duration,
+}
+
+/// Cloud save quotas per user
+#[derive(Debug, Clone, Copy)]
+pub struct SaveLimits {
+    pub max_value_bytes: usize,
+    pub max_keys: i64,
 }
 
 impl Context {
-    pub fn new(database: Database) -> Self {
+    pub fn new(config: &AppConfig, database: Database, presence: PresenceStore) -> Self {
         Self {
-            inner: Arc::new(ContextInner { database }),
+            inner: Arc::new(ContextInner {
+                database,
+                token_ttl: Duration::seconds(config.token_ttl_secs as i64),
+                username_policy: UsernamePolicy::new(config),
+                username_change_cooldown: Duration::seconds(
+                    config.username_change_cooldown_secs as i64,
+                ),
+                account_deletion_grace: Duration::seconds(
+                    config.account_deletion_grace_secs as i64,
+                ),
+                presence,
+                save_limits: SaveLimits {
+                    max_value_bytes: config.save_max_value_bytes,
+                    max_keys: config.save_max_keys,
+                },
+                idempotency_ttl: Duration::seconds(config.idempotency_ttl_secs as i64),
+            }),
         }
     }
 
     pub fn database(&self) -> &Database {
         &self.inner.database
     }
+
+    pub fn token_ttl(&self) -> Duration {
+        self.inner.token_ttl
+    }
+
+    pub fn username_policy(&self) -> &UsernamePolicy {
+        &self.inner.username_policy
+    }
+
+    pub fn username_change_cooldown(&self) -> Duration {
+        self.inner.username_change_cooldown
+    }
+
+    pub fn account_deletion_grace(&self) -> Duration {
+        self.inner.account_deletion_grace
+    }
+
+    pub fn presence(&self) -> &PresenceStore {
+        &self.inner.presence
+    }
+
+    pub fn save_limits(&self) -> SaveLimits {
+        self.inner.save_limits
+    }
+
+    pub fn idempotency_ttl(&self) -> Duration {
+        self.inner.idempotency_ttl
+    }
 }
 
 const fn is_send<T: Send>() {}
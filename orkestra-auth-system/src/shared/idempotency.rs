@@ -0,0 +1,390 @@
+use std::time::Duration;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header::CONTENT_TYPE, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+use time::OffsetDateTime;
+use tracing::{error, info, info_span, warn, Instrument};
+
+use super::{
+    config::AppConfig,
+    context::Context,
+    database::Database,
+    utils::{bad_request_json, conflict_json, internal_error_json},
+};
+
+/// Header clients put a unique key per logical operation into, retries reuse it
+pub static IDEMPOTENCY_KEY_HEADER: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Set on responses replayed from an earlier request with the same key
+pub static IDEMPOTENT_REPLAYED_HEADER: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const KEY_MAX_LEN: usize = 255;
+const BODY_MAX_BYTES: usize = 1024 * 1024;
+
+/// A request that is still running after this long is assumed to be lost,
+/// e.g. the client went away and the handler was dropped
+const ABANDONED_AFTER_SECS: i64 = 60;
+
+#[derive(FromRow)]
+struct StoredKey {
+    request_hash: String,
+    status_code: Option<i32>,
+    content_type: Option<String>,
+    response_body: Option<Vec<u8>>,
+}
+
+enum Claim {
+    /// This request handles the key
+    Owned,
+    Replay {
+        status_code: i32,
+        content_type: Option<String>,
+        body: Vec<u8>,
+    },
+    InProgress,
+    PayloadMismatch,
+}
+
+/// Replays the first response for repeated requests carrying the same
+/// `Idempotency-Key` and payload. The scope separates keys of different endpoints.
+/// Requests without the header pass through.
+pub async fn idempotency(
+    State(scope): State<&'static str>,
+    Extension(context): Extension<Context>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = request.headers().get(&IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+
+    let Some(key) = key
+        .to_str()
+        .ok()
+        .filter(|key| valid_key(key))
+        .map(str::to_string)
+    else {
+        return bad_request_json(serde_json::json!({
+            "error": "Invalid idempotency key"
+        }))
+        .into_response();
+    };
+
+    let (parts, body) = request.into_parts();
+
+    let Ok(body) = to_bytes(body, BODY_MAX_BYTES).await else {
+        return bad_request_json(serde_json::json!({
+            "error": "Request body is too large"
+        }))
+        .into_response();
+    };
+
+    let project = parts
+        .headers
+        .get("x-project")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    let hash = fingerprint(parts.method.as_str(), parts.uri.path(), project, &body);
+
+    let database = context.database();
+
+    let claim = match claim(database, scope, &key, &hash, context.idempotency_ttl())
+        .in_current_span()
+        .await
+    {
+        Ok(claim) => claim,
+        Err(err) => {
+            error!(event = "Couldn't claim idempotency key", error = %err);
+
+            return internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+            .into_response();
+        }
+    };
+
+    match claim {
+        Claim::Owned => {}
+        Claim::Replay {
+            status_code,
+            content_type,
+            body,
+        } => {
+            info!(event = "Replaying idempotent response", scope = scope);
+
+            return replay(status_code, content_type, body);
+        }
+        Claim::InProgress => {
+            return conflict_json(serde_json::json!({
+                "error": "A request with this idempotency key is still in progress"
+            }))
+            .into_response();
+        }
+        Claim::PayloadMismatch => {
+            warn!(
+                event = "Idempotency key reused with a different payload",
+                scope = scope
+            );
+
+            return conflict_json(serde_json::json!({
+                "error": "Idempotency key was already used with a different request"
+            }))
+            .into_response();
+        }
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, usize::MAX).await.unwrap_or_default();
+
+    // Server errors aren't an outcome worth replaying, the retry gets a fresh try
+    let result = if parts.status.is_server_error() {
+        release(database, scope, &key).in_current_span().await
+    } else {
+        let content_type = parts
+            .headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok());
+
+        complete(
+            database,
+            scope,
+            &key,
+            parts.status.as_u16() as i32,
+            content_type,
+            &body,
+        )
+        .in_current_span()
+        .await
+    };
+
+    if let Err(err) = result {
+        error!(event = "Couldn't store idempotent response", error = %err);
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+async fn claim(
+    database: &Database,
+    scope: &str,
+    key: &str,
+    hash: &str,
+    ttl: time::Duration,
+) -> Result<Claim, sqlx::Error> {
+    // Takes the key if it's free, expired or abandoned
+    const CLAIM_QUERY: &str =
+        "INSERT INTO idempotency_keys (scope, key, request_hash, expires_at) \
+        VALUES ($1, $2, $3, $4) ON CONFLICT (scope, key) DO UPDATE \
+        SET request_hash = excluded.request_hash, status_code = NULL, content_type = NULL, \
+        response_body = NULL, created_at = now(), expires_at = excluded.expires_at \
+        WHERE idempotency_keys.expires_at <= now() OR (idempotency_keys.status_code IS NULL \
+        AND idempotency_keys.created_at < now() - make_interval(secs => $5) \
+        AND idempotency_keys.request_hash = excluded.request_hash) \
+        RETURNING true;";
+    const EXISTING_QUERY: &str = "SELECT request_hash, status_code, content_type, response_body \
+        FROM idempotency_keys WHERE scope = $1 AND key = $2;";
+
+    let owned: Option<bool> = sqlx::query_scalar(CLAIM_QUERY)
+        .bind(scope)
+        .bind(key)
+        .bind(hash)
+        .bind(OffsetDateTime::now_utc() + ttl)
+        .bind(ABANDONED_AFTER_SECS as f64)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    if owned.is_some() {
+        return Ok(Claim::Owned);
+    }
+
+    let existing: Option<StoredKey> = sqlx::query_as(EXISTING_QUERY)
+        .bind(scope)
+        .bind(key)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(match existing {
+        // Released between the two queries
+        None => Claim::InProgress,
+        Some(stored) if stored.request_hash != hash => Claim::PayloadMismatch,
+        Some(StoredKey {
+            status_code: Some(status_code),
+            content_type,
+            response_body,
+            ..
+        }) => Claim::Replay {
+            status_code,
+            content_type,
+            body: response_body.unwrap_or_default(),
+        },
+        Some(_) => Claim::InProgress,
+    })
+}
+
+async fn complete(
+    database: &Database,
+    scope: &str,
+    key: &str,
+    status_code: i32,
+    content_type: Option<&str>,
+    body: &[u8],
+) -> Result<(), sqlx::Error> {
+    const COMPLETE_QUERY: &str = "UPDATE idempotency_keys \
+        SET status_code = $3, content_type = $4, response_body = $5 \
+        WHERE scope = $1 AND key = $2;";
+
+    sqlx::query(COMPLETE_QUERY)
+        .bind(scope)
+        .bind(key)
+        .bind(status_code)
+        .bind(content_type)
+        .bind(body)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(())
+}
+
+async fn release(database: &Database, scope: &str, key: &str) -> Result<(), sqlx::Error> {
+    const RELEASE_QUERY: &str = "DELETE FROM idempotency_keys \
+        WHERE scope = $1 AND key = $2 AND status_code IS NULL;";
+
+    sqlx::query(RELEASE_QUERY)
+        .bind(scope)
+        .bind(key)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(())
+}
+
+fn replay(status_code: i32, content_type: Option<String>, body: Vec<u8>) -> Response {
+    let status = u16::try_from(status_code)
+        .ok()
+        .and_then(|status| StatusCode::from_u16(status).ok())
+        .unwrap_or(StatusCode::OK);
+
+    let mut response = (status, body).into_response();
+    let headers = response.headers_mut();
+
+    if let Some(content_type) = content_type.and_then(|value| HeaderValue::from_str(&value).ok()) {
+        headers.insert(CONTENT_TYPE, content_type);
+    }
+
+    headers.insert(
+        IDEMPOTENT_REPLAYED_HEADER.clone(),
+        HeaderValue::from_static("true"),
+    );
+
+    response
+}
+
+/// Keys are 1..255 visible ASCII characters, UUIDs are a good choice
+pub fn valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= KEY_MAX_LEN && key.chars().all(|c| c.is_ascii_graphic())
+}
+
+/// Hash of everything that makes two requests the same operation
+pub fn fingerprint(method: &str, path: &str, project: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+
+    for part in [method.as_bytes(), path.as_bytes(), project.as_bytes()] {
+        hasher.update(part);
+        hasher.update([0]);
+    }
+
+    hasher.update(body);
+
+    hex::encode(hasher.finalize())
+}
+
+/// Deletes expired idempotency keys from time to time
+pub struct IdempotencyPurger {
+    database: Database,
+    interval: Duration,
+}
+
+impl IdempotencyPurger {
+    pub fn new(config: &AppConfig, database: Database) -> Self {
+        Self {
+            database,
+            interval: Duration::from_secs(config.idempotency_purge_interval_secs.max(1)),
+        }
+    }
+
+    pub fn spawn(self) {
+        tokio::spawn(
+            async move {
+                let mut interval = tokio::time::interval(self.interval);
+
+                loop {
+                    interval.tick().await;
+
+                    self.purge().in_current_span().await;
+                }
+            }
+            .instrument(info_span!("idempotency_purger")),
+        );
+    }
+
+    async fn purge(&self) {
+        const PURGE_QUERY: &str = "DELETE FROM idempotency_keys WHERE expires_at <= now();";
+
+        match sqlx::query(PURGE_QUERY)
+            .execute(self.database.as_ref())
+            .in_current_span()
+            .await
+        {
+            Ok(result) if result.rows_affected() > 0 => {
+                info!(
+                    event = "Purged expired idempotency keys",
+                    count = result.rows_affected()
+                );
+            }
+            Ok(_) => {}
+            Err(err) => {
+                error!(event = "Couldn't purge idempotency keys", error = %err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fingerprint, valid_key};
+
+    #[test]
+    fn key_validation() {
+        assert!(valid_key("6f1c2a4e-0b7d-4c1e-9a51-3e2f8d9b7c10"));
+        assert!(valid_key("retry:signup/42"));
+
+        assert!(!valid_key(""));
+        assert!(!valid_key("with space"));
+        assert!(!valid_key(&"k".repeat(256)));
+    }
+
+    #[test]
+    fn fingerprint_covers_the_whole_request() {
+        let base = fingerprint("POST", "/auth/v1/signup", "", b"{}");
+
+        assert_eq!(base, fingerprint("POST", "/auth/v1/signup", "", b"{}"));
+        assert_ne!(base, fingerprint("POST", "/auth/v1/signup", "", b"{ }"));
+        assert_ne!(base, fingerprint("POST", "/auth/v1/signup", "game", b"{}"));
+        assert_ne!(base, fingerprint("POST", "/auth/v1/signu", "p", b"{}"));
+    }
+}
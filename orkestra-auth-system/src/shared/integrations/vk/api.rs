@@ -1,6 +1,8 @@
 use std::{net::Ipv4Addr, sync::Arc};
 use tracing::Instrument;
 
+use crate::shared::project::VkCredentials;
+
 use super::{
     error::{VkAuthError, VkError, VkResult},
     models::VkUserProfileData,
@@ -14,34 +16,43 @@ pub struct VkService {
 #[derive(Debug)]
 struct VkServiceInner {
     client: reqwest::Client,
-    game_id: String,
-    secret: String,
+}
+
+impl Default for VkService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl VkService {
     const BASE_URL: &'static str = "https://vkplay.ru/app";
 
-    pub fn new(game_id: &str, secret: &str) -> Self {
+    pub fn new() -> Self {
         let client = reqwest::Client::new();
 
         Self {
-            inner: Arc::new(VkServiceInner {
-                client,
-                game_id: game_id.to_string(),
-                secret: secret.to_string(),
-            }),
+            inner: Arc::new(VkServiceInner { client }),
         }
     }
 
-    pub async fn auth(&self, uid: &str, hash: &str, ip: Ipv4Addr) -> Result<(), VkAuthError> {
-        let sign = self.calc_sign(serde_json::json!({
-            "appid": self.inner.game_id,
-            "uid": uid,
-            "hash": hash,
-            "ip": ip.to_string()
-        }));
+    pub async fn auth(
+        &self,
+        credentials: &VkCredentials,
+        uid: &str,
+        hash: &str,
+        ip: Ipv4Addr,
+    ) -> Result<(), VkAuthError> {
+        let sign = Self::calc_sign(
+            credentials,
+            serde_json::json!({
+                "appid": credentials.game_id,
+                "uid": uid,
+                "hash": hash,
+                "ip": ip.to_string()
+            }),
+        );
 
-        let url = format!("{}/{}/gas", Self::BASE_URL, self.inner.game_id);
+        let url = format!("{}/{}/gas", Self::BASE_URL, credentials.game_id);
 
         let response = self
             .inner
@@ -80,13 +91,20 @@ impl VkService {
         }
     }
 
-    pub async fn get_user_profile(&self, uid: &str) -> Result<VkUserProfileData, VkError> {
-        let sign = self.calc_sign(serde_json::json!({
-            "appid": self.inner.game_id,
-            "uid": uid,
-        }));
+    pub async fn get_user_profile(
+        &self,
+        credentials: &VkCredentials,
+        uid: &str,
+    ) -> Result<VkUserProfileData, VkError> {
+        let sign = Self::calc_sign(
+            credentials,
+            serde_json::json!({
+                "appid": credentials.game_id,
+                "uid": uid,
+            }),
+        );
 
-        let url = format!("{}/{}/user/profile", Self::BASE_URL, self.inner.game_id);
+        let url = format!("{}/{}/user/profile", Self::BASE_URL, credentials.game_id);
 
         let response = self
             .inner
@@ -108,8 +126,8 @@ impl VkService {
         response.into()
     }
 
-    fn calc_sign(&self, json: serde_json::Value) -> String {
-        let json = format!("{json}{}", self.inner.secret);
+    fn calc_sign(credentials: &VkCredentials, json: serde_json::Value) -> String {
+        let json = format!("{json}{}", credentials.secret);
         let digest = md5::compute(json);
 
         format!("{:x}", digest)
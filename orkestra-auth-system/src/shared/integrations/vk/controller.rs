@@ -6,20 +6,31 @@ use axum::{
     Extension,
 };
 
-use tracing::{error, info, info_span, Instrument};
+use tracing::{error, info, info_span, warn, Instrument};
 
 use crate::shared::{
+    audit::{self, AuditEvent},
+    auth::token,
+    ban::{self, banned_json},
+    client_gate::SupportedClient,
+    context::Context,
     integrations::vk::dto::UserProfileResponse,
-    utils::{bad_request_json, just_ok, ok},
+    project::Project,
+    utils::{bad_request_json, internal_error_json, ok},
 };
 
 use super::{
     api::VkService,
     dto::{UserProfileData, VkAuthData},
+    error::VkLinkError,
+    use_case,
 };
 
 pub async fn auth(
     Extension(context): Extension<VkService>,
+    Extension(app_context): Extension<Context>,
+    project: Project,
+    client: SupportedClient,
     ConnectInfo(ip): ConnectInfo<SocketAddr>,
     Query(request): Query<VkAuthData>,
 ) -> impl IntoResponse {
@@ -28,9 +39,23 @@ pub async fn auth(
 
     info!(
         event = "Request to login user in VK",
-        username = request.uid
+        project = project.slug,
+        username = request.uid,
+        client_platform = ?client.platform,
+        client_version = ?client.version.as_ref().map(ToString::to_string),
     );
 
+    let Some(credentials) = project.vk_credentials() else {
+        warn!(
+            event = "VK credentials are not configured",
+            project = project.slug
+        );
+
+        return bad_request_json(serde_json::json!({
+            "error": "VK integration is not configured for the project"
+        }));
+    };
+
     let SocketAddr::V4(ip) = ip else {
         return bad_request_json(serde_json::json!({
             "error": "Couldn't parse request ip address"
@@ -43,21 +68,87 @@ pub async fn auth(
     );
 
     let result = context
-        .auth(&request.uid, &request.hash, *ip.ip())
+        .auth(&credentials, &request.uid, &request.hash, *ip.ip())
         .in_current_span()
         .await;
 
-    match result {
-        Ok(_) => {
-            info!(event = "Successfully login");
+    if let Err(err) = result {
+        error!(event = %err);
+
+        return bad_request_json(serde_json::json!({
+            "error": err.to_string()
+        }));
+    }
+
+    let database = app_context.database();
+
+    let result = use_case::link_user(database, &context, &project, &credentials, &request.uid)
+        .in_current_span()
+        .await;
 
-            just_ok()
+    let user_id = match result {
+        Ok(user_id) => user_id,
+        Err(VkLinkError::Database(err)) => {
+            error!(event = "Database error", error = %err);
+
+            return internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }));
         }
         Err(err) => {
             error!(event = %err);
 
-            bad_request_json(serde_json::json!({
+            return bad_request_json(serde_json::json!({
                 "error": err.to_string()
+            }));
+        }
+    };
+
+    match ban::active_ban(database.as_ref(), user_id)
+        .in_current_span()
+        .await
+    {
+        Ok(None) => {}
+        Ok(Some(ban)) => {
+            warn!(event = "Banned user tried to login", user_id = %user_id);
+
+            return banned_json(&ban);
+        }
+        Err(err) => {
+            error!(event = "Database error", error = %err);
+
+            return internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }));
+        }
+    }
+
+    match token::issue(database, project.id, user_id, app_context.token_ttl())
+        .in_current_span()
+        .await
+    {
+        Ok(token) => {
+            info!(event = "Successfully login", user_id = %user_id);
+
+            if let Err(err) = audit::record(
+                database.as_ref(),
+                user_id,
+                AuditEvent::VkLogin,
+                serde_json::json!({ "uid": request.uid }),
+            )
+            .in_current_span()
+            .await
+            {
+                warn!(event = "Couldn't record audit event", error = %err);
+            }
+
+            ok(token)
+        }
+        Err(err) => {
+            error!(event = "Couldn't issue access token", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
             }))
         }
     }
@@ -65,15 +156,31 @@ pub async fn auth(
 
 pub async fn get_user_profile(
     Extension(context): Extension<VkService>,
+    project: Project,
     Query(request): Query<UserProfileData>,
 ) -> impl IntoResponse {
     let span = info_span!("vk_user_profile");
     let _guard = span.enter();
 
-    info!(event = "Request user profile in VK", username = request.uid);
+    info!(
+        event = "Request user profile in VK",
+        project = project.slug,
+        username = request.uid
+    );
+
+    let Some(credentials) = project.vk_credentials() else {
+        warn!(
+            event = "VK credentials are not configured",
+            project = project.slug
+        );
+
+        return bad_request_json(serde_json::json!({
+            "error": "VK integration is not configured for the project"
+        }));
+    };
 
     let result = context
-        .get_user_profile(&request.uid)
+        .get_user_profile(&credentials, &request.uid)
         .in_current_span()
         .await;
 
@@ -90,3 +90,15 @@ pub enum VkAuthError {
     #[error("User has not paid for this game (for P2P games)")]
     NoPayment,
 }
+
+#[derive(Debug, Error)]
+pub enum VkLinkError {
+    #[error("Account is being linked by another request, retry later")]
+    Conflict,
+
+    #[error("Account is scheduled for deletion")]
+    AccountDeleted,
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
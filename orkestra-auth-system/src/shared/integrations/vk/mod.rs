@@ -1,6 +1,7 @@
 mod controller;
 mod error;
 mod models;
+mod use_case;
 
 pub mod api;
 pub mod dto;
@@ -0,0 +1,163 @@
+use rand::{distributions::Alphanumeric, Rng};
+use sqlx::types::Uuid;
+use tracing::{info, warn, Instrument};
+
+use crate::shared::{
+    database::Database,
+    project::{Project, VkCredentials},
+};
+
+use super::{api::VkService, error::VkLinkError};
+
+const PROVIDER: &str = "vk";
+
+/// Returns the user linked to the VK account, creating one with a profile
+/// seeded from VK on the first login
+pub async fn link_user(
+    database: &Database,
+    vk_service: &VkService,
+    project: &Project,
+    credentials: &VkCredentials,
+    uid: &str,
+) -> Result<Uuid, VkLinkError> {
+    const INSERT_USER_QUERY: &str =
+        "INSERT INTO users (project_id, username, password) VALUES ($1, $2, '') RETURNING id;";
+    const INSERT_IDENTITY_QUERY: &str =
+        "INSERT INTO identities (project_id, user_id, provider, provider_uid) \
+        VALUES ($1, $2, $3, $4) ON CONFLICT DO NOTHING;";
+    const INSERT_PROFILE_QUERY: &str =
+        "INSERT INTO profiles (user_id, display_name, avatar_url) VALUES ($1, $2, $3);";
+
+    if let Some(user_id) = linked_user(database, project, uid)
+        .in_current_span()
+        .await?
+    {
+        return Ok(user_id);
+    }
+
+    let (display_name, avatar_url) = match vk_service
+        .get_user_profile(credentials, uid)
+        .in_current_span()
+        .await
+    {
+        Ok(profile) => (profile.nick, Some(profile.avatar).filter(|a| !a.is_empty())),
+        Err(err) => {
+            warn!(
+                event = "Couldn't fetch VK profile, using defaults",
+                uid = uid,
+                error = err.errmsg
+            );
+
+            (format!("{PROVIDER}_{uid}"), None)
+        }
+    };
+
+    // The plain username may belong to an unrelated account, the second one
+    // gets a random suffix
+    let usernames = [
+        format!("{PROVIDER}_{uid}"),
+        format!("{PROVIDER}_{uid}_{}", username_suffix()),
+    ];
+
+    for username in usernames {
+        let mut transaction = database.as_ref().begin().in_current_span().await?;
+
+        let inserted = sqlx::query_scalar(INSERT_USER_QUERY)
+            .bind(project.id)
+            .bind(&username)
+            .fetch_one(&mut *transaction)
+            .in_current_span()
+            .await;
+
+        let user_id: Uuid = match inserted {
+            Ok(user_id) => user_id,
+            Err(sqlx::Error::Database(err)) if err.is_unique_violation() => {
+                transaction.rollback().in_current_span().await?;
+
+                // A concurrent first login created the user with this username
+                if let Some(user_id) = linked_user(database, project, uid)
+                    .in_current_span()
+                    .await?
+                {
+                    return Ok(user_id);
+                }
+
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let linked = sqlx::query(INSERT_IDENTITY_QUERY)
+            .bind(project.id)
+            .bind(user_id)
+            .bind(PROVIDER)
+            .bind(uid)
+            .execute(&mut *transaction)
+            .in_current_span()
+            .await?;
+
+        if linked.rows_affected() == 0 {
+            // A concurrent login has linked the account first
+            transaction.rollback().in_current_span().await?;
+
+            return linked_user(database, project, uid)
+                .in_current_span()
+                .await?
+                .ok_or(VkLinkError::Conflict);
+        }
+
+        sqlx::query(INSERT_PROFILE_QUERY)
+            .bind(user_id)
+            .bind(&display_name)
+            .bind(&avatar_url)
+            .execute(&mut *transaction)
+            .in_current_span()
+            .await?;
+
+        transaction.commit().in_current_span().await?;
+
+        info!(
+            event = "Created user for VK account",
+            project = project.slug,
+            uid = uid,
+            user_id = %user_id
+        );
+
+        return Ok(user_id);
+    }
+
+    Err(VkLinkError::Conflict)
+}
+
+/// User already linked to the VK account
+async fn linked_user(
+    database: &Database,
+    project: &Project,
+    uid: &str,
+) -> Result<Option<Uuid>, VkLinkError> {
+    const IDENTITY_QUERY: &str = "SELECT i.user_id, u.deleted_at IS NOT NULL FROM identities i \
+        JOIN users u ON u.id = i.user_id \
+        WHERE i.project_id = $1 AND i.provider = $2 AND i.provider_uid = $3;";
+
+    let identity: Option<(Uuid, bool)> = sqlx::query_as(IDENTITY_QUERY)
+        .bind(project.id)
+        .bind(PROVIDER)
+        .bind(uid)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    match identity {
+        Some((_, true)) => Err(VkLinkError::AccountDeleted),
+        Some((user_id, false)) => Ok(Some(user_id)),
+        None => Ok(None),
+    }
+}
+
+fn username_suffix() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(4)
+        .map(|c| char::from(c).to_ascii_lowercase())
+        .collect()
+}
@@ -1,7 +1,18 @@
+pub mod audit;
+pub mod auth;
+pub mod ban;
+pub mod client_gate;
+pub mod client_version;
 pub mod config;
 pub mod context;
 pub mod database;
+pub mod idempotency;
 pub mod integrations;
 pub mod logger;
+pub mod notification;
+pub mod pagination;
+pub mod project;
 pub mod router;
+pub mod services;
+pub mod username;
 pub mod utils;
@@ -0,0 +1,44 @@
+use sqlx::{types::Uuid, PgExecutor};
+use tracing::Instrument;
+
+/// Notifications the auth system delivers itself, services post their own kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NotificationKind {
+    FriendRequest,
+    FriendRequestAccepted,
+    ReportResolved,
+}
+
+impl NotificationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::FriendRequest => "friend_request",
+            NotificationKind::FriendRequestAccepted => "friend_request_accepted",
+            NotificationKind::ReportResolved => "report_resolved",
+        }
+    }
+}
+
+/// Puts a notification into the user's inbox
+pub async fn deliver<'e, E>(
+    executor: E,
+    user_id: Uuid,
+    kind: NotificationKind,
+    payload: serde_json::Value,
+) -> Result<(), sqlx::Error>
+where
+    E: PgExecutor<'e>,
+{
+    const INSERT_QUERY: &str =
+        "INSERT INTO notifications (user_id, kind, payload) VALUES ($1, $2, $3);";
+
+    sqlx::query(INSERT_QUERY)
+        .bind(user_id)
+        .bind(kind.as_str())
+        .bind(payload)
+        .execute(executor)
+        .in_current_span()
+        .await?;
+
+    Ok(())
+}
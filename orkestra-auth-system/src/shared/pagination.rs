@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 100;
+
+/// `?limit=&offset=` query of paginated endpoints
+#[derive(Debug, Default, Deserialize)]
+pub struct PageQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl PageQuery {
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    pub fn offset(&self) -> i64 {
+        self.offset.unwrap_or(0).max(0)
+    }
+
+    /// Row count to fetch, one extra row tells whether there is a next page
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit() + 1
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_offset: Option<i64>,
+}
+
+impl<T> Page<T> {
+    /// Builds a page out of rows fetched with [`PageQuery::fetch_limit`]
+    pub fn new(mut rows: Vec<T>, query: &PageQuery) -> Self {
+        let limit = query.limit() as usize;
+
+        let next_offset = if rows.len() > limit {
+            rows.truncate(limit);
+            Some(query.offset() + query.limit())
+        } else {
+            None
+        };
+
+        Self {
+            items: rows,
+            next_offset,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Page, PageQuery};
+
+    #[test]
+    fn limits_are_clamped() {
+        let query = PageQuery {
+            limit: Some(1000),
+            offset: Some(-5),
+        };
+
+        assert_eq!(query.limit(), 100);
+        assert_eq!(query.offset(), 0);
+        assert_eq!(PageQuery::default().limit(), 50);
+    }
+
+    #[test]
+    fn next_offset_only_with_extra_row() {
+        let query = PageQuery {
+            limit: Some(2),
+            offset: Some(4),
+        };
+
+        let page = Page::new(vec![1, 2, 3], &query);
+        assert_eq!(page.items, vec![1, 2]);
+        assert_eq!(page.next_offset, Some(6));
+
+        let page = Page::new(vec![1, 2], &query);
+        assert_eq!(page.next_offset, None);
+    }
+}
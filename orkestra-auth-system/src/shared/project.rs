@@ -0,0 +1,156 @@
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, RawPathParams},
+    http::{request::Parts, HeaderName, StatusCode},
+    Json,
+};
+use sqlx::{prelude::FromRow, types::Uuid};
+use tracing::{error, Instrument};
+
+use super::{
+    config::AppConfig,
+    context::Context,
+    database::Database,
+    utils::{internal_error_json, not_found_json},
+};
+
+pub const DEFAULT_PROJECT: &str = "default";
+
+/// Path segment parameter selecting the project, as in `/projects/:project/...`
+pub const PROJECT_PATH_PARAM: &str = "project";
+
+/// Header selecting the project when the path has no project segment
+pub static PROJECT_HEADER: HeaderName = HeaderName::from_static("x-project");
+
+/// Game project every user, identity and token belongs to
+#[derive(Debug, Clone, FromRow)]
+pub struct Project {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub vk_game_id: Option<String>,
+    pub vk_gas_secret: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VkCredentials {
+    pub game_id: String,
+    pub secret: String,
+}
+
+impl Project {
+    pub fn is_default(&self) -> bool {
+        self.slug == DEFAULT_PROJECT
+    }
+
+    pub fn vk_credentials(&self) -> Option<VkCredentials> {
+        match (&self.vk_game_id, &self.vk_gas_secret) {
+            (Some(game_id), Some(secret)) => Some(VkCredentials {
+                game_id: game_id.clone(),
+                secret: secret.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub async fn find(database: &Database, slug: &str) -> Result<Option<Project>, sqlx::Error> {
+    const FIND_QUERY: &str =
+        "SELECT id, slug, name, vk_game_id, vk_gas_secret FROM projects WHERE slug = $1;";
+
+    sqlx::query_as(FIND_QUERY)
+        .bind(slug)
+        .fetch_optional(database.as_ref())
+        .in_current_span()
+        .await
+}
+
+/// Copies the VK credentials from the config to the default project, keeping
+/// single-game deployments working without any project setup
+pub async fn sync_default_credentials(
+    database: &Database,
+    config: &AppConfig,
+) -> Result<(), sqlx::Error> {
+    const UPDATE_QUERY: &str =
+        "UPDATE projects SET vk_game_id = $2, vk_gas_secret = $3 WHERE slug = $1;";
+
+    let (Some(game_id), Some(secret)) = (&config.vk_game_id, &config.vk_gas_secret) else {
+        return Ok(());
+    };
+
+    sqlx::query(UPDATE_QUERY)
+        .bind(DEFAULT_PROJECT)
+        .bind(game_id)
+        .bind(secret)
+        .execute(database.as_ref())
+        .in_current_span()
+        .await?;
+
+    Ok(())
+}
+
+/// Resolves the project from the `:project` path segment, then from the
+/// `X-Project` header, falling back to the default project
+#[async_trait]
+impl<S> FromRequestParts<S> for Project
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(project) = parts.extensions.get::<Project>() {
+            return Ok(project.clone());
+        }
+
+        let Some(context) = parts.extensions.get::<Context>().cloned() else {
+            error!(event = "Context is not attached to the router");
+
+            return Err(internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            })));
+        };
+
+        let from_path = RawPathParams::from_request_parts(parts, state)
+            .await
+            .ok()
+            .and_then(|params| {
+                params
+                    .iter()
+                    .find(|(key, _)| *key == PROJECT_PATH_PARAM)
+                    .map(|(_, value)| value.to_string())
+            });
+
+        let from_header = || {
+            parts
+                .headers
+                .get(&PROJECT_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.trim().to_string())
+        };
+
+        let slug = from_path
+            .or_else(from_header)
+            .unwrap_or_else(|| DEFAULT_PROJECT.to_string());
+
+        let project = match find(context.database(), &slug).in_current_span().await {
+            Ok(Some(project)) => project,
+            Ok(None) => {
+                return Err(not_found_json(serde_json::json!({
+                    "error": format!("Unknown project: {slug}")
+                })));
+            }
+            Err(err) => {
+                error!(event = "Couldn't resolve project", error = %err);
+
+                return Err(internal_error_json(serde_json::json!({
+                    "error": "Internal error"
+                })));
+            }
+        };
+
+        parts.extensions.insert(project.clone());
+
+        Ok(project)
+    }
+}
@@ -1,21 +1,54 @@
-use axum::{Extension, Router};
+use axum::{middleware, Extension, Router};
 use tower_http::trace::TraceLayer;
 
 use crate::plugins::*;
 
-use super::context::Context;
+use super::{auth::api_key::api_key_middleware, context::Context};
 
 pub fn base_router(router: Router) -> Router {
     Router::new().nest("/auth", router)
 }
 
 pub fn v1(context: Context) -> Router {
+    let account = account::router::service();
+    let api_keys = api_keys::router::service();
+    let change_username = change_username::router::service();
+    let client_versions = client_versions::router::service();
+    let reports = reports::router::service();
+    let notifications = notifications::router::service();
+    let announcements = announcements::router::service();
+    let friends = friends::router::service();
     let login = login::router::service();
+    let remote_config = remote_config::router::service();
+    let saves = saves::router::service();
     let signup = signup::router::service();
+    let presence = presence::router::service();
+    let profile = profile::router::service();
+    let projects = projects::router::service();
 
-    let merged = Router::new()
+    let routes = Router::new()
         .merge(login)
         .merge(signup)
+        .merge(profile)
+        .merge(change_username)
+        .merge(account)
+        .merge(friends)
+        .merge(presence)
+        .merge(saves)
+        .merge(remote_config)
+        .merge(client_versions)
+        .merge(reports)
+        .merge(notifications)
+        .merge(announcements)
+        .merge(api_keys)
+        .merge(projects);
+
+    // Every route is also served under `/projects/:project`, requests outside
+    // of it pick the project from the `X-Project` header
+    let merged = Router::new()
+        .merge(routes.clone())
+        .nest("/projects/:project", routes)
+        .layer(middleware::from_fn(api_key_middleware))
         .layer(Extension(context));
 
     let v1 = Router::new()
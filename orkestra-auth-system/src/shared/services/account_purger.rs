@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use time::OffsetDateTime;
+use tracing::{error, info, info_span, Instrument};
+
+use crate::shared::{config::AppConfig, database::Database};
+
+/// Hard-deletes accounts whose deletion grace period is over. Rows owned by the
+/// account are removed by cascades, audit rows were anonymized on soft delete.
+#[derive(Clone)]
+pub struct AccountPurger {
+    database: Database,
+    grace: time::Duration,
+    interval: Duration,
+}
+
+impl AccountPurger {
+    pub fn new(config: &AppConfig, database: Database) -> Self {
+        Self {
+            database,
+            grace: time::Duration::seconds(config.account_deletion_grace_secs as i64),
+            interval: Duration::from_secs(config.account_purge_interval_secs.max(1)),
+        }
+    }
+
+    pub fn spawn(self) {
+        tokio::spawn(
+            async move {
+                let mut interval = tokio::time::interval(self.interval);
+
+                loop {
+                    interval.tick().await;
+
+                    self.purge().in_current_span().await;
+                }
+            }
+            .instrument(info_span!("account_purger")),
+        );
+    }
+
+    async fn purge(&self) {
+        const PURGE_QUERY: &str =
+            "DELETE FROM users WHERE deleted_at IS NOT NULL AND deleted_at < $1;";
+
+        let cutoff = OffsetDateTime::now_utc() - self.grace;
+
+        let result = sqlx::query(PURGE_QUERY)
+            .bind(cutoff)
+            .execute(self.database.as_ref())
+            .in_current_span()
+            .await;
+
+        match result {
+            Ok(result) if result.rows_affected() > 0 => {
+                info!(
+                    event = "Purged deleted accounts",
+                    count = result.rows_affected()
+                );
+            }
+            Ok(_) => {}
+            Err(err) => {
+                error!(event = "Couldn't purge deleted accounts", error = %err);
+            }
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod account_purger;
+pub mod presence;
@@ -0,0 +1,250 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use dashmap::{mapref::one::RefMut, DashMap};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+use tracing::{debug, info_span, Instrument};
+
+use crate::shared::config::AppConfig;
+
+/// What the client reports it's doing, being in a session is reported by the server manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Activity {
+    Menu,
+    Lobby,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceStatus {
+    Menu,
+    Lobby,
+    InSession,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Presence {
+    pub user_id: Uuid,
+    pub status: PresenceStatus,
+    pub lobby_id: Option<String>,
+    pub session_id: Option<String>,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    activity: Activity,
+    lobby_id: Option<String>,
+    session_id: Option<String>,
+    updated_at: OffsetDateTime,
+    expires_at: Instant,
+}
+
+/// In-memory presence of online players. Entries live for the presence TTL
+/// after the last heartbeat, players without an entry are offline.
+#[derive(Clone, Debug)]
+pub struct PresenceStore {
+    inner: Arc<PresenceStoreInner>,
+}
+
+#[derive(Debug)]
+struct PresenceStoreInner {
+    ttl: Duration,
+    entries: DashMap<Uuid, Entry>,
+}
+
+impl PresenceStore {
+    pub fn new(config: &AppConfig) -> Self {
+        Self::with_ttl(Duration::from_secs(config.presence_ttl_secs.max(1)))
+    }
+
+    fn with_ttl(ttl: Duration) -> Self {
+        Self {
+            inner: Arc::new(PresenceStoreInner {
+                ttl,
+                entries: DashMap::new(),
+            }),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.inner.ttl
+    }
+
+    /// Client heartbeat, keeps the session set by the server manager
+    pub fn heartbeat(
+        &self,
+        user_id: Uuid,
+        activity: Activity,
+        lobby_id: Option<String>,
+    ) -> Presence {
+        let mut entry = self.live_entry(user_id);
+
+        entry.activity = activity;
+        entry.lobby_id = lobby_id.filter(|_| activity == Activity::Lobby);
+        self.touch(&mut entry);
+
+        to_presence(user_id, &entry)
+    }
+
+    pub fn set_session(&self, user_id: Uuid, session_id: String) {
+        let mut entry = self.live_entry(user_id);
+
+        entry.session_id = Some(session_id);
+        self.touch(&mut entry);
+    }
+
+    /// Clears the session only if it's still the current one, so a late
+    /// leave doesn't override a join into another session
+    pub fn clear_session(&self, user_id: Uuid, session_id: &str) {
+        if let Some(mut entry) = self.inner.entries.get_mut(&user_id) {
+            if entry.session_id.as_deref() == Some(session_id) {
+                entry.session_id = None;
+                entry.updated_at = OffsetDateTime::now_utc();
+            }
+        }
+    }
+
+    pub fn remove(&self, user_id: Uuid) {
+        self.inner.entries.remove(&user_id);
+    }
+
+    /// Presence of the online users among `user_ids`
+    pub fn get_many(&self, user_ids: &[Uuid]) -> Vec<Presence> {
+        let now = Instant::now();
+
+        user_ids
+            .iter()
+            .filter_map(|user_id| {
+                let entry = self.inner.entries.get(user_id)?;
+
+                (entry.expires_at > now).then(|| to_presence(*user_id, &entry))
+            })
+            .collect()
+    }
+
+    pub fn spawn_sweeper(&self) {
+        let store = self.clone();
+
+        tokio::spawn(
+            async move {
+                let mut interval = tokio::time::interval(store.ttl());
+
+                loop {
+                    interval.tick().await;
+
+                    store.sweep();
+                }
+            }
+            .instrument(info_span!("presence_sweeper")),
+        );
+    }
+
+    fn sweep(&self) {
+        let now = Instant::now();
+        let before = self.inner.entries.len();
+
+        self.inner.entries.retain(|_, entry| entry.expires_at > now);
+
+        debug!(
+            event = "Swept expired presence",
+            count = before.saturating_sub(self.inner.entries.len())
+        );
+    }
+
+    /// Entry of the user, expired ones not swept yet start over
+    fn live_entry(&self, user_id: Uuid) -> RefMut<'_, Uuid, Entry> {
+        let mut entry = self
+            .inner
+            .entries
+            .entry(user_id)
+            .or_insert_with(|| self.new_entry());
+
+        if entry.expires_at <= Instant::now() {
+            *entry = self.new_entry();
+        }
+
+        entry
+    }
+
+    fn new_entry(&self) -> Entry {
+        Entry {
+            activity: Activity::Menu,
+            lobby_id: None,
+            session_id: None,
+            updated_at: OffsetDateTime::now_utc(),
+            expires_at: Instant::now() + self.inner.ttl,
+        }
+    }
+
+    fn touch(&self, entry: &mut Entry) {
+        entry.updated_at = OffsetDateTime::now_utc();
+        entry.expires_at = Instant::now() + self.inner.ttl;
+    }
+}
+
+fn to_presence(user_id: Uuid, entry: &Entry) -> Presence {
+    let status = match (&entry.session_id, entry.activity) {
+        (Some(_), _) => PresenceStatus::InSession,
+        (None, Activity::Menu) => PresenceStatus::Menu,
+        (None, Activity::Lobby) => PresenceStatus::Lobby,
+    };
+
+    Presence {
+        user_id,
+        status,
+        lobby_id: entry.lobby_id.clone(),
+        session_id: entry.session_id.clone(),
+        updated_at: entry.updated_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use sqlx::types::Uuid;
+
+    use super::{Activity, PresenceStatus, PresenceStore};
+
+    #[test]
+    fn session_overrides_activity_until_cleared() {
+        let store = PresenceStore::with_ttl(Duration::from_secs(60));
+        let user_id = Uuid::from_u128(1);
+
+        store.heartbeat(user_id, Activity::Lobby, Some("lobby".to_string()));
+        store.set_session(user_id, "first".to_string());
+        assert_eq!(
+            store.get_many(&[user_id])[0].status,
+            PresenceStatus::InSession
+        );
+
+        store.clear_session(user_id, "second");
+        assert_eq!(
+            store.get_many(&[user_id])[0].status,
+            PresenceStatus::InSession
+        );
+
+        store.clear_session(user_id, "first");
+        let presence = &store.get_many(&[user_id])[0];
+        assert_eq!(presence.status, PresenceStatus::Lobby);
+        assert_eq!(presence.lobby_id.as_deref(), Some("lobby"));
+    }
+
+    #[test]
+    fn expired_entries_are_offline() {
+        let store = PresenceStore::with_ttl(Duration::ZERO);
+        let user_id = Uuid::from_u128(1);
+
+        store.heartbeat(user_id, Activity::Menu, None);
+
+        assert!(store.get_many(&[user_id]).is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use thiserror::Error;
+
+use super::config::AppConfig;
+
+const USERNAME_MIN_LEN: usize = 3;
+const USERNAME_MAX_LEN: usize = 20;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum UsernameError {
+    #[error("Username must be from {USERNAME_MIN_LEN} to {USERNAME_MAX_LEN} characters long")]
+    InvalidLength,
+
+    #[error("Username may contain only latin letters, digits and underscores")]
+    InvalidCharacters,
+
+    #[error("Username is reserved")]
+    Reserved,
+
+    #[error("Username contains forbidden words")]
+    Profane,
+}
+
+/// Rules shared by signup and rename. Reserved names are matched exactly, or as
+/// a prefix when they end with `*`; profane words are matched as substrings.
+/// Both comparisons ignore case.
+#[derive(Debug, Clone)]
+pub struct UsernamePolicy {
+    reserved: Vec<String>,
+    profane: Vec<String>,
+}
+
+impl UsernamePolicy {
+    pub fn new(config: &AppConfig) -> Self {
+        Self::from_lists(&config.reserved_usernames, &config.profane_words)
+    }
+
+    pub fn from_lists(reserved: &[String], profane: &[String]) -> Self {
+        let normalize = |list: &[String]| {
+            list.iter()
+                .map(|entry| entry.trim().to_lowercase())
+                .filter(|entry| !entry.is_empty())
+                .collect()
+        };
+
+        Self {
+            reserved: normalize(reserved),
+            profane: normalize(profane),
+        }
+    }
+
+    pub fn check(&self, username: &str) -> Result<(), UsernameError> {
+        if !(USERNAME_MIN_LEN..=USERNAME_MAX_LEN).contains(&username.chars().count()) {
+            return Err(UsernameError::InvalidLength);
+        }
+
+        if !valid_username(username) {
+            return Err(UsernameError::InvalidCharacters);
+        }
+
+        let username = username.to_lowercase();
+
+        let reserved = self
+            .reserved
+            .iter()
+            .any(|entry| match entry.strip_suffix('*') {
+                Some(prefix) => username.starts_with(prefix),
+                None => username.eq(entry),
+            });
+
+        if reserved {
+            return Err(UsernameError::Reserved);
+        }
+
+        if self.profane.iter().any(|word| username.contains(word)) {
+            return Err(UsernameError::Profane);
+        }
+
+        Ok(())
+    }
+}
+
+fn valid_username(username: &str) -> bool {
+    username
+        .chars()
+        .all(|c| matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UsernameError, UsernamePolicy};
+
+    fn policy() -> UsernamePolicy {
+        UsernamePolicy::from_lists(
+            &["Admin".to_string(), "vk_*".to_string(), " ".to_string()],
+            &["badword".to_string()],
+        )
+    }
+
+    #[test]
+    fn username_length() {
+        assert_eq!(policy().check(""), Err(UsernameError::InvalidLength));
+        assert_eq!(policy().check("ab"), Err(UsernameError::InvalidLength));
+        assert_eq!(
+            policy().check(&"a".repeat(21)),
+            Err(UsernameError::InvalidLength)
+        );
+
+        assert_eq!(policy().check("abc"), Ok(()));
+        assert_eq!(policy().check(&"a".repeat(20)), Ok(()));
+    }
+
+    #[test]
+    fn username_characters() {
+        assert_eq!(
+            policy().check("jack sparrow"),
+            Err(UsernameError::InvalidCharacters)
+        );
+        assert_eq!(
+            policy().check("джек"),
+            Err(UsernameError::InvalidCharacters)
+        );
+
+        // Counted in characters, not bytes
+        assert_eq!(
+            policy().check(&"ж".repeat(12)),
+            Err(UsernameError::InvalidCharacters)
+        );
+
+        assert_eq!(policy().check("Jack_Sparrow_1"), Ok(()));
+    }
+
+    #[test]
+    fn username_reserved() {
+        assert_eq!(policy().check("admin"), Err(UsernameError::Reserved));
+        assert_eq!(policy().check("ADMIN"), Err(UsernameError::Reserved));
+        assert_eq!(policy().check("VK_12345"), Err(UsernameError::Reserved));
+
+        assert_eq!(policy().check("admiral"), Ok(()));
+    }
+
+    #[test]
+    fn username_profane() {
+        assert_eq!(policy().check("xxBadWordxx"), Err(UsernameError::Profane));
+    }
+}
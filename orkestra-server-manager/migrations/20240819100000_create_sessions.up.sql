@@ -0,0 +1,20 @@
+-- Add up migration script here
+create table if not exists "sessions"
+(
+    id text primary key not null,
+    addr text not null,
+    title text not null,
+    code text not null,
+    game_map text not null,
+    max_players integer not null,
+    -- JSON array of player ids
+    players text not null default '[]',
+    pid integer,
+    -- Process start time from /proc, tells a re-used pid from our game server
+    pid_started_at integer,
+    state text not null default 'running' check (state in ('running', 'ended')),
+    created_at integer not null,
+    ended_at integer
+);
+
+create index if not exists "sessions_state_idx" on "sessions" (state);
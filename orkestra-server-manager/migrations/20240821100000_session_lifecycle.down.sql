@@ -0,0 +1,28 @@
+-- Add down migration script here
+create table "sessions_running"
+(
+    id text primary key not null,
+    addr text not null,
+    title text not null,
+    code text not null,
+    game_map text not null,
+    max_players integer not null,
+    players text not null default '[]',
+    pid integer,
+    pid_started_at integer,
+    state text not null default 'running' check (state in ('running', 'ended')),
+    created_at integer not null,
+    ended_at integer
+);
+
+insert into "sessions_running"
+select id, addr, title, code, game_map, max_players, players, pid, pid_started_at,
+       case when state in ('ended', 'failed') then 'ended' else 'running' end,
+       created_at,
+       ended_at
+from "sessions";
+
+drop table "sessions";
+alter table "sessions_running" rename to "sessions";
+
+create index if not exists "sessions_state_idx" on "sessions" (state);
@@ -0,0 +1,35 @@
+-- Add up migration script here
+-- SQLite can't change a check constraint in place, so the table is rebuilt
+create table "sessions_lifecycle"
+(
+    id text primary key not null,
+    addr text not null,
+    title text not null,
+    code text not null,
+    game_map text not null,
+    max_players integer not null,
+    -- JSON array of player ids
+    players text not null default '[]',
+    pid integer,
+    -- Process start time from /proc, tells a re-used pid from our game server
+    pid_started_at integer,
+    state text not null default 'allocating' check (
+        state in ('allocating', 'starting', 'ready', 'in_progress', 'draining', 'ended', 'failed')
+    ),
+    created_at integer not null,
+    state_changed_at integer not null,
+    ended_at integer
+);
+
+insert into "sessions_lifecycle"
+select id, addr, title, code, game_map, max_players, players, pid, pid_started_at,
+       case state when 'running' then 'ready' else 'ended' end,
+       created_at,
+       coalesce(ended_at, created_at),
+       ended_at
+from "sessions";
+
+drop table "sessions";
+alter table "sessions_lifecycle" rename to "sessions";
+
+create index if not exists "sessions_state_idx" on "sessions" (state);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+alter table "sessions" drop column results;
+alter table "sessions" drop column secret;
@@ -0,0 +1,5 @@
+-- Add up migration script here
+-- Sessions started before this have no secret, their game servers can't call back
+alter table "sessions" add column secret text not null default '';
+-- JSON results of the last match
+alter table "sessions" add column results text;
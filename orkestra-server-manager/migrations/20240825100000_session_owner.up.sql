@@ -0,0 +1,3 @@
+-- Add up migration script here
+-- Player who created the session, empty for sessions from before
+alter table "sessions" add column owner_id text not null default '';
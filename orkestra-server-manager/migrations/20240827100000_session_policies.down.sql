@@ -0,0 +1,4 @@
+-- Add down migration script here
+alter table "sessions" drop column creator_grace_secs;
+alter table "sessions" drop column max_lifetime_secs;
+alter table "sessions" drop column idle_timeout_secs;
@@ -0,0 +1,5 @@
+-- Add up migration script here
+-- Seconds, 0 means no limit, so sessions from before keep running as they did
+alter table "sessions" add column idle_timeout_secs integer not null default 0;
+alter table "sessions" add column max_lifetime_secs integer not null default 0;
+alter table "sessions" add column creator_grace_secs integer not null default 0;
@@ -0,0 +1,3 @@
+-- Add down migration script here
+alter table "sessions" drop column restarts;
+alter table "sessions" drop column restart_policy;
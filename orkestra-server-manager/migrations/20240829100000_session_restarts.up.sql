@@ -0,0 +1,5 @@
+-- Add up migration script here
+-- JSON restart policy, sessions from before are never restarted
+alter table "sessions" add column restart_policy text not null default '{"mode":"never"}';
+-- Times the game server was started again
+alter table "sessions" add column restarts integer not null default 0;
@@ -0,0 +1,3 @@
+-- Add up migration script here
+-- Whether the creator's game client ever connected, sessions from before count as connected
+alter table "sessions" add column creator_connected integer not null default 1;
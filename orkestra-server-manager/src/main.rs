@@ -1,18 +1,22 @@
-use std::{future::IntoFuture, net::SocketAddr};
+use std::{future::IntoFuture, net::SocketAddr, time::Duration};
 
 use anyhow::{Ok, Result};
 
 use shared::{
-    config::AppConfig,
+    config::{AppConfig, SesserKind},
     context::Context,
+    database::Database,
+    heartbeats, idempotency,
     logger::Logger,
+    presence,
     router::v1,
     services::{
         server_cloner::{simple_server_cloner::SimplerServerCloner, ServerCloner},
-        sesser::inmemory_sesser::InMemorySesser,
+        sesser::{inmemory_sesser::InMemorySesser, sqlite_sesser::SqliteSesser, Sesser},
     },
+    session_logs, session_policies,
 };
-use tracing::{info, info_span};
+use tracing::{info, info_span, warn, Instrument};
 
 mod models;
 mod plugins;
@@ -26,13 +30,53 @@ async fn main() -> Result<()> {
     let _logger = Logger::new();
     let config = AppConfig::load()?;
 
-    let sesser = InMemorySesser::new(&config)?;
+    match config.sesser {
+        SesserKind::Memory => {
+            let sesser = InMemorySesser::new(&config)?;
+
+            serve(config, sesser).await
+        }
+        SesserKind::Sqlite => {
+            let database = Database::new(&config).await?;
+            database.migrate().await?;
+
+            let sesser = SqliteSesser::new(&config, database).await?;
+
+            serve(config, sesser).await
+        }
+    }
+}
+
+async fn serve<S: Sesser>(config: AppConfig, sesser: S) -> Result<()> {
     let context = Context::new(&config, sesser)?;
 
+    // Fetched ahead, clients can't pass the version gate until they are
+    let auth_client = context.auth_client().clone();
+    tokio::spawn(
+        async move {
+            if let Err(err) = auth_client.client_version_policies().await {
+                warn!(event = "Couldn't prefetch client version policies", error = %err);
+            }
+        }
+        .in_current_span(),
+    );
+
+    tokio::spawn(heartbeats::reap(context.clone()).in_current_span());
+    tokio::spawn(idempotency::sweep(context.clone()).in_current_span());
+    tokio::spawn(presence::clear_finished(context.clone()).in_current_span());
+    tokio::spawn(session_logs::rotate(context.clone()).in_current_span());
+    tokio::spawn(
+        session_policies::enforce(
+            context.clone(),
+            Duration::from_secs(config.session_policy_interval_secs.max(1)),
+        )
+        .in_current_span(),
+    );
+
     let server_cloner = SimplerServerCloner::new(context.clone());
     server_cloner.clone_server_repo()?;
 
-    let v1 = v1(context);
+    let v1 = v1(context.clone());
     let app = v1;
 
     let addr = format!("0.0.0.0:{}", config.port);
@@ -50,5 +94,7 @@ async fn main() -> Result<()> {
 
     info!(event = "Shutdown the server",);
 
+    context.sesser().shutdown().in_current_span().await;
+
     Ok(())
 }
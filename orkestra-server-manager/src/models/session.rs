@@ -1,4 +1,14 @@
-use std::{collections::HashSet, net::SocketAddrV4};
+use std::{
+    collections::HashSet,
+    fmt,
+    net::SocketAddrV4,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rand::{distributions::Alphanumeric, Rng};
+
+#[cfg(test)]
+use std::net::Ipv4Addr;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -9,8 +19,158 @@ pub struct Session {
     pub addr: SocketAddrV4,
     pub title: String,
     pub code: String,
+    pub game_map: String,
     pub max_players: u32,
+    /// Player who created the session
+    pub owner: Id,
+    /// The creator holds a slot from the start, this tells whether their game
+    /// client ever connected to the game server
+    pub creator_connected: bool,
     pub players: HashSet<Id>,
+    /// Of the game server, leading its process group
+    pub pid: Option<u32>,
+
+    pub state: SessionState,
+    /// Unix seconds
+    pub created_at: u64,
+    /// Unix seconds of the last state change
+    pub state_changed_at: u64,
+
+    pub secret: SessionSecret,
+    /// Reported by the game server with the last match end
+    pub results: Option<serde_json::Value>,
+
+    pub policy: SessionPolicy,
+    pub restart: RestartPolicy,
+    /// Times the game server was started again after exiting
+    pub restarts: u32,
+}
+
+impl Session {
+    /// Moves the session to `next`, refusing transitions the lifecycle doesn't allow
+    pub fn transition(&mut self, next: SessionState) -> Result<(), SessionState> {
+        if !self.state.can_become(next) {
+            return Err(self.state);
+        }
+
+        self.state = next;
+        self.state_changed_at = unix_now();
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionState {
+    /// Port and code are reserved, the game server isn't spawned yet
+    Allocating,
+    /// The game server process is booting, also again after it exited
+    /// under a restart policy
+    Starting,
+    /// Accepting players
+    Ready,
+    /// A match is being played, players can still join
+    InProgress,
+    /// Shutting down, no new players
+    Draining,
+    /// The game server exited normally
+    Ended,
+    /// The game server couldn't start or crashed
+    Failed,
+}
+
+impl SessionState {
+    pub fn can_become(self, next: SessionState) -> bool {
+        use SessionState::*;
+
+        matches!(
+            (self, next),
+            (Allocating, Starting)
+                | (Starting, Ready)
+                | (Ready, InProgress)
+                | (InProgress, Ready)
+                | (Ready | InProgress, Starting)
+                | (Starting | Ready | InProgress, Draining)
+                | (
+                    Allocating | Starting | Ready | InProgress | Draining,
+                    Ended | Failed
+                )
+        )
+    }
+
+    pub fn is_joinable(self) -> bool {
+        matches!(self, SessionState::Ready | SessionState::InProgress)
+    }
+
+    pub fn is_finished(self) -> bool {
+        matches!(self, SessionState::Ended | SessionState::Failed)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SessionState::Allocating => "allocating",
+            SessionState::Starting => "starting",
+            SessionState::Ready => "ready",
+            SessionState::InProgress => "in_progress",
+            SessionState::Draining => "draining",
+            SessionState::Ended => "ended",
+            SessionState::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "allocating" => SessionState::Allocating,
+            "starting" => SessionState::Starting,
+            "ready" => SessionState::Ready,
+            "in_progress" => SessionState::InProgress,
+            "draining" => SessionState::Draining,
+            "ended" => SessionState::Ended,
+            "failed" => SessionState::Failed,
+            _ => return None,
+        })
+    }
+}
+
+impl fmt::Display for SessionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+impl Session {
+    /// Session owned by the player `owner`, tests override what they need
+    pub fn fixture() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            addr: SocketAddrV4::new(Ipv4Addr::LOCALHOST, 7777),
+            title: "title".to_string(),
+            code: "000001".to_string(),
+            game_map: "map".to_string(),
+            max_players: 4,
+            owner: Id("owner".to_string()),
+            creator_connected: false,
+            players: HashSet::from([Id("owner".to_string())]),
+            pid: None,
+            state: SessionState::Ready,
+            created_at: unix_now(),
+            state_changed_at: unix_now(),
+            secret: SessionSecret::generate(),
+            results: None,
+            policy: SessionPolicy::default(),
+            restart: RestartPolicy::Never,
+            restarts: 0,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,13 +178,197 @@ pub struct SessionConfig {
     pub max_players: u32,
     pub game_map: String,
     pub title: String,
+
+    /// Overrides of the server-wide policy, see [`SessionPolicy`]
+    #[serde(default)]
+    pub idle_timeout_secs: Option<u64>,
+    #[serde(default)]
+    pub max_lifetime_secs: Option<u64>,
+    #[serde(default)]
+    pub creator_grace_secs: Option<u64>,
+
+    #[serde(default)]
+    pub restart: RestartPolicy,
+}
+
+impl SessionConfig {
+    /// The requested policy, falling back to `defaults` for what wasn't asked for
+    pub fn policy(&self, defaults: SessionPolicy) -> SessionPolicy {
+        SessionPolicy {
+            idle_timeout_secs: self.idle_timeout_secs.unwrap_or(defaults.idle_timeout_secs),
+            max_lifetime_secs: self.max_lifetime_secs.unwrap_or(defaults.max_lifetime_secs),
+            creator_grace_secs: self
+                .creator_grace_secs
+                .unwrap_or(defaults.creator_grace_secs),
+        }
+    }
+}
+
+/// Whether a game server that exited is started again for the same session
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum RestartPolicy {
+    #[default]
+    Never,
+    /// After a crash, at most `max_attempts` times over the session's life
+    OnFailure { max_attempts: u32 },
+    /// After every exit, until the session is terminated
+    Always,
+}
+
+impl RestartPolicy {
+    /// Whether a game server that was already restarted `restarts` times is
+    /// started again
+    pub fn allows(self, exited_cleanly: bool, restarts: u32) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure { max_attempts } => !exited_cleanly && restarts < max_attempts,
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+/// When the game server of a session gets terminated, 0 turns a limit off
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SessionPolicy {
+    /// Seconds the session may stay without players
+    pub idle_timeout_secs: u64,
+    /// Seconds the session may exist, whatever is going on in it
+    pub max_lifetime_secs: u64,
+    /// Seconds after creation the session isn't idle even when empty, so the
+    /// creator has time to join
+    pub creator_grace_secs: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct Id(pub String);
 
+/// Given to the game server at start, authorizes its callbacks for the session
+#[derive(Clone)]
+pub struct SessionSecret(pub String);
+
+impl SessionSecret {
+    pub fn generate() -> Self {
+        Self(
+            rand::thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(48)
+                .map(char::from)
+                .collect(),
+        )
+    }
+
+    /// Compares in constant time. Sessions stored before secrets existed have
+    /// an empty one, which matches nothing
+    pub fn matches(&self, presented: &str) -> bool {
+        let (stored, presented) = (self.0.as_bytes(), presented.as_bytes());
+
+        if stored.is_empty() || stored.len() != presented.len() {
+            return false;
+        }
+
+        stored
+            .iter()
+            .zip(presented)
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+    }
+}
+
+impl fmt::Debug for SessionSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SessionSecret(..)")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum UpdateSession {
     AddPlayer(Id),
     RemovePlayer(Id),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        RestartPolicy, SessionSecret,
+        SessionState::{self, *},
+    };
+
+    const STATES: [SessionState; 7] = [
+        Allocating, Starting, Ready, InProgress, Draining, Ended, Failed,
+    ];
+
+    #[test]
+    fn sessions_start_before_taking_players() {
+        assert!(Allocating.can_become(Starting));
+        assert!(Starting.can_become(Ready));
+        assert!(!Allocating.can_become(Ready));
+        assert!(!Starting.can_become(InProgress));
+    }
+
+    #[test]
+    fn running_sessions_can_restart_and_drain() {
+        for state in [Ready, InProgress] {
+            assert!(state.can_become(Starting));
+            assert!(state.can_become(Draining));
+        }
+
+        assert!(Starting.can_become(Draining));
+        assert!(!Allocating.can_become(Draining));
+        assert!(!Draining.can_become(Starting));
+        assert!(!Draining.can_become(Ready));
+    }
+
+    #[test]
+    fn finished_sessions_stay_finished() {
+        for from in [Ended, Failed] {
+            for to in STATES {
+                assert!(!from.can_become(to), "{from} -> {to}");
+            }
+        }
+
+        for from in [Allocating, Starting, Ready, InProgress, Draining] {
+            assert!(from.can_become(Ended), "{from} -> ended");
+            assert!(from.can_become(Failed), "{from} -> failed");
+        }
+    }
+
+    #[test]
+    fn no_state_becomes_itself() {
+        for state in STATES {
+            assert!(!state.can_become(state), "{state}");
+        }
+    }
+
+    #[test]
+    fn secret_matches_only_itself() {
+        let secret = SessionSecret("s3cret".to_string());
+
+        assert!(secret.matches("s3cret"));
+        assert!(!secret.matches("s3creT"));
+        assert!(!secret.matches("s3cret!"));
+        assert!(!secret.matches(""));
+    }
+
+    #[test]
+    fn empty_secret_matches_nothing() {
+        let secret = SessionSecret(String::new());
+
+        assert!(!secret.matches(""));
+        assert!(!secret.matches("anything"));
+    }
+
+    #[test]
+    fn restart_policy_allows() {
+        assert!(!RestartPolicy::Never.allows(false, 0));
+
+        let on_failure = RestartPolicy::OnFailure { max_attempts: 2 };
+        assert!(on_failure.allows(false, 0));
+        assert!(on_failure.allows(false, 1));
+        assert!(!on_failure.allows(false, 2));
+        assert!(!on_failure.allows(true, 0));
+
+        assert!(RestartPolicy::Always.allows(true, u32::MAX));
+        assert!(RestartPolicy::Always.allows(false, 0));
+    }
+}
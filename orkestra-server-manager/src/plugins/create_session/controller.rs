@@ -1,17 +1,17 @@
 use axum::{extract::Extension, response::IntoResponse, Json};
 
-use tracing::{info, info_span, Instrument};
+use tracing::{error, info, info_span, Instrument};
 
 use crate::{
     plugins::create_session::use_case,
     shared::{
-        services::sesser::Sesser,
-        utils::{bad_request_json, ok_json},
+        services::sesser::{error::SesserError, Sesser},
+        utils::{conflict_json, internal_error_json, ok_json, service_unavailable_json},
     },
     Context,
 };
 
-use super::dto::CreateSessionRequest;
+use super::{dto::CreateSessionRequest, error::CreateSessionError};
 
 pub async fn create_session<S: Sesser>(
     Extension(context): Extension<Context<S>>,
@@ -35,8 +35,33 @@ pub async fn create_session<S: Sesser>(
         Ok(session) => ok_json(serde_json::json!({
             "connection": session.addr.to_string()
         })),
-        Err(error) => bad_request_json(serde_json::json!({
-            "error": error.to_string()
+        // Terminated while it was starting
+        Err(
+            err @ CreateSessionError::StartServer(
+                SesserError::InvalidTransition { .. } | SesserError::SessionNotFound(_),
+            ),
+        ) => conflict_json(serde_json::json!({
+            "error": err.to_string()
         })),
+        Err(
+            err @ CreateSessionError::StartServer(
+                SesserError::StartServer(_)
+                | SesserError::ExitedBeforeReady(_)
+                | SesserError::ReadyTimeout(_),
+            ),
+        ) => {
+            error!(event = "Couldn't start game server", error = %err);
+
+            service_unavailable_json(serde_json::json!({
+                "error": "Game server couldn't be started"
+            }))
+        }
+        Err(err) => {
+            error!(event = "Couldn't create session", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
     }
 }
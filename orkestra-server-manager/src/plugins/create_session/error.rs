@@ -1,7 +1,9 @@
 use thiserror::Error;
 
+use crate::shared::services::sesser::error::SesserError;
+
 #[derive(Debug, Error)]
 pub enum CreateSessionError {
     #[error("Start server error: {0}")]
-    StartServer(String),
+    StartServer(#[from] SesserError),
 }
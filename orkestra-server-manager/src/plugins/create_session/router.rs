@@ -1,9 +1,18 @@
-use axum::{routing::post, Router};
+use axum::{middleware, routing::post, Router};
 
-use crate::shared::services::sesser::Sesser;
+use crate::shared::{
+    client_version::client_version_gate, idempotency::idempotency, services::sesser::Sesser,
+};
 
 use super::controller::create_session;
 
 pub fn service<S: Sesser>() -> Router {
-    Router::new().route("/create_session", post(create_session::<S>))
+    Router::new()
+        .route("/create_session", post(create_session::<S>))
+        .route_layer(middleware::from_fn_with_state(
+            "create_session",
+            idempotency::<S>,
+        ))
+        // Outside idempotency, so an upgraded client retrying isn't replayed the 426
+        .route_layer(middleware::from_fn(client_version_gate::<S>))
 }
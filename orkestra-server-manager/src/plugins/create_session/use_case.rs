@@ -12,9 +12,8 @@ pub async fn create_session<S: Sesser>(
     creator_id: Id,
     config: SessionConfig,
 ) -> Result<Session, CreateSessionError> {
-    sesser
+    Ok(sesser
         .create_session(creator_id, config)
         .in_current_span()
-        .await
-        .map_err(|err| CreateSessionError::StartServer(err.to_string()))
+        .await?)
 }
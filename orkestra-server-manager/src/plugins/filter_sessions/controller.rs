@@ -2,11 +2,15 @@ use axum::{
     extract::{Extension, Query},
     response::IntoResponse,
 };
-use tracing::{info, info_span};
+use tracing::{error, info, info_span, Instrument};
 
 use crate::{
     plugins::filter_sessions::{dto::SessionPresent, use_case},
-    shared::{context::Context, services::sesser::Sesser, utils::ok_json},
+    shared::{
+        context::Context,
+        services::sesser::Sesser,
+        utils::{internal_error_json, ok_json},
+    },
 };
 
 use super::dto::FilterParams;
@@ -24,11 +28,27 @@ pub async fn filter_sessions<S: Sesser>(
         request = "Filter sessions",
     );
 
-    let sessions = use_case::filter_sessions(context, request.code)
+    let sessions = match use_case::filter_sessions(context.clone(), request.code)
+        .in_current_span()
+        .await
+    {
+        Ok(sessions) => sessions,
+        Err(err) => {
+            error!(event = "Couldn't fetch sessions", error = %err);
+
+            return internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }));
+        }
+    };
+
+    let sessions = sessions
         .into_iter()
         .map(|session| SessionPresent {
             id: session.id,
             title: session.title,
+            state: session.state,
+            healthy: context.heartbeats().is_healthy(session.id),
         })
         .collect::<Vec<_>>();
 
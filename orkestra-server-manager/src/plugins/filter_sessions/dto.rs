@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::session::SessionState;
+
 #[derive(Debug, Deserialize)]
 pub struct FilterParams {
     pub code: Option<String>,
@@ -10,4 +12,8 @@ pub struct FilterParams {
 pub struct SessionPresent {
     pub id: Uuid,
     pub title: String,
+    /// Only `ready` and `in_progress` sessions can be joined
+    pub state: SessionState,
+    /// `false` once the game server missed its heartbeats
+    pub healthy: bool,
 }
@@ -1,11 +1,17 @@
-use tracing::info;
+use tracing::{info, Instrument};
 
 use crate::{
     models::session::Session,
-    shared::{context::Context, services::sesser::Sesser},
+    shared::{
+        context::Context,
+        services::sesser::{error::SesserError, Sesser},
+    },
 };
 
-pub fn filter_sessions<S: Sesser>(context: Context<S>, code: Option<String>) -> Vec<Session> {
+pub async fn filter_sessions<S: Sesser>(
+    context: Context<S>,
+    code: Option<String>,
+) -> Result<Vec<Session>, SesserError> {
     if let Some(code) = code {
         info!(
             target: "filter_sessions",
@@ -13,13 +19,17 @@ pub fn filter_sessions<S: Sesser>(context: Context<S>, code: Option<String>) ->
             code = code
         );
 
-        context.sesser().filter_by_code(code)
+        context
+            .sesser()
+            .filter_by_code(code)
+            .in_current_span()
+            .await
     } else {
         info!(
             target: "filter_sessions",
             event = "Fetching all sessions",
         );
 
-        context.sesser().get_all_sessions()
+        context.sesser().get_all_sessions().in_current_span().await
     }
 }
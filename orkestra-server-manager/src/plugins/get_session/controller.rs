@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Extension, Query},
+    response::IntoResponse,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::get_session::{dto::SessionDetails, use_case},
+    shared::{
+        auth::api_key::ServiceCaller,
+        context::Context,
+        services::sesser::Sesser,
+        utils::{forbidden_json, internal_error_json, not_found_json, ok},
+    },
+};
+
+use super::{dto::GetSessionParams, error::GetSessionError};
+
+const SESSIONS_READ_SCOPE: &str = "sessions:read";
+
+pub async fn get_session<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    ServiceCaller(service): ServiceCaller,
+    Query(request): Query<GetSessionParams>,
+) -> impl IntoResponse {
+    let span = info_span!("get_session");
+    let _guard = span.enter();
+
+    info!(
+        target: "get_session",
+        event = "Handle request",
+        request = "Get session",
+        "session id" = %request.id,
+        service = service.name,
+        "service id" = %service.id,
+    );
+
+    if !service.has_scope(SESSIONS_READ_SCOPE) {
+        return forbidden_json(serde_json::json!({
+            "error": format!("Api key lacks the {SESSIONS_READ_SCOPE} scope")
+        }));
+    }
+
+    let result = use_case::get_session(context.sesser(), request.id)
+        .in_current_span()
+        .await;
+
+    match result {
+        Ok(session) => {
+            let healthy = context.heartbeats().is_healthy(session.id);
+
+            ok(SessionDetails::new(session, healthy))
+        }
+        Err(err @ GetSessionError::Sesser(_)) => {
+            error!(event = "Couldn't get session", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
+        Err(err) => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::session::{Id, RestartPolicy, Session, SessionPolicy, SessionState};
+
+#[derive(Debug, Deserialize)]
+pub struct GetSessionParams {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionDetails {
+    pub id: Uuid,
+    pub title: String,
+    pub code: String,
+    pub game_map: String,
+    pub connection: String,
+    pub max_players: u32,
+    pub players: Vec<Id>,
+    pub state: SessionState,
+    pub created_at: u64,
+    pub state_changed_at: u64,
+    /// Of the last finished match
+    pub results: Option<serde_json::Value>,
+    pub policy: SessionPolicy,
+    pub restart: RestartPolicy,
+    pub restarts: u32,
+    /// `false` once the game server missed its heartbeats
+    pub healthy: bool,
+}
+
+impl SessionDetails {
+    pub fn new(value: Session, healthy: bool) -> Self {
+        Self {
+            id: value.id,
+            title: value.title,
+            code: value.code,
+            game_map: value.game_map,
+            connection: value.addr.to_string(),
+            max_players: value.max_players,
+            players: value.players.into_iter().collect(),
+            state: value.state,
+            created_at: value.created_at,
+            state_changed_at: value.state_changed_at,
+            results: value.results,
+            policy: value.policy,
+            restart: value.restart,
+            restarts: value.restarts,
+            healthy,
+        }
+    }
+}
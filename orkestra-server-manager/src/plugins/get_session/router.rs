@@ -0,0 +1,9 @@
+use axum::{routing::get, Router};
+
+use crate::shared::services::sesser::Sesser;
+
+use super::controller::get_session;
+
+pub fn service<S: Sesser>() -> Router {
+    Router::new().route("/get_session", get(get_session::<S>))
+}
@@ -0,0 +1,14 @@
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{models::session::Session, shared::services::sesser::Sesser};
+
+use super::error::GetSessionError;
+
+pub async fn get_session<S: Sesser>(sesser: S, id: Uuid) -> Result<Session, GetSessionError> {
+    sesser
+        .get_by_id(id)
+        .in_current_span()
+        .await?
+        .ok_or(GetSessionError::SessionNotFound(id))
+}
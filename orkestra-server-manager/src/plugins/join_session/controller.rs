@@ -1,16 +1,16 @@
 use axum::{response::IntoResponse, Extension, Json};
-use tracing::{info, info_span};
+use tracing::{error, info, info_span, warn, Instrument};
 
 use crate::{
     plugins::join_session::use_case,
     shared::{
         context::Context,
         services::sesser::Sesser,
-        utils::{bad_request_json, ok_json},
+        utils::{bad_request_json, conflict_json, internal_error_json, ok_json},
     },
 };
 
-use super::dto::JoinSessionRequest;
+use super::{dto::JoinSessionRequest, error::JoinSessionError};
 
 pub async fn join_session<S: Sesser>(
     Extension(context): Extension<Context<S>>,
@@ -27,13 +27,51 @@ pub async fn join_session<S: Sesser>(
         "player id" = ?request.player_id,
     );
 
-    let session =
-        use_case::join_session(context.sesser(), request.player_id, request.server_id).await;
+    let session = use_case::join_session(
+        context.sesser(),
+        context.heartbeats(),
+        request.player_id.clone(),
+        request.server_id,
+    )
+    .in_current_span()
+    .await;
 
     match session {
-        Ok(addr) => ok_json(serde_json::json!({
-            "connection": addr.to_string()
+        Ok(addr) => {
+            let auth_client = context.auth_client().clone();
+
+            // Presence is best effort and must not delay joining
+            tokio::spawn(
+                async move {
+                    let result = auth_client
+                        .set_session_presence(&request.player_id, request.server_id)
+                        .await;
+
+                    if let Err(err) = result {
+                        warn!(event = "Couldn't report player presence", error = %err);
+                    }
+                }
+                .in_current_span(),
+            );
+
+            ok_json(serde_json::json!({
+                "connection": addr.to_string()
+            }))
+        }
+        Err(JoinSessionError::NotJoinable(state)) => conflict_json(serde_json::json!({
+            "error": "Session isn't accepting players",
+            "state": state,
         })),
+        Err(err @ JoinSessionError::Unhealthy) => conflict_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        Err(err @ JoinSessionError::Sesser(_)) => {
+            error!(event = "Couldn't join session", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
         Err(err) => bad_request_json(serde_json::json!({
             "error": err.to_string()
         })),
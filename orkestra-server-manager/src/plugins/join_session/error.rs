@@ -1,6 +1,8 @@
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::{models::session::SessionState, shared::services::sesser::error::SesserError};
+
 #[derive(Debug, Error)]
 pub enum JoinSessionError {
     #[error("Session not found: {0}")]
@@ -8,4 +10,13 @@ pub enum JoinSessionError {
 
     #[error("Session is full")]
     SessionIsFull,
+
+    #[error("Session doesn't accept players while {0}")]
+    NotJoinable(SessionState),
+
+    #[error("Session is unhealthy")]
+    Unhealthy,
+
+    #[error("Sesser error: {0}")]
+    Sesser(SesserError),
 }
@@ -1,9 +1,11 @@
-use axum::{routing::post, Router};
+use axum::{middleware, routing::post, Router};
 
-use crate::shared::services::sesser::Sesser;
+use crate::shared::{client_version::client_version_gate, services::sesser::Sesser};
 
 use super::controller::join_session;
 
 pub fn service<S: Sesser>() -> Router {
-    Router::new().route("/join_session", post(join_session::<S>))
+    Router::new()
+        .route("/join_session", post(join_session::<S>))
+        .route_layer(middleware::from_fn(client_version_gate::<S>))
 }
@@ -1,24 +1,40 @@
 use std::net::SocketAddrV4;
 
+use tracing::Instrument;
 use uuid::Uuid;
 
 use crate::{
     models::session::{Id, UpdateSession},
-    shared::services::sesser::{error::UpdateSessionError, Sesser},
+    shared::{
+        heartbeats::Heartbeats,
+        services::sesser::{error::SesserError, Sesser},
+    },
 };
 
 use super::error::JoinSessionError;
 
 pub async fn join_session<S: Sesser>(
     sesser: S,
+    heartbeats: &Heartbeats,
     player_id: Id,
     id: Uuid,
 ) -> Result<SocketAddrV4, JoinSessionError> {
-    match sesser.update_session(id, UpdateSession::AddPlayer(player_id)) {
+    if !heartbeats.is_healthy(id) {
+        return Err(JoinSessionError::Unhealthy);
+    }
+
+    let result = sesser
+        .update_session(id, UpdateSession::AddPlayer(player_id))
+        .in_current_span()
+        .await;
+
+    match result {
         Ok(session) => Ok(session.addr),
         Err(err) => match err {
-            UpdateSessionError::SessionNotFound(id) => Err(JoinSessionError::SessionNotFound(id)),
-            UpdateSessionError::SessionIsFull => Err(JoinSessionError::SessionIsFull),
+            SesserError::SessionNotFound(id) => Err(JoinSessionError::SessionNotFound(id)),
+            SesserError::SessionIsFull => Err(JoinSessionError::SessionIsFull),
+            SesserError::NotJoinable(state) => Err(JoinSessionError::NotJoinable(state)),
+            err => Err(JoinSessionError::Sesser(err)),
         },
     }
 }
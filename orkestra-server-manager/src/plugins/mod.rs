@@ -1,4 +1,8 @@
 pub mod create_session;
 pub mod filter_sessions;
+pub mod get_session;
 pub mod join_session;
 pub mod remove_player_from_session;
+pub mod session_callbacks;
+pub mod session_logs;
+pub mod terminate_session;
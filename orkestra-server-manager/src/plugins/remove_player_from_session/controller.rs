@@ -1,16 +1,16 @@
 use axum::{response::IntoResponse, Extension, Json};
-use tracing::{info, info_span};
+use tracing::{error, info, info_span, warn, Instrument};
 
 use crate::{
     plugins::remove_player_from_session::use_case,
     shared::{
         context::Context,
         services::sesser::Sesser,
-        utils::{bad_request_json, just_ok},
+        utils::{bad_request_json, internal_error_json, just_ok},
     },
 };
 
-use super::dto::RemovePlayerFromSessionRequest;
+use super::{dto::RemovePlayerFromSessionRequest, error::RemovePlayerFromSessionError};
 
 pub async fn remove_player_from_session<S: Sesser>(
     Extension(context): Extension<Context<S>>,
@@ -29,13 +29,39 @@ pub async fn remove_player_from_session<S: Sesser>(
 
     let session = use_case::remove_player_from_session(
         context.sesser(),
-        request.player_id,
+        request.player_id.clone(),
         request.server_id,
     )
+    .in_current_span()
     .await;
 
     match session {
-        Ok(_) => just_ok(),
+        Ok(_) => {
+            let auth_client = context.auth_client().clone();
+
+            // Presence is best effort and must not delay leaving
+            tokio::spawn(
+                async move {
+                    let result = auth_client
+                        .clear_session_presence(&request.player_id, request.server_id)
+                        .await;
+
+                    if let Err(err) = result {
+                        warn!(event = "Couldn't report player presence", error = %err);
+                    }
+                }
+                .in_current_span(),
+            );
+
+            just_ok()
+        }
+        Err(err @ RemovePlayerFromSessionError::Sesser(_)) => {
+            error!(event = "Couldn't remove player from session", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
         Err(err) => bad_request_json(serde_json::json!({
             "error": err.to_string()
         })),
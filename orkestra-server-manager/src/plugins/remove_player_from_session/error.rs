@@ -1,8 +1,13 @@
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::shared::services::sesser::error::SesserError;
+
 #[derive(Debug, Error)]
 pub enum RemovePlayerFromSessionError {
     #[error("Session not found: {0}")]
     SessionNotFound(Uuid),
+
+    #[error("Sesser error: {0}")]
+    Sesser(SesserError),
 }
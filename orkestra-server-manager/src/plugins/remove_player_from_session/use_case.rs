@@ -1,8 +1,9 @@
+use tracing::Instrument;
 use uuid::Uuid;
 
 use crate::{
     models::session::{Id, UpdateSession},
-    shared::services::sesser::{error::UpdateSessionError, Sesser},
+    shared::services::sesser::{error::SesserError, Sesser},
 };
 
 use super::error::RemovePlayerFromSessionError;
@@ -12,13 +13,18 @@ pub async fn remove_player_from_session<S: Sesser>(
     player_id: Id,
     id: Uuid,
 ) -> Result<(), RemovePlayerFromSessionError> {
-    match sesser.update_session(id, UpdateSession::RemovePlayer(player_id)) {
+    let result = sesser
+        .update_session(id, UpdateSession::RemovePlayer(player_id))
+        .in_current_span()
+        .await;
+
+    match result {
         Ok(_) => Ok(()),
         Err(err) => match err {
-            UpdateSessionError::SessionNotFound(id) => {
+            SesserError::SessionNotFound(id) => {
                 Err(RemovePlayerFromSessionError::SessionNotFound(id))
             }
-            _ => unreachable!(),
+            err => Err(RemovePlayerFromSessionError::Sesser(err)),
         },
     }
 }
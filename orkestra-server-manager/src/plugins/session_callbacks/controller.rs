@@ -0,0 +1,197 @@
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Extension, Json};
+use tracing::{debug, error, info, info_span, warn, Instrument};
+use uuid::Uuid;
+
+use crate::{
+    models::session::{Id, Session},
+    plugins::session_callbacks::use_case,
+    shared::{
+        auth::{client::AuthClient, session_secret::PresentedSecret},
+        context::Context,
+        services::sesser::Sesser,
+        utils::{conflict_json, internal_error_json, ok_json, unauthorized_json},
+    },
+};
+
+use super::{
+    dto::{MatchEndedData, PlayerData, SessionPath},
+    error::SessionCallbackError,
+};
+
+pub async fn ready<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    Path(SessionPath { id }): Path<SessionPath>,
+    PresentedSecret(secret): PresentedSecret,
+) -> impl IntoResponse {
+    let span = info_span!("session_ready");
+    let _guard = span.enter();
+
+    info!(event = "Game server reports ready", session_id = %id);
+
+    let result = use_case::ready(context.sesser(), id, &secret)
+        .in_current_span()
+        .await;
+
+    respond(result)
+}
+
+pub async fn heartbeat<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    Path(SessionPath { id }): Path<SessionPath>,
+    PresentedSecret(secret): PresentedSecret,
+) -> impl IntoResponse {
+    let span = info_span!("session_heartbeat");
+    let _guard = span.enter();
+
+    debug!(event = "Game server heartbeat", session_id = %id);
+
+    let result = use_case::heartbeat(context.sesser(), context.heartbeats(), id, &secret)
+        .in_current_span()
+        .await;
+
+    respond(result)
+}
+
+pub async fn player_connected<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    Path(SessionPath { id }): Path<SessionPath>,
+    PresentedSecret(secret): PresentedSecret,
+    Json(request): Json<PlayerData>,
+) -> impl IntoResponse {
+    let span = info_span!("session_player_connected");
+    let _guard = span.enter();
+
+    info!(
+        event = "Game server reports player connected",
+        session_id = %id,
+        player_id = ?request.player_id,
+    );
+
+    let result =
+        use_case::player_connected(context.sesser(), id, &secret, request.player_id.clone())
+            .in_current_span()
+            .await;
+
+    if result.is_ok() {
+        report_presence(context.auth_client().clone(), request.player_id, id, true);
+    }
+
+    respond(result)
+}
+
+pub async fn player_disconnected<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    Path(SessionPath { id }): Path<SessionPath>,
+    PresentedSecret(secret): PresentedSecret,
+    Json(request): Json<PlayerData>,
+) -> impl IntoResponse {
+    let span = info_span!("session_player_disconnected");
+    let _guard = span.enter();
+
+    info!(
+        event = "Game server reports player disconnected",
+        session_id = %id,
+        player_id = ?request.player_id,
+    );
+
+    let result =
+        use_case::player_disconnected(context.sesser(), id, &secret, request.player_id.clone())
+            .in_current_span()
+            .await;
+
+    if result.is_ok() {
+        report_presence(context.auth_client().clone(), request.player_id, id, false);
+    }
+
+    respond(result)
+}
+
+pub async fn match_started<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    Path(SessionPath { id }): Path<SessionPath>,
+    PresentedSecret(secret): PresentedSecret,
+) -> impl IntoResponse {
+    let span = info_span!("session_match_started");
+    let _guard = span.enter();
+
+    info!(event = "Game server reports match started", session_id = %id);
+
+    let result = use_case::match_started(context.sesser(), id, &secret)
+        .in_current_span()
+        .await;
+
+    respond(result)
+}
+
+pub async fn match_ended<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    Path(SessionPath { id }): Path<SessionPath>,
+    PresentedSecret(secret): PresentedSecret,
+    Json(request): Json<MatchEndedData>,
+) -> impl IntoResponse {
+    let span = info_span!("session_match_ended");
+    let _guard = span.enter();
+
+    info!(event = "Game server reports match ended", session_id = %id);
+
+    let result = use_case::match_ended(context.sesser(), id, &secret, request.results)
+        .in_current_span()
+        .await;
+
+    respond(result)
+}
+
+/// Presence is best effort and must not delay the game server
+fn report_presence(auth_client: AuthClient, player_id: Id, session_id: Uuid, connected: bool) {
+    tokio::spawn(
+        async move {
+            let result = if connected {
+                auth_client
+                    .set_session_presence(&player_id, session_id)
+                    .await
+            } else {
+                auth_client
+                    .clear_session_presence(&player_id, session_id)
+                    .await
+            };
+
+            if let Err(err) = result {
+                warn!(event = "Couldn't report player presence", error = %err);
+            }
+        }
+        .in_current_span(),
+    );
+}
+
+fn respond(result: Result<Session, SessionCallbackError>) -> (StatusCode, Json<serde_json::Value>) {
+    match result {
+        Ok(session) => ok_json(serde_json::json!({
+            "state": session.state,
+            "players": session.players,
+        })),
+        Err(err) => error_response(err),
+    }
+}
+
+fn error_response(err: SessionCallbackError) -> (StatusCode, Json<serde_json::Value>) {
+    match err {
+        SessionCallbackError::Unauthorized => {
+            warn!(event = %err);
+
+            unauthorized_json(serde_json::json!({
+                "error": err.to_string()
+            }))
+        }
+        SessionCallbackError::InvalidTransition { from, .. } => conflict_json(serde_json::json!({
+            "error": err.to_string(),
+            "state": from,
+        })),
+        SessionCallbackError::Sesser(_) => {
+            error!(event = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::models::session::Id;
+
+#[derive(Debug, Deserialize)]
+pub struct SessionPath {
+    pub id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlayerData {
+    pub player_id: Id,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MatchEndedData {
+    /// Whatever the game reports, kept as is
+    #[serde(default)]
+    pub results: serde_json::Value,
+}
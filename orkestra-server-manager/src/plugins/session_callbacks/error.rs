@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use crate::{models::session::SessionState, shared::services::sesser::error::SesserError};
+
+#[derive(Debug, Error)]
+pub enum SessionCallbackError {
+    #[error("Invalid session secret")]
+    Unauthorized,
+
+    #[error("Session can't go from {from} to {to}")]
+    InvalidTransition {
+        from: SessionState,
+        to: SessionState,
+    },
+
+    #[error("Sesser error: {0}")]
+    Sesser(SesserError),
+}
+
+impl From<SesserError> for SessionCallbackError {
+    fn from(value: SesserError) -> Self {
+        match value {
+            // A session which ended in the meantime is gone for the game server too
+            SesserError::SessionNotFound(_) => SessionCallbackError::Unauthorized,
+            SesserError::InvalidTransition { from, to } => {
+                SessionCallbackError::InvalidTransition { from, to }
+            }
+            err => SessionCallbackError::Sesser(err),
+        }
+    }
+}
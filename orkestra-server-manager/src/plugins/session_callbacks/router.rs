@@ -0,0 +1,27 @@
+use axum::{routing::post, Router};
+
+use crate::shared::services::sesser::Sesser;
+
+use super::controller::{
+    heartbeat, match_ended, match_started, player_connected, player_disconnected, ready,
+};
+
+/// Called by game servers with the secret of their session
+pub fn service<S: Sesser>() -> Router {
+    Router::new()
+        .route("/internal/sessions/:id/ready", post(ready::<S>))
+        .route("/internal/sessions/:id/heartbeat", post(heartbeat::<S>))
+        .route(
+            "/internal/sessions/:id/player_connected",
+            post(player_connected::<S>),
+        )
+        .route(
+            "/internal/sessions/:id/player_disconnected",
+            post(player_disconnected::<S>),
+        )
+        .route(
+            "/internal/sessions/:id/match_started",
+            post(match_started::<S>),
+        )
+        .route("/internal/sessions/:id/match_ended", post(match_ended::<S>))
+}
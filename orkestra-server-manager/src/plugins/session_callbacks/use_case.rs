@@ -0,0 +1,144 @@
+use tracing::{info, Instrument};
+use uuid::Uuid;
+
+use crate::{
+    models::session::{Id, Session, SessionState},
+    shared::{
+        heartbeats::Heartbeats,
+        services::sesser::{error::SesserError, Sesser},
+    },
+};
+
+use super::error::SessionCallbackError;
+
+/// Checks the secret before anything about the session is revealed, unknown
+/// sessions look the same as a wrong secret
+async fn authorize<S: Sesser>(
+    sesser: &S,
+    id: Uuid,
+    secret: &str,
+) -> Result<Session, SessionCallbackError> {
+    let session = sesser
+        .get_by_id(id)
+        .in_current_span()
+        .await?
+        .ok_or(SessionCallbackError::Unauthorized)?;
+
+    if !session.secret.matches(secret) {
+        return Err(SessionCallbackError::Unauthorized);
+    }
+
+    Ok(session)
+}
+
+pub async fn ready<S: Sesser>(
+    sesser: S,
+    id: Uuid,
+    secret: &str,
+) -> Result<Session, SessionCallbackError> {
+    authorize(&sesser, id, secret).in_current_span().await?;
+
+    Ok(sesser.report_ready(id).in_current_span().await?)
+}
+
+/// The state in the answer tells a game server its session ended
+pub async fn heartbeat<S: Sesser>(
+    sesser: S,
+    heartbeats: &Heartbeats,
+    id: Uuid,
+    secret: &str,
+) -> Result<Session, SessionCallbackError> {
+    let session = authorize(&sesser, id, secret).in_current_span().await?;
+
+    if !session.state.is_finished() && heartbeats.beat(id) {
+        info!(event = "Game server is healthy again", session_id = %id);
+    }
+
+    Ok(session)
+}
+
+/// The game server is the authority on who's connected, so capacity isn't checked
+pub async fn player_connected<S: Sesser>(
+    sesser: S,
+    id: Uuid,
+    secret: &str,
+    player_id: Id,
+) -> Result<Session, SessionCallbackError> {
+    authorize(&sesser, id, secret).in_current_span().await?;
+
+    let session = sesser
+        .modify_session(id, move |session| {
+            if player_id == session.owner {
+                session.creator_connected = true;
+            }
+
+            session.players.insert(player_id);
+
+            Ok(())
+        })
+        .in_current_span()
+        .await?;
+
+    Ok(session)
+}
+
+pub async fn player_disconnected<S: Sesser>(
+    sesser: S,
+    id: Uuid,
+    secret: &str,
+    player_id: Id,
+) -> Result<Session, SessionCallbackError> {
+    authorize(&sesser, id, secret).in_current_span().await?;
+
+    let session = sesser
+        .modify_session(id, move |session| {
+            session.players.remove(&player_id);
+
+            Ok(())
+        })
+        .in_current_span()
+        .await?;
+
+    Ok(session)
+}
+
+pub async fn match_started<S: Sesser>(
+    sesser: S,
+    id: Uuid,
+    secret: &str,
+) -> Result<Session, SessionCallbackError> {
+    authorize(&sesser, id, secret).in_current_span().await?;
+
+    Ok(sesser
+        .transition_session(id, SessionState::InProgress)
+        .in_current_span()
+        .await?)
+}
+
+/// The session goes back to accepting players for the next match
+pub async fn match_ended<S: Sesser>(
+    sesser: S,
+    id: Uuid,
+    secret: &str,
+    results: serde_json::Value,
+) -> Result<Session, SessionCallbackError> {
+    authorize(&sesser, id, secret).in_current_span().await?;
+
+    let session = sesser
+        .modify_session(id, move |session| {
+            session.transition(SessionState::Ready).map_err(|from| {
+                SesserError::InvalidTransition {
+                    from,
+                    to: SessionState::Ready,
+                }
+            })?;
+
+            session.results = Some(results);
+
+            Ok(())
+        })
+        .in_current_span()
+        .await?;
+
+    Ok(session)
+}
@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Extension, Query},
+    http::StatusCode,
+    response::{sse::KeepAlive, IntoResponse, Response, Sse},
+    Json,
+};
+use tracing::{error, info, info_span, Instrument};
+
+use crate::{
+    plugins::session_logs::use_case,
+    shared::{
+        auth::api_key::ServiceCaller,
+        context::Context,
+        services::sesser::Sesser,
+        utils::{forbidden_json, internal_error_json, not_found_json, ok},
+    },
+};
+
+use super::{dto::SessionLogParams, error::SessionLogError};
+
+const SESSIONS_ADMIN_SCOPE: &str = "sessions:admin";
+
+pub async fn get_session_log<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    ServiceCaller(service): ServiceCaller,
+    Query(request): Query<SessionLogParams>,
+) -> impl IntoResponse {
+    let span = info_span!("get_session_log");
+    let _guard = span.enter();
+
+    info!(
+        target: "get_session_log",
+        event = "Handle request",
+        request = "Get session log",
+        "session id" = %request.id,
+        service = service.name,
+        "service id" = %service.id,
+    );
+
+    if !service.has_scope(SESSIONS_ADMIN_SCOPE) {
+        return forbidden_json(serde_json::json!({
+            "error": format!("Api key lacks the {SESSIONS_ADMIN_SCOPE} scope")
+        }));
+    }
+
+    match use_case::get_session_log(context.session_logs(), request.id, request.tail_bytes())
+        .in_current_span()
+        .await
+    {
+        Ok(log) => ok(log),
+        Err(err) => error_response(err),
+    }
+}
+
+pub async fn follow_session_log<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    ServiceCaller(service): ServiceCaller,
+    Query(request): Query<SessionLogParams>,
+) -> Response {
+    let span = info_span!("follow_session_log");
+    let _guard = span.enter();
+
+    info!(
+        target: "follow_session_log",
+        event = "Handle request",
+        request = "Follow session log",
+        "session id" = %request.id,
+        service = service.name,
+        "service id" = %service.id,
+    );
+
+    if !service.has_scope(SESSIONS_ADMIN_SCOPE) {
+        return forbidden_json(serde_json::json!({
+            "error": format!("Api key lacks the {SESSIONS_ADMIN_SCOPE} scope")
+        }))
+        .into_response();
+    }
+
+    let result = use_case::follow_session_log(
+        context.sesser(),
+        context.session_logs(),
+        request.id,
+        request.tail_bytes(),
+    )
+    .in_current_span()
+    .await;
+
+    match result {
+        Ok(lines) => Sse::new(lines)
+            .keep_alive(KeepAlive::default())
+            .into_response(),
+        Err(err) => error_response(err).into_response(),
+    }
+}
+
+fn error_response(err: SessionLogError) -> (StatusCode, Json<serde_json::Value>) {
+    match err {
+        err @ SessionLogError::LogNotFound(_) => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        err @ SessionLogError::Io(_) => {
+            error!(event = "Couldn't read session log", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Bytes of the log returned, or sent first when following it
+const DEFAULT_TAIL_BYTES: u64 = 16 * 1024;
+
+const MAX_TAIL_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct SessionLogParams {
+    pub id: Uuid,
+    #[serde(default)]
+    pub bytes: Option<u64>,
+}
+
+impl SessionLogParams {
+    pub fn tail_bytes(&self) -> u64 {
+        self.bytes.unwrap_or(DEFAULT_TAIL_BYTES).min(MAX_TAIL_BYTES)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionLog {
+    pub log: String,
+}
@@ -0,0 +1,11 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum SessionLogError {
+    #[error("No log for session: {0}")]
+    LogNotFound(Uuid),
+
+    #[error("Couldn't read log: {0}")]
+    Io(std::io::Error),
+}
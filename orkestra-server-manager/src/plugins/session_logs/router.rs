@@ -0,0 +1,11 @@
+use axum::{routing::get, Router};
+
+use crate::shared::services::sesser::Sesser;
+
+use super::controller::{follow_session_log, get_session_log};
+
+pub fn service<S: Sesser>() -> Router {
+    Router::new()
+        .route("/get_session_log", get(get_session_log::<S>))
+        .route("/follow_session_log", get(follow_session_log::<S>))
+}
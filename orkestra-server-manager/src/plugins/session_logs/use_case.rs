@@ -0,0 +1,231 @@
+use std::{collections::VecDeque, convert::Infallible, io, path::PathBuf, time::Duration};
+
+use axum::response::sse::Event;
+use futures_util::{stream, Stream};
+use tracing::{warn, Instrument};
+use uuid::Uuid;
+
+use crate::shared::{
+    services::sesser::Sesser,
+    session_logs::{self, SessionLogs},
+};
+
+use super::{dto::SessionLog, error::SessionLogError};
+
+/// How often a followed log is checked for new lines
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Longer lines are sent in pieces, so a game server writing without
+/// newlines doesn't grow what's kept of the line being written
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+pub async fn get_session_log(
+    logs: &SessionLogs,
+    id: Uuid,
+    bytes: u64,
+) -> Result<SessionLog, SessionLogError> {
+    let (log, _) = read_tail(logs, id, bytes).await?;
+
+    Ok(SessionLog { log })
+}
+
+/// Lines of the session's log as they're written, one event each, starting
+/// with its tail. Ends with an `end` event once the session is over
+pub async fn follow_session_log<S: Sesser>(
+    sesser: S,
+    logs: &SessionLogs,
+    id: Uuid,
+    bytes: u64,
+) -> Result<impl Stream<Item = Result<Event, Infallible>>, SessionLogError> {
+    let (tail, offset) = read_tail(logs, id, bytes).await?;
+
+    let mut following = Following {
+        sesser,
+        id,
+        path: logs.path(id),
+        offset,
+        partial: Vec::new(),
+        events: VecDeque::new(),
+        ended: false,
+    };
+
+    following.push_lines(tail.into_bytes());
+
+    Ok(stream::unfold(following, |mut following| {
+        async move {
+            following
+                .next()
+                .await
+                .map(|event| (Ok::<_, Infallible>(event), following))
+        }
+        .in_current_span()
+    }))
+}
+
+async fn read_tail(
+    logs: &SessionLogs,
+    id: Uuid,
+    bytes: u64,
+) -> Result<(String, u64), SessionLogError> {
+    session_logs::tail(&logs.path(id), bytes)
+        .await
+        .map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => SessionLogError::LogNotFound(id),
+            _ => SessionLogError::Io(err),
+        })
+}
+
+struct Following<S> {
+    sesser: S,
+    id: Uuid,
+    path: PathBuf,
+    offset: u64,
+    /// Start of a line still being written
+    partial: Vec<u8>,
+    events: VecDeque<Event>,
+    ended: bool,
+}
+
+impl<S: Sesser> Following<S> {
+    async fn next(&mut self) -> Option<Event> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Some(event);
+            }
+
+            if self.ended {
+                return None;
+            }
+
+            tokio::time::sleep(FOLLOW_POLL_INTERVAL).await;
+
+            // Looked at before reading, so whatever the game server wrote
+            // before it exited is still sent
+            let finished = match self.sesser.get_by_id(self.id).await {
+                Ok(session) => session.map_or(true, |session| session.state.is_finished()),
+                Err(err) => {
+                    warn!(event = "Couldn't check session", session_id = %self.id, error = %err);
+                    false
+                }
+            };
+
+            match session_logs::read_from(&self.path, self.offset).await {
+                Ok((appended, offset)) => {
+                    self.offset = offset;
+                    self.push_lines(appended);
+                }
+                // Between the rename and the copy of a restart
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    warn!(event = "Couldn't read game server log", session_id = %self.id, error = %err);
+                    self.end("error");
+                }
+            }
+
+            if finished {
+                let partial = std::mem::take(&mut self.partial);
+
+                if !partial.is_empty() {
+                    self.push_line(&partial);
+                }
+
+                self.end("finished");
+            }
+        }
+    }
+}
+
+impl<S> Following<S> {
+    fn push_lines(&mut self, appended: Vec<u8>) {
+        self.partial.extend(appended);
+
+        if let Some(last_newline) = self.partial.iter().rposition(|b| *b == b'\n') {
+            let rest = self.partial.split_off(last_newline + 1);
+            let complete = std::mem::replace(&mut self.partial, rest);
+
+            for line in complete[..complete.len() - 1].split(|b| *b == b'\n') {
+                self.push_line(line);
+            }
+        }
+
+        while self.partial.len() >= MAX_LINE_BYTES {
+            let rest = self.partial.split_off(MAX_LINE_BYTES);
+            let piece = std::mem::replace(&mut self.partial, rest);
+
+            self.push_line(&piece);
+        }
+    }
+
+    fn push_line(&mut self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+
+        self.events
+            .push_back(Event::default().data(line.trim_end_matches('\r')));
+    }
+
+    fn end(&mut self, reason: &str) {
+        if !self.ended {
+            self.events
+                .push_back(Event::default().event("end").data(reason));
+            self.ended = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, path::PathBuf};
+
+    use uuid::Uuid;
+
+    use super::{Following, MAX_LINE_BYTES};
+
+    fn following() -> Following<()> {
+        Following {
+            sesser: (),
+            id: Uuid::new_v4(),
+            path: PathBuf::new(),
+            offset: 0,
+            partial: Vec::new(),
+            events: VecDeque::new(),
+            ended: false,
+        }
+    }
+
+    fn data(following: &Following<()>) -> Vec<String> {
+        following
+            .events
+            .iter()
+            .map(|event| format!("{event:?}"))
+            .collect()
+    }
+
+    #[test]
+    fn complete_lines_become_events() {
+        let mut following = following();
+
+        following.push_lines(b"first\r\nsec".to_vec());
+        following.push_lines(b"ond\nthi".to_vec());
+
+        let events = data(&following);
+        assert_eq!(events.len(), 2);
+        assert!(events[0].contains("data: first\\n"), "{}", events[0]);
+        assert!(events[1].contains("data: second\\n"), "{}", events[1]);
+        assert_eq!(following.partial, b"thi");
+    }
+
+    #[test]
+    fn long_partial_line_is_sent_in_pieces() {
+        let mut following = following();
+
+        following.push_lines(vec![b'a'; MAX_LINE_BYTES * 2 + 10]);
+
+        assert_eq!(following.events.len(), 2);
+        assert_eq!(following.partial.len(), 10);
+
+        following.push_lines(b"\n".to_vec());
+
+        assert_eq!(following.events.len(), 3);
+        assert!(following.partial.is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use axum::{response::IntoResponse, Extension, Json};
+use tracing::{error, info, info_span, warn, Instrument};
+
+use crate::{
+    plugins::terminate_session::use_case::{self, Terminator},
+    shared::{
+        auth::{api_key::ServiceCaller, player_token::PlayerToken},
+        context::Context,
+        services::sesser::Sesser,
+        utils::{
+            conflict_json, forbidden_json, internal_error_json, not_found_json, ok_json,
+            unauthorized_json,
+        },
+    },
+};
+
+use super::{dto::TerminateSessionRequest, error::TerminateSessionError};
+
+const SESSIONS_ADMIN_SCOPE: &str = "sessions:admin";
+
+pub async fn terminate_session<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    service: Option<ServiceCaller>,
+    token: Option<PlayerToken>,
+    Json(request): Json<TerminateSessionRequest>,
+) -> impl IntoResponse {
+    let span = info_span!("terminate_session");
+    let _guard = span.enter();
+
+    info!(
+        target: "terminate_session",
+        event = "Handle request",
+        request = "Terminate session",
+        "session id" = %request.server_id,
+        service = service.as_ref().map(|ServiceCaller(service)| service.name.as_str()),
+    );
+
+    // Admins call with an api key, players with their access token
+    let terminator = match (service, token) {
+        (Some(ServiceCaller(service)), _) => {
+            if !service.has_scope(SESSIONS_ADMIN_SCOPE) {
+                return forbidden_json(serde_json::json!({
+                    "error": format!("Api key lacks the {SESSIONS_ADMIN_SCOPE} scope")
+                }));
+            }
+
+            Terminator::Admin
+        }
+        (None, Some(PlayerToken(token))) => {
+            match context
+                .auth_client()
+                .verify_user(&token)
+                .in_current_span()
+                .await
+            {
+                Ok(Some(player_id)) => Terminator::Player(player_id),
+                Ok(None) => {
+                    warn!(event = "Rejected invalid access token");
+
+                    return unauthorized_json(serde_json::json!({
+                        "error": "Invalid access token"
+                    }));
+                }
+                Err(err) => {
+                    error!(event = "Couldn't verify access token", error = %err);
+
+                    return internal_error_json(serde_json::json!({
+                        "error": "Internal error"
+                    }));
+                }
+            }
+        }
+        (None, None) => {
+            return unauthorized_json(serde_json::json!({
+                "error": "Missing api key or access token"
+            }))
+        }
+    };
+
+    let result = use_case::terminate_session(
+        context.sesser(),
+        terminator,
+        request.server_id,
+        context.terminate_grace(),
+    )
+    .in_current_span()
+    .await;
+
+    match result {
+        Ok(session) => ok_json(serde_json::json!({
+            "state": session.state
+        })),
+        Err(err @ TerminateSessionError::SessionNotFound(_)) => not_found_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        Err(err @ TerminateSessionError::NotOwner) => forbidden_json(serde_json::json!({
+            "error": err.to_string()
+        })),
+        Err(TerminateSessionError::NotTerminable(state)) => conflict_json(serde_json::json!({
+            "error": "Session can't be terminated yet",
+            "state": state,
+        })),
+        Err(err @ TerminateSessionError::Sesser(_)) => {
+            error!(event = "Couldn't terminate session", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{models::session::SessionState, shared::services::sesser::error::SesserError};
+
+#[derive(Debug, Error)]
+pub enum TerminateSessionError {
+    #[error("Session not found: {0}")]
+    SessionNotFound(Uuid),
+
+    #[error("Only the owner of the session can terminate it")]
+    NotOwner,
+
+    #[error("Session can't be terminated while {0}")]
+    NotTerminable(SessionState),
+
+    #[error("Sesser error: {0}")]
+    Sesser(SesserError),
+}
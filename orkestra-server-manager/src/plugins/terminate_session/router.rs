@@ -0,0 +1,9 @@
+use axum::{routing::post, Router};
+
+use crate::shared::services::sesser::Sesser;
+
+use super::controller::terminate_session;
+
+pub fn service<S: Sesser>() -> Router {
+    Router::new().route("/terminate_session", post(terminate_session::<S>))
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use tracing::Instrument;
+use uuid::Uuid;
+
+use crate::{
+    models::session::{Id, Session},
+    shared::services::sesser::{error::SesserError, Sesser},
+};
+
+use super::error::TerminateSessionError;
+
+/// Who asks for the termination
+#[derive(Debug)]
+pub enum Terminator {
+    Admin,
+    Player(Id),
+}
+
+pub async fn terminate_session<S: Sesser>(
+    sesser: S,
+    terminator: Terminator,
+    id: Uuid,
+    grace: Duration,
+) -> Result<Session, TerminateSessionError> {
+    if let Terminator::Player(player_id) = terminator {
+        let session = sesser
+            .get_by_id(id)
+            .in_current_span()
+            .await
+            .map_err(TerminateSessionError::Sesser)?
+            .filter(|session| !session.state.is_finished())
+            .ok_or(TerminateSessionError::SessionNotFound(id))?;
+
+        if session.owner != player_id {
+            return Err(TerminateSessionError::NotOwner);
+        }
+    }
+
+    let result = sesser.terminate_session(id, grace).in_current_span().await;
+
+    match result {
+        Ok(session) => Ok(session),
+        Err(err) => match err {
+            SesserError::SessionNotFound(id) => Err(TerminateSessionError::SessionNotFound(id)),
+            SesserError::InvalidTransition { from, .. } if from.is_finished() => {
+                Err(TerminateSessionError::SessionNotFound(id))
+            }
+            SesserError::InvalidTransition { from, .. } => {
+                Err(TerminateSessionError::NotTerminable(from))
+            }
+            err => Err(TerminateSessionError::Sesser(err)),
+        },
+    }
+}
@@ -0,0 +1,105 @@
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request},
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::Deserialize;
+use tracing::{error, warn, Instrument};
+use uuid::Uuid;
+
+use crate::shared::{
+    context::Context,
+    services::sesser::Sesser,
+    utils::{internal_error_json, unauthorized_json},
+};
+
+/// Identity of a service calling with `Authorization: ApiKey <key>`, the keys
+/// are issued and verified by the auth system
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServiceIdentity {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<String>,
+}
+
+impl ServiceIdentity {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// Resolves `Authorization: ApiKey <key>` and exposes the caller as a
+/// [`ServiceIdentity`] request extension. Requests without an api key pass through.
+pub async fn api_key_middleware<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let key = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("ApiKey "))
+        .map(|key| key.trim().to_string());
+
+    let Some(key) = key else {
+        return next.run(request).await;
+    };
+
+    match context
+        .auth_client()
+        .verify_api_key(&key)
+        .in_current_span()
+        .await
+    {
+        Ok(Some(identity)) => {
+            request.extensions_mut().insert(identity);
+
+            next.run(request).await
+        }
+        Ok(None) => {
+            warn!(event = "Rejected unknown api key");
+
+            unauthorized_json(serde_json::json!({
+                "error": "Invalid api key"
+            }))
+            .into_response()
+        }
+        Err(err) => {
+            error!(event = "Couldn't verify api key", error = %err);
+
+            internal_error_json(serde_json::json!({
+                "error": "Internal error"
+            }))
+            .into_response()
+        }
+    }
+}
+
+/// Service identity attached by the api key middleware
+#[derive(Debug, Clone)]
+pub struct ServiceCaller(pub ServiceIdentity);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ServiceCaller
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<ServiceIdentity>()
+            .cloned()
+            .map(Self)
+            .ok_or_else(|| {
+                unauthorized_json(serde_json::json!({
+                    "error": "Missing api key"
+                }))
+            })
+    }
+}
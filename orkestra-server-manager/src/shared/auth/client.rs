@@ -0,0 +1,300 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use dashmap::DashMap;
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::{debug, warn, Instrument};
+use uuid::Uuid;
+
+use crate::{
+    models::session::Id,
+    shared::{client_version::ClientVersionPolicy, config::AppConfig},
+};
+
+use super::api_key::ServiceIdentity;
+
+#[derive(Debug, Error)]
+pub enum AuthClientError {
+    #[error("Auth system request failed: {0}")]
+    Request(#[from] reqwest::Error),
+
+    #[error("Auth system responded with {0}")]
+    UnexpectedStatus(StatusCode),
+
+    #[error("Player id is not an auth system user id: {0}")]
+    InvalidPlayerId(String),
+}
+
+/// Client of the auth system. Verified api keys are cached for a short time so
+/// that every request doesn't need a round trip, a revoked key keeps working
+/// until its entry expires.
+#[derive(Clone, Debug)]
+pub struct AuthClient {
+    inner: Arc<AuthClientInner>,
+}
+
+#[derive(Debug)]
+struct AuthClientInner {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+
+    cache_ttl: Duration,
+    /// Keyed by the hash of the api key, plaintext keys aren't kept around
+    verified_keys: DashMap<String, (ServiceIdentity, Instant)>,
+
+    project: String,
+    policies_ttl: Duration,
+    policies: RwLock<Option<(Vec<ClientVersionPolicy>, Instant)>>,
+    /// Held while the policies are fetched, so only one request refreshes them
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ClientVersionsResponse {
+    platforms: Vec<ClientVersionPolicy>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct UserResponse {
+    id: Uuid,
+}
+
+impl AuthClient {
+    pub fn new(config: &AppConfig) -> Result<Self, AuthClientError> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(config.auth_connect_timeout_secs))
+            .timeout(Duration::from_secs(config.auth_timeout_secs))
+            .build()?;
+
+        Ok(Self {
+            inner: Arc::new(AuthClientInner {
+                client,
+                base_url: config.auth_url.trim_end_matches('/').to_string(),
+                api_key: config.auth_api_key.clone().filter(|key| !key.is_empty()),
+                cache_ttl: Duration::from_secs(config.api_key_cache_ttl_secs),
+                verified_keys: Default::default(),
+                project: config.auth_project.clone(),
+                policies_ttl: Duration::from_secs(config.client_versions_cache_ttl_secs),
+                policies: Default::default(),
+                refreshing: Default::default(),
+            }),
+        })
+    }
+
+    pub async fn verify_api_key(
+        &self,
+        key: &str,
+    ) -> Result<Option<ServiceIdentity>, AuthClientError> {
+        let key_hash = hash(key);
+
+        if let Some(entry) = self.inner.verified_keys.get(&key_hash) {
+            let (identity, verified_at) = entry.value();
+
+            if verified_at.elapsed() < self.inner.cache_ttl {
+                return Ok(Some(identity.clone()));
+            }
+        }
+
+        self.inner
+            .verified_keys
+            .retain(|_, (_, verified_at)| verified_at.elapsed() < self.inner.cache_ttl);
+
+        debug!(event = "Verify api key in auth system");
+
+        let response = self
+            .inner
+            .client
+            .get(format!("{}/auth/v1/service/identity", self.inner.base_url))
+            .header(reqwest::header::AUTHORIZATION, format!("ApiKey {key}"))
+            .send()
+            .in_current_span()
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let identity = response.json::<ServiceIdentity>().await?;
+
+                self.inner
+                    .verified_keys
+                    .insert(key_hash, (identity.clone(), Instant::now()));
+
+                Ok(Some(identity))
+            }
+            StatusCode::UNAUTHORIZED => Ok(None),
+            status => Err(AuthClientError::UnexpectedStatus(status)),
+        }
+    }
+
+    /// Resolves a player's access token to their user id with the auth system,
+    /// `None` when the token isn't valid
+    pub async fn verify_user(&self, token: &str) -> Result<Option<Id>, AuthClientError> {
+        debug!(event = "Verify access token in auth system");
+
+        let response = self
+            .inner
+            .client
+            .get(format!("{}/auth/v1/me", self.inner.base_url))
+            .header(reqwest::header::AUTHORIZATION, format!("Bearer {token}"))
+            .header("X-Project", &self.inner.project)
+            .send()
+            .in_current_span()
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => {
+                let user = response.json::<UserResponse>().await?;
+
+                Ok(Some(Id(user.id.to_string())))
+            }
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Ok(None),
+            status => Err(AuthClientError::UnexpectedStatus(status)),
+        }
+    }
+
+    /// Client version policies of the project, cached for `CLIENT_VERSIONS_CACHE_TTL_SECS`.
+    /// When the auth system is unreachable the last known policies are kept.
+    /// Only one request refreshes them, the others get the stale ones meanwhile
+    /// or, with none fetched yet, wait for it
+    pub async fn client_version_policies(
+        &self,
+    ) -> Result<Vec<ClientVersionPolicy>, AuthClientError> {
+        if let Some(policies) = self.fresh_client_version_policies() {
+            return Ok(policies);
+        }
+
+        let _refreshing = match self.inner.refreshing.try_lock() {
+            Ok(guard) => guard,
+            Err(_) => {
+                if let Some((policies, _)) = self.inner.policies.read().unwrap().clone() {
+                    return Ok(policies);
+                }
+
+                self.inner.refreshing.lock().await
+            }
+        };
+
+        // Someone else may have refreshed them while this one waited
+        if let Some(policies) = self.fresh_client_version_policies() {
+            return Ok(policies);
+        }
+
+        let cached = self.inner.policies.read().unwrap().clone();
+
+        debug!(event = "Fetch client version policies from auth system");
+
+        match self.fetch_client_version_policies().await {
+            Ok(policies) => {
+                *self.inner.policies.write().unwrap() = Some((policies.clone(), Instant::now()));
+
+                Ok(policies)
+            }
+            Err(err) => match cached {
+                Some((policies, _)) => {
+                    warn!(event = "Using stale client version policies", error = %err);
+
+                    Ok(policies)
+                }
+                None => Err(err),
+            },
+        }
+    }
+
+    fn fresh_client_version_policies(&self) -> Option<Vec<ClientVersionPolicy>> {
+        match &*self.inner.policies.read().unwrap() {
+            Some((policies, fetched_at)) if fetched_at.elapsed() < self.inner.policies_ttl => {
+                Some(policies.clone())
+            }
+            _ => None,
+        }
+    }
+
+    async fn fetch_client_version_policies(
+        &self,
+    ) -> Result<Vec<ClientVersionPolicy>, AuthClientError> {
+        let response = self
+            .inner
+            .client
+            .get(format!("{}/auth/v1/client_versions", self.inner.base_url))
+            .header("X-Project", &self.inner.project)
+            .send()
+            .in_current_span()
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(response.json::<ClientVersionsResponse>().await?.platforms),
+            status => Err(AuthClientError::UnexpectedStatus(status)),
+        }
+    }
+
+    /// Marks the player as being in the session. Does nothing without `AUTH_API_KEY`
+    pub async fn set_session_presence(
+        &self,
+        player_id: &Id,
+        session_id: Uuid,
+    ) -> Result<(), AuthClientError> {
+        let Some(api_key) = &self.inner.api_key else {
+            return Ok(());
+        };
+
+        let response = self
+            .inner
+            .client
+            .put(self.presence_session_url(player_id)?)
+            .header(reqwest::header::AUTHORIZATION, format!("ApiKey {api_key}"))
+            .json(&serde_json::json!({ "session_id": session_id }))
+            .send()
+            .in_current_span()
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            status => Err(AuthClientError::UnexpectedStatus(status)),
+        }
+    }
+
+    /// Clears the player's session presence if it's still this session.
+    /// Does nothing without `AUTH_API_KEY`
+    pub async fn clear_session_presence(
+        &self,
+        player_id: &Id,
+        session_id: Uuid,
+    ) -> Result<(), AuthClientError> {
+        let Some(api_key) = &self.inner.api_key else {
+            return Ok(());
+        };
+
+        let response = self
+            .inner
+            .client
+            .delete(self.presence_session_url(player_id)?)
+            .header(reqwest::header::AUTHORIZATION, format!("ApiKey {api_key}"))
+            .query(&[("session_id", session_id.to_string())])
+            .send()
+            .in_current_span()
+            .await?;
+
+        match response.status() {
+            StatusCode::OK => Ok(()),
+            status => Err(AuthClientError::UnexpectedStatus(status)),
+        }
+    }
+
+    fn presence_session_url(&self, player_id: &Id) -> Result<String, AuthClientError> {
+        let user_id = Uuid::parse_str(&player_id.0)
+            .map_err(|_| AuthClientError::InvalidPlayerId(player_id.0.clone()))?;
+
+        Ok(format!(
+            "{}/auth/v1/service/presence/{user_id}/session",
+            self.inner.base_url
+        ))
+    }
+}
+
+fn hash(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
@@ -0,0 +1,4 @@
+pub mod api_key;
+pub mod client;
+pub mod player_token;
+pub mod session_secret;
@@ -0,0 +1,35 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    Json,
+};
+
+use crate::shared::utils::unauthorized_json;
+
+/// Access token a player calls with, `Authorization: Bearer <token>`. It's
+/// only verified by the auth system when the controller asks for it
+#[derive(Debug, Clone)]
+pub struct PlayerToken(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for PlayerToken
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(|token| Self(token.trim().to_string()))
+            .ok_or_else(|| {
+                unauthorized_json(serde_json::json!({
+                    "error": "Missing access token"
+                }))
+            })
+    }
+}
@@ -0,0 +1,34 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, StatusCode},
+    Json,
+};
+
+use crate::shared::utils::unauthorized_json;
+
+/// Secret a game server calls back with, `Authorization: Session <secret>`
+#[derive(Debug, Clone)]
+pub struct PresentedSecret(pub String);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for PresentedSecret
+where
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, Json<serde_json::Value>);
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Session "))
+            .map(|secret| Self(secret.trim().to_string()))
+            .ok_or_else(|| {
+                unauthorized_json(serde_json::json!({
+                    "error": "Missing session secret"
+                }))
+            })
+    }
+}
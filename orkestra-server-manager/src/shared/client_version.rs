@@ -0,0 +1,214 @@
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use axum::{
+    extract::Request,
+    http::HeaderName,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{warn, Instrument};
+
+use crate::shared::{
+    context::Context,
+    services::sesser::Sesser,
+    utils::{bad_request_json, service_unavailable_json, upgrade_required_json},
+};
+
+/// Header clients put their build version into, e.g. `X-Client-Version: 1.4.2`
+pub static CLIENT_VERSION_HEADER: HeaderName = HeaderName::from_static("x-client-version");
+
+/// Header clients put their platform into, e.g. `X-Client-Platform: android`
+pub static CLIENT_PLATFORM_HEADER: HeaderName = HeaderName::from_static("x-client-platform");
+
+/// Policy of the auth system applied to platforms without their own one
+const DEFAULT_PLATFORM: &str = "default";
+
+const MAX_PARTS: usize = 4;
+
+/// Dotted numeric client version, same format the auth system uses
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ClientVersion(Vec<u32>);
+
+impl FromStr for ClientVersion {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .trim()
+            .split('.')
+            .map(str::parse)
+            .collect::<Result<Vec<u32>, _>>()
+            .map_err(|_| format!("Invalid client version: {s}"))?;
+
+        if parts.len() > MAX_PARTS {
+            return Err(format!("Invalid client version: {s}"));
+        }
+
+        Ok(Self(parts))
+    }
+}
+
+impl TryFrom<String> for ClientVersion {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ClientVersion> for String {
+    fn from(value: ClientVersion) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ClientVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(u32::to_string).collect();
+
+        write!(f, "{}", parts.join("."))
+    }
+}
+
+impl Ord for ClientVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.0.len().max(other.0.len());
+        let part = |version: &Self, i: usize| version.0.get(i).copied().unwrap_or(0);
+
+        (0..len)
+            .map(|i| part(self, i).cmp(&part(other, i)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialEq for ClientVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for ClientVersion {}
+
+impl PartialOrd for ClientVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Version policy of a platform as published by the auth system
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientVersionPolicy {
+    pub platform: String,
+    pub min_version: ClientVersion,
+    pub latest_version: ClientVersion,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateRequired {
+    pub platform: Option<String>,
+    pub client_version: Option<ClientVersion>,
+    pub min_version: ClientVersion,
+    pub latest_version: ClientVersion,
+}
+
+/// Same rules as in the auth system: the platform's policy or the default one,
+/// no policy lets everyone in, a client without a version is too old
+pub fn check(
+    policies: &[ClientVersionPolicy],
+    platform: Option<&str>,
+    version: Option<&ClientVersion>,
+) -> Result<(), UpdateRequired> {
+    let policy = platform
+        .and_then(|platform| policies.iter().find(|policy| policy.platform == platform))
+        .or_else(|| {
+            policies
+                .iter()
+                .find(|policy| policy.platform == DEFAULT_PLATFORM)
+        });
+
+    let Some(policy) = policy else {
+        return Ok(());
+    };
+
+    if version.is_some_and(|version| *version >= policy.min_version) {
+        return Ok(());
+    }
+
+    Err(UpdateRequired {
+        platform: platform.map(str::to_string),
+        client_version: version.cloned(),
+        min_version: policy.min_version.clone(),
+        latest_version: policy.latest_version.clone(),
+    })
+}
+
+/// Rejects clients older than the auth system's version policy with
+/// `426 Upgrade Required`. Without policies, when they were never fetched,
+/// clients get `503 Service Unavailable`
+pub async fn client_version_gate<S: Sesser>(
+    Extension(context): Extension<Context<S>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let headers = request.headers();
+
+    let platform = headers
+        .get(&CLIENT_PLATFORM_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.trim().to_lowercase());
+
+    let version = match headers.get(&CLIENT_VERSION_HEADER) {
+        Some(value) => match value
+            .to_str()
+            .map_err(|_| "Invalid client version".to_string())
+            .and_then(str::parse::<ClientVersion>)
+        {
+            Ok(version) => Some(version),
+            Err(err) => {
+                return bad_request_json(serde_json::json!({
+                    "error": err
+                }))
+                .into_response()
+            }
+        },
+        None => None,
+    };
+
+    let policies = match context
+        .auth_client()
+        .client_version_policies()
+        .in_current_span()
+        .await
+    {
+        Ok(policies) => policies,
+        Err(err) => {
+            warn!(event = "Couldn't fetch client version policies", error = %err);
+
+            return service_unavailable_json(serde_json::json!({
+                "error": "Client version policies are unavailable"
+            }))
+            .into_response();
+        }
+    };
+
+    match check(&policies, platform.as_deref(), version.as_ref()) {
+        Ok(_) => next.run(request).await,
+        Err(update) => {
+            warn!(
+                event = "Rejected outdated client",
+                platform = ?update.platform,
+                client_version = ?update.client_version.as_ref().map(ToString::to_string),
+            );
+
+            upgrade_required_json(serde_json::json!({
+                "error": "Update required",
+                "update_required": update
+            }))
+            .into_response()
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use serde::Deserialize;
 
+use crate::models::session::SessionPolicy;
+
 #[derive(Debug, Deserialize)]
 pub struct AppConfig {
     pub host: String,
@@ -8,10 +10,218 @@ pub struct AppConfig {
 
     pub project_name: String,
     pub repo_path: String,
+    /// Where game servers on this host reach the manager, `http://127.0.0.1:<PORT>` by default
+    #[serde(default)]
+    pub manager_url: Option<String>,
+
+    pub auth_url: String,
+    /// Requests to the auth system are made while serving requests, so they
+    /// give up after these rather than hang with the auth system
+    #[serde(default = "default_auth_connect_timeout_secs")]
+    pub auth_connect_timeout_secs: u64,
+    #[serde(default = "default_auth_timeout_secs")]
+    pub auth_timeout_secs: u64,
+    #[serde(default = "default_api_key_cache_ttl_secs")]
+    pub api_key_cache_ttl_secs: u64,
+    /// Key with the `presence:write` scope, player presence isn't reported without it
+    #[serde(default)]
+    pub auth_api_key: Option<String>,
+    /// Project of the auth system whose client version policies gate the sessions
+    #[serde(default = "default_auth_project")]
+    pub auth_project: String,
+    #[serde(default = "default_client_versions_cache_ttl_secs")]
+    pub client_versions_cache_ttl_secs: u64,
+
+    #[serde(default = "default_idempotency_ttl_secs")]
+    pub idempotency_ttl_secs: u64,
+    /// Keys kept at most, requests with new keys get 503 while the store is full
+    #[serde(default = "default_idempotency_max_keys")]
+    pub idempotency_max_keys: usize,
+    #[serde(default = "default_idempotency_sweep_interval_secs")]
+    pub idempotency_sweep_interval_secs: u64,
+
+    #[serde(default)]
+    pub sesser: SesserKind,
+    #[serde(default = "default_session_db_path")]
+    pub session_db_path: String,
+    /// Game servers write their stdout and stderr here, one file per session
+    #[serde(default = "default_session_log_dir")]
+    pub session_log_dir: String,
+    /// A game server log is rotated once it's larger, 0 never rotates
+    #[serde(default = "default_session_log_max_bytes")]
+    pub session_log_max_bytes: u64,
+    /// Rotated logs kept per session
+    #[serde(default = "default_session_log_files")]
+    pub session_log_files: u32,
+    /// Logs of finished sessions are removed once they weren't written for this long
+    #[serde(default = "default_session_log_retention_secs")]
+    pub session_log_retention_secs: u64,
+    #[serde(default = "default_orphan_poll_interval_secs")]
+    pub orphan_poll_interval_secs: u64,
+
+    /// Line the game server prints once it accepts players. Without it the
+    /// game port accepting TCP connections means ready
+    #[serde(default)]
+    pub ready_marker: Option<String>,
+    #[serde(default = "default_ready_timeout_secs")]
+    pub ready_timeout_secs: u64,
+
+    /// A game server silent this long after its last heartbeat is unhealthy
+    #[serde(default = "default_heartbeat_timeout_secs")]
+    pub heartbeat_timeout_secs: u64,
+    /// An unhealthy game server is killed after this long
+    #[serde(default = "default_heartbeat_grace_secs")]
+    pub heartbeat_grace_secs: u64,
+    #[serde(default = "default_reaper_interval_secs")]
+    pub reaper_interval_secs: u64,
+
+    /// A terminated game server is killed if it hasn't exited after this long
+    #[serde(default = "default_terminate_grace_secs")]
+    pub terminate_grace_secs: u64,
+
+    /// Defaults of the session policies, requests may override them. 0 turns
+    /// a limit off
+    #[serde(default = "default_session_idle_timeout_secs")]
+    pub session_idle_timeout_secs: u64,
+    #[serde(default)]
+    pub session_max_lifetime_secs: u64,
+    #[serde(default = "default_session_creator_grace_secs")]
+    pub session_creator_grace_secs: u64,
+    #[serde(default = "default_session_policy_interval_secs")]
+    pub session_policy_interval_secs: u64,
+
+    /// Delay before the first restart of a game server, doubled with every
+    /// further restart up to the max
+    #[serde(default = "default_restart_backoff_secs")]
+    pub restart_backoff_secs: u64,
+    #[serde(default = "default_restart_backoff_max_secs")]
+    pub restart_backoff_max_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SesserKind {
+    /// Sessions are lost with the manager
+    Memory,
+    /// Sessions are kept in SQLite, running game servers are re-adopted on restart
+    #[default]
+    Sqlite,
 }
 
 impl AppConfig {
     pub fn load() -> Result<Self> {
         Ok(envy::from_env::<Self>()?)
     }
+
+    pub fn session_policy(&self) -> SessionPolicy {
+        SessionPolicy {
+            idle_timeout_secs: self.session_idle_timeout_secs,
+            max_lifetime_secs: self.session_max_lifetime_secs,
+            creator_grace_secs: self.session_creator_grace_secs,
+        }
+    }
+
+    pub fn manager_url(&self) -> String {
+        self.manager_url
+            .clone()
+            .filter(|url| !url.is_empty())
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| format!("http://127.0.0.1:{}", self.port))
+    }
+}
+
+fn default_auth_connect_timeout_secs() -> u64 {
+    2
+}
+
+fn default_auth_timeout_secs() -> u64 {
+    5
+}
+
+fn default_api_key_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_auth_project() -> String {
+    "default".to_string()
+}
+
+fn default_client_versions_cache_ttl_secs() -> u64 {
+    60
+}
+
+fn default_idempotency_ttl_secs() -> u64 {
+    60 * 60 * 24
+}
+
+fn default_idempotency_max_keys() -> usize {
+    100_000
+}
+
+fn default_idempotency_sweep_interval_secs() -> u64 {
+    60
+}
+
+fn default_session_db_path() -> String {
+    "sessions.db".to_string()
+}
+
+fn default_session_log_dir() -> String {
+    "session_logs".to_string()
+}
+
+fn default_session_log_max_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_session_log_files() -> u32 {
+    3
+}
+
+fn default_session_log_retention_secs() -> u64 {
+    7 * 24 * 60 * 60
+}
+
+fn default_orphan_poll_interval_secs() -> u64 {
+    5
+}
+
+fn default_ready_timeout_secs() -> u64 {
+    60
+}
+
+fn default_heartbeat_timeout_secs() -> u64 {
+    30
+}
+
+fn default_heartbeat_grace_secs() -> u64 {
+    30
+}
+
+fn default_reaper_interval_secs() -> u64 {
+    5
+}
+
+fn default_terminate_grace_secs() -> u64 {
+    10
+}
+
+fn default_session_idle_timeout_secs() -> u64 {
+    300
+}
+
+fn default_session_creator_grace_secs() -> u64 {
+    120
+}
+
+fn default_session_policy_interval_secs() -> u64 {
+    5
+}
+
+fn default_restart_backoff_secs() -> u64 {
+    1
+}
+
+fn default_restart_backoff_max_secs() -> u64 {
+    60
 }
@@ -1,8 +1,11 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use anyhow::Result;
 
-use super::{config::AppConfig, services::sesser::Sesser};
+use super::{
+    auth::client::AuthClient, config::AppConfig, heartbeats::Heartbeats,
+    idempotency::IdempotencyStore, services::sesser::Sesser, session_logs::SessionLogs,
+};
 
 #[derive(Clone)]
 pub struct Context<S: Sesser> {
@@ -11,7 +14,12 @@ pub struct Context<S: Sesser> {
 
 struct ContextInner<S: Sesser> {
     pub sesser: S,
+    pub auth_client: AuthClient,
+    pub idempotency: IdempotencyStore,
+    pub heartbeats: Heartbeats,
+    pub session_logs: SessionLogs,
 
+    pub terminate_grace: Duration,
     pub project_name: String,
     pub repo_path: String,
 }
@@ -21,6 +29,11 @@ impl<S: Sesser> Context<S> {
         Ok(Self {
             inner: Arc::new(ContextInner {
                 sesser,
+                auth_client: AuthClient::new(config)?,
+                idempotency: IdempotencyStore::new(config),
+                heartbeats: Heartbeats::new(config),
+                session_logs: SessionLogs::new(config)?,
+                terminate_grace: Duration::from_secs(config.terminate_grace_secs),
                 project_name: config.project_name.clone(),
                 repo_path: config.repo_path.clone(),
             }),
@@ -38,4 +51,24 @@ impl<S: Sesser> Context<S> {
     pub fn sesser(&self) -> S {
         self.inner.sesser.clone()
     }
+
+    pub fn auth_client(&self) -> &AuthClient {
+        &self.inner.auth_client
+    }
+
+    pub fn idempotency(&self) -> &IdempotencyStore {
+        &self.inner.idempotency
+    }
+
+    pub fn heartbeats(&self) -> &Heartbeats {
+        &self.inner.heartbeats
+    }
+
+    pub fn session_logs(&self) -> &SessionLogs {
+        &self.inner.session_logs
+    }
+
+    pub fn terminate_grace(&self) -> Duration {
+        self.inner.terminate_grace
+    }
 }
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use sqlx::{
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    Pool, Sqlite,
+};
+use tracing::{info, Instrument};
+
+use super::config::AppConfig;
+
+/// SQLite database the persistent sesser keeps sessions in
+#[derive(Clone, Debug)]
+pub struct Database {
+    inner: Pool<Sqlite>,
+}
+
+impl Database {
+    pub async fn new(config: &AppConfig) -> Result<Self> {
+        info!(
+            target: "database",
+            event = "Opening SQLite database",
+            path = config.session_db_path,
+        );
+
+        let options = SqliteConnectOptions::new()
+            .filename(&config.session_db_path)
+            .create_if_missing(true);
+
+        let conn = SqlitePoolOptions::new()
+            .max_connections(4)
+            .connect_with(options)
+            .in_current_span()
+            .await?;
+
+        Ok(Self { inner: conn })
+    }
+
+    /// Migrated in-memory database. Every connection to `:memory:` opens a
+    /// database of its own, so the pool keeps a single one for good
+    #[cfg(test)]
+    pub async fn in_memory() -> Result<Self> {
+        let conn = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await?;
+
+        let database = Self { inner: conn };
+        database.migrate().await?;
+
+        Ok(database)
+    }
+
+    pub async fn migrate(&self) -> Result<()> {
+        info!(
+            target: "database",
+            event = "Running migrations",
+        );
+
+        sqlx::migrate!().run(&self.inner).in_current_span().await?;
+
+        info!(
+            target: "database",
+            event = "Migrated",
+        );
+
+        Ok(())
+    }
+}
+
+impl AsRef<Pool<Sqlite>> for Database {
+    fn as_ref(&self) -> &Pool<Sqlite> {
+        &self.inner
+    }
+}
@@ -0,0 +1,205 @@
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use tracing::{error, info, info_span, warn, Instrument};
+use uuid::Uuid;
+
+use crate::models::session::SessionState;
+
+use super::{config::AppConfig, context::Context, services::sesser::Sesser};
+
+/// Heartbeats of game servers. Sending one opts the game server into being
+/// watched: once they stop it's marked unhealthy, and killed by the reaper
+/// if they don't come back within the grace period
+#[derive(Debug)]
+pub struct Heartbeats {
+    seen: DashMap<Uuid, Beat>,
+
+    timeout: Duration,
+    grace: Duration,
+    reap_interval: Duration,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Beat {
+    last: Instant,
+    unhealthy_since: Option<Instant>,
+}
+
+impl Heartbeats {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            seen: Default::default(),
+            timeout: Duration::from_secs(config.heartbeat_timeout_secs),
+            grace: Duration::from_secs(config.heartbeat_grace_secs),
+            reap_interval: Duration::from_secs(config.reaper_interval_secs.max(1)),
+        }
+    }
+
+    /// Records a heartbeat, `true` when the session was unhealthy until now
+    pub fn beat(&self, id: Uuid) -> bool {
+        let beat = Beat {
+            last: Instant::now(),
+            unhealthy_since: None,
+        };
+
+        self.seen
+            .insert(id, beat)
+            .is_some_and(|previous| previous.unhealthy_since.is_some())
+    }
+
+    /// Sessions that never sent a heartbeat count as healthy
+    pub fn is_healthy(&self, id: Uuid) -> bool {
+        self.seen
+            .get(&id)
+            .map_or(true, |beat| beat.unhealthy_since.is_none())
+    }
+
+    fn forget(&self, id: Uuid) {
+        self.seen.remove(&id);
+    }
+
+    /// Marks the session unhealthy unless a heartbeat came in since `last`
+    fn mark_unhealthy(&self, id: Uuid, last: Instant) -> bool {
+        match self.seen.get_mut(&id) {
+            Some(mut beat) if beat.last == last && beat.unhealthy_since.is_none() => {
+                beat.unhealthy_since = Some(Instant::now());
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Kills game servers whose heartbeats stopped, runs for the manager's lifetime
+pub async fn reap<S: Sesser>(context: Context<S>) {
+    let heartbeats = context.heartbeats();
+    let sesser = context.sesser();
+
+    let mut interval = tokio::time::interval(heartbeats.reap_interval);
+
+    loop {
+        interval.tick().await;
+
+        let span = info_span!("reaper");
+        let _guard = span.enter();
+
+        let beats: Vec<(Uuid, Beat)> = heartbeats
+            .seen
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect();
+
+        for (id, beat) in beats {
+            let session = match sesser.get_by_id(id).in_current_span().await {
+                Ok(session) => session,
+                Err(err) => {
+                    error!(event = "Couldn't check session", session_id = %id, error = %err);
+                    continue;
+                }
+            };
+
+            // Ended on its own, or restarting and the new game server opts in
+            // again with its first heartbeat
+            let gone = session.map_or(true, |session| {
+                session.state.is_finished() || session.state == SessionState::Starting
+            });
+
+            if gone {
+                heartbeats.forget(id);
+                continue;
+            }
+
+            match beat.unhealthy_since {
+                None if beat.last.elapsed() > heartbeats.timeout => {
+                    if heartbeats.mark_unhealthy(id, beat.last) {
+                        warn!(
+                            event = "Game server missed its heartbeats, session is unhealthy",
+                            session_id = %id,
+                            silent_secs = beat.last.elapsed().as_secs(),
+                        );
+                    }
+                }
+                Some(since) if since.elapsed() > heartbeats.grace => {
+                    warn!(event = "Killing unresponsive game server", session_id = %id);
+
+                    // Whoever watches the process ends or restarts the session
+                    // once it's gone
+                    match sesser.kill_session(id).in_current_span().await {
+                        Ok(killed) => info!(event = "Game server killed", session_id = %id, killed),
+                        Err(err) => {
+                            error!(event = "Couldn't kill game server", session_id = %id, error = %err)
+                        }
+                    }
+
+                    heartbeats.forget(id);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use uuid::Uuid;
+
+    use super::Heartbeats;
+
+    fn heartbeats() -> Heartbeats {
+        Heartbeats {
+            seen: Default::default(),
+            timeout: Duration::from_secs(30),
+            grace: Duration::from_secs(30),
+            reap_interval: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn beat_recovers_unhealthy_session() {
+        let heartbeats = heartbeats();
+        let id = Uuid::new_v4();
+
+        assert!(heartbeats.is_healthy(id));
+        assert!(!heartbeats.beat(id));
+
+        let last = heartbeats.seen.get(&id).unwrap().last;
+        assert!(heartbeats.mark_unhealthy(id, last));
+        assert!(!heartbeats.is_healthy(id));
+
+        assert!(heartbeats.beat(id));
+        assert!(heartbeats.is_healthy(id));
+        assert!(!heartbeats.beat(id));
+    }
+
+    #[test]
+    fn mark_unhealthy_skips_newer_beat() {
+        let heartbeats = heartbeats();
+        let id = Uuid::new_v4();
+
+        heartbeats.beat(id);
+        let seen = heartbeats.seen.get(&id).unwrap().last;
+
+        std::thread::sleep(Duration::from_millis(1));
+        heartbeats.beat(id);
+
+        assert!(!heartbeats.mark_unhealthy(id, seen));
+        assert!(heartbeats.is_healthy(id));
+    }
+
+    #[test]
+    fn mark_unhealthy_once() {
+        let heartbeats = heartbeats();
+        let id = Uuid::new_v4();
+
+        assert!(!heartbeats.mark_unhealthy(id, Instant::now()));
+
+        heartbeats.beat(id);
+        let last = heartbeats.seen.get(&id).unwrap().last;
+
+        assert!(heartbeats.mark_unhealthy(id, last));
+        assert!(!heartbeats.mark_unhealthy(id, last));
+    }
+}
@@ -0,0 +1,385 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    http::{header::CONTENT_TYPE, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use dashmap::{mapref::entry::Entry, DashMap};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, info_span, warn};
+
+use super::{
+    config::AppConfig,
+    context::Context,
+    services::sesser::Sesser,
+    utils::{bad_request_json, conflict_json, service_unavailable_json},
+};
+
+/// Header clients put a unique key per logical operation into, retries reuse it
+pub static IDEMPOTENCY_KEY_HEADER: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Set on responses replayed from an earlier request with the same key
+pub static IDEMPOTENT_REPLAYED_HEADER: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+const KEY_MAX_LEN: usize = 255;
+const BODY_MAX_BYTES: usize = 1024 * 1024;
+
+/// A request still running after this long is assumed to be lost
+const ABANDONED_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone)]
+struct StoredResponse {
+    status: StatusCode,
+    content_type: Option<HeaderValue>,
+    body: Bytes,
+}
+
+#[derive(Debug)]
+struct IdempotencyEntry {
+    fingerprint: String,
+    /// None while the first request is still being handled
+    response: Option<StoredResponse>,
+    created_at: Instant,
+}
+
+enum Claim {
+    Owned,
+    Replay(StoredResponse),
+    InProgress,
+    PayloadMismatch,
+    Full,
+}
+
+/// First responses of requests carrying an `Idempotency-Key`, kept in memory for a TTL
+#[derive(Clone, Debug)]
+pub struct IdempotencyStore {
+    inner: Arc<IdempotencyStoreInner>,
+}
+
+#[derive(Debug)]
+struct IdempotencyStoreInner {
+    ttl: Duration,
+    max_keys: usize,
+    sweep_interval: Duration,
+    entries: DashMap<(&'static str, String), IdempotencyEntry>,
+}
+
+impl IdempotencyStore {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            inner: Arc::new(IdempotencyStoreInner {
+                ttl: Duration::from_secs(config.idempotency_ttl_secs),
+                max_keys: config.idempotency_max_keys,
+                sweep_interval: Duration::from_secs(config.idempotency_sweep_interval_secs.max(1)),
+                entries: Default::default(),
+            }),
+        }
+    }
+
+    fn claim(&self, scope: &'static str, key: &str, fingerprint: String) -> Claim {
+        // Counted before taking the entry, `len` locks every shard. Concurrent
+        // claims may overshoot the cap slightly.
+        let full = self.inner.entries.len() >= self.inner.max_keys;
+
+        let new_entry = |fingerprint| IdempotencyEntry {
+            fingerprint,
+            response: None,
+            created_at: Instant::now(),
+        };
+
+        match self.inner.entries.entry((scope, key.to_string())) {
+            Entry::Vacant(_) if full => Claim::Full,
+            Entry::Vacant(entry) => {
+                entry.insert(new_entry(fingerprint));
+
+                Claim::Owned
+            }
+            // Expired but not swept yet
+            Entry::Occupied(mut entry) if entry.get().created_at.elapsed() >= self.inner.ttl => {
+                entry.insert(new_entry(fingerprint));
+
+                Claim::Owned
+            }
+            Entry::Occupied(mut entry) => {
+                let existing = entry.get();
+
+                if existing.fingerprint != fingerprint {
+                    return Claim::PayloadMismatch;
+                }
+
+                match &existing.response {
+                    Some(response) => Claim::Replay(response.clone()),
+                    None if existing.created_at.elapsed() >= ABANDONED_AFTER => {
+                        entry.get_mut().created_at = Instant::now();
+
+                        Claim::Owned
+                    }
+                    None => Claim::InProgress,
+                }
+            }
+        }
+    }
+
+    fn complete(&self, scope: &'static str, key: &str, response: StoredResponse) {
+        if let Some(mut entry) = self.inner.entries.get_mut(&(scope, key.to_string())) {
+            entry.response = Some(response);
+        }
+    }
+
+    fn release(&self, scope: &'static str, key: &str) {
+        self.inner
+            .entries
+            .remove_if(&(scope, key.to_string()), |_, entry| {
+                entry.response.is_none()
+            });
+    }
+
+    /// Forgets keys older than the TTL
+    fn sweep(&self) -> usize {
+        let ttl = self.inner.ttl;
+        let before = self.inner.entries.len();
+
+        self.inner
+            .entries
+            .retain(|_, entry| entry.created_at.elapsed() < ttl);
+
+        before.saturating_sub(self.inner.entries.len())
+    }
+}
+
+/// Forgets expired idempotency keys from time to time
+pub async fn sweep<S: Sesser>(context: Context<S>) {
+    let store = context.idempotency();
+
+    let mut interval = tokio::time::interval(store.inner.sweep_interval);
+
+    loop {
+        interval.tick().await;
+
+        let swept = store.sweep();
+
+        if swept > 0 {
+            let span = info_span!("idempotency_sweeper");
+            let _guard = span.enter();
+
+            debug!(event = "Forgot expired idempotency keys", count = swept);
+        }
+    }
+}
+
+/// Replays the first response for repeated requests carrying the same
+/// `Idempotency-Key` and payload. Requests without the header pass through.
+pub async fn idempotency<S: Sesser>(
+    State(scope): State<&'static str>,
+    Extension(context): Extension<Context<S>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = request.headers().get(&IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+
+    let Some(key) = key
+        .to_str()
+        .ok()
+        .filter(|key| valid_key(key))
+        .map(str::to_string)
+    else {
+        return bad_request_json(serde_json::json!({
+            "error": "Invalid idempotency key"
+        }))
+        .into_response();
+    };
+
+    let (parts, body) = request.into_parts();
+
+    let Ok(body) = to_bytes(body, BODY_MAX_BYTES).await else {
+        return bad_request_json(serde_json::json!({
+            "error": "Request body is too large"
+        }))
+        .into_response();
+    };
+
+    let fingerprint = fingerprint(parts.method.as_str(), parts.uri.path(), &body);
+
+    let store = context.idempotency();
+
+    match store.claim(scope, &key, fingerprint) {
+        Claim::Owned => {}
+        Claim::Replay(response) => {
+            info!(event = "Replaying idempotent response", scope = scope);
+
+            return replay(response);
+        }
+        Claim::InProgress => {
+            return conflict_json(serde_json::json!({
+                "error": "A request with this idempotency key is still in progress"
+            }))
+            .into_response();
+        }
+        Claim::Full => {
+            warn!(event = "Idempotency store is full", scope = scope);
+
+            return service_unavailable_json(serde_json::json!({
+                "error": "Too many idempotency keys in use, retry later"
+            }))
+            .into_response();
+        }
+        Claim::PayloadMismatch => {
+            warn!(
+                event = "Idempotency key reused with a different payload",
+                scope = scope
+            );
+
+            return conflict_json(serde_json::json!({
+                "error": "Idempotency key was already used with a different request"
+            }))
+            .into_response();
+        }
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, usize::MAX).await.unwrap_or_default();
+
+    // Server errors aren't an outcome worth replaying, the retry gets a fresh try
+    if parts.status.is_server_error() {
+        store.release(scope, &key);
+    } else {
+        store.complete(
+            scope,
+            &key,
+            StoredResponse {
+                status: parts.status,
+                content_type: parts.headers.get(CONTENT_TYPE).cloned(),
+                body: body.clone(),
+            },
+        );
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+fn replay(stored: StoredResponse) -> Response {
+    let mut response = (stored.status, stored.body).into_response();
+    let headers = response.headers_mut();
+
+    if let Some(content_type) = stored.content_type {
+        headers.insert(CONTENT_TYPE, content_type);
+    }
+
+    headers.insert(
+        IDEMPOTENT_REPLAYED_HEADER.clone(),
+        HeaderValue::from_static("true"),
+    );
+
+    response
+}
+
+/// Hash of everything that makes two requests the same operation
+fn fingerprint(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+
+    for part in [method.as_bytes(), path.as_bytes()] {
+        hasher.update(part);
+        hasher.update([0]);
+    }
+
+    hasher.update(body);
+
+    hex::encode(hasher.finalize())
+}
+
+/// Keys are 1..255 visible ASCII characters, UUIDs are a good choice
+fn valid_key(key: &str) -> bool {
+    !key.is_empty() && key.len() <= KEY_MAX_LEN && key.chars().all(|c| c.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::shared::config::AppConfig;
+
+    use super::{fingerprint, Claim, IdempotencyStore};
+
+    fn store(max_keys: &str) -> IdempotencyStore {
+        let config: AppConfig = envy::from_iter(
+            [
+                ("HOST", "127.0.0.1"),
+                ("PORT", "8001"),
+                ("PROJECT_NAME", "test"),
+                ("REPO_PATH", "test"),
+                ("AUTH_URL", "http://127.0.0.1:8000"),
+                ("IDEMPOTENCY_TTL_SECS", "60"),
+                ("IDEMPOTENCY_MAX_KEYS", max_keys),
+            ]
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+        )
+        .unwrap();
+
+        IdempotencyStore::new(&config)
+    }
+
+    fn expire(store: &IdempotencyStore, key: &str) {
+        store
+            .inner
+            .entries
+            .get_mut(&("test", key.to_string()))
+            .unwrap()
+            .created_at = Instant::now() - Duration::from_secs(61);
+    }
+
+    #[test]
+    fn new_keys_are_refused_while_full() {
+        let store = store("2");
+
+        assert!(matches!(store.claim("test", "a", "1".into()), Claim::Owned));
+        assert!(matches!(store.claim("test", "b", "1".into()), Claim::Owned));
+        assert!(matches!(store.claim("test", "c", "1".into()), Claim::Full));
+
+        // Known keys are still answered
+        assert!(matches!(
+            store.claim("test", "a", "1".into()),
+            Claim::InProgress
+        ));
+
+        expire(&store, "a");
+        assert_eq!(store.sweep(), 1);
+
+        assert!(matches!(store.claim("test", "c", "1".into()), Claim::Owned));
+    }
+
+    #[test]
+    fn expired_keys_are_claimed_again() {
+        let store = store("10");
+
+        assert!(matches!(store.claim("test", "a", "1".into()), Claim::Owned));
+        assert!(matches!(
+            store.claim("test", "a", "2".into()),
+            Claim::PayloadMismatch
+        ));
+
+        expire(&store, "a");
+
+        assert!(matches!(store.claim("test", "a", "2".into()), Claim::Owned));
+    }
+
+    #[test]
+    fn fingerprint_covers_the_whole_request() {
+        let base = fingerprint("POST", "/api/v1/create_session", b"{}");
+
+        assert_eq!(base, fingerprint("POST", "/api/v1/create_session", b"{}"));
+        assert_ne!(base, fingerprint("POST", "/api/v1/create_session", b"{ }"));
+        assert_ne!(base, fingerprint("PUT", "/api/v1/create_session", b"{}"));
+        assert_ne!(base, fingerprint("POST", "/api/v1/create_sessio", b"n{}"));
+    }
+}
@@ -1,6 +1,14 @@
+pub mod auth;
+pub mod client_version;
 pub mod config;
 pub mod context;
+pub mod database;
+pub mod heartbeats;
+pub mod idempotency;
 pub mod logger;
+pub mod presence;
 pub mod router;
 pub mod services;
+pub mod session_logs;
+pub mod session_policies;
 pub mod utils;
@@ -0,0 +1,29 @@
+use tracing::{info_span, warn, Instrument};
+
+use super::{context::Context, services::sesser::Sesser};
+
+/// Clears the presence of the players of every finished session, so they
+/// don't show as in it until the presence expires. Runs for the manager's lifetime
+pub async fn clear_finished<S: Sesser>(context: Context<S>) {
+    let Some(mut finished) = context.sesser().finished().take() else {
+        return;
+    };
+
+    while let Some(session) = finished.recv().await {
+        let span = info_span!("clear_presence", session_id = %session.id);
+
+        for player_id in &session.players {
+            let result = context
+                .auth_client()
+                .clear_session_presence(player_id, session.id)
+                .instrument(span.clone())
+                .await;
+
+            if let Err(err) = result {
+                let _guard = span.enter();
+
+                warn!(event = "Couldn't clear player presence", error = %err);
+            }
+        }
+    }
+}
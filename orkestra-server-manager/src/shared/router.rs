@@ -1,9 +1,9 @@
-use axum::{Extension, Router};
+use axum::{middleware, Extension, Router};
 use tower_http::trace::TraceLayer;
 
 use crate::plugins::*;
 
-use super::{context::Context, services::sesser::Sesser};
+use super::{auth::api_key::api_key_middleware, context::Context, services::sesser::Sesser};
 
 pub fn base_router(router: Router) -> Router {
     Router::new().nest("/api", router)
@@ -13,13 +13,22 @@ pub fn v1<S: Sesser>(context: Context<S>) -> Router {
     let create_session = create_session::router::service::<S>();
     let join_session = join_session::router::service::<S>();
     let filter_sessions = filter_sessions::router::service::<S>();
+    let get_session = get_session::router::service::<S>();
     let remove_player_from_session = remove_player_from_session::router::service::<S>();
+    let session_callbacks = session_callbacks::router::service::<S>();
+    let session_logs = session_logs::router::service::<S>();
+    let terminate_session = terminate_session::router::service::<S>();
 
     let merged = Router::new()
         .merge(create_session)
         .merge(join_session)
         .merge(filter_sessions)
+        .merge(get_session)
         .merge(remove_player_from_session)
+        .merge(session_callbacks)
+        .merge(session_logs)
+        .merge(terminate_session)
+        .layer(middleware::from_fn(api_key_middleware::<S>))
         .layer(Extension(context));
 
     let v1 = Router::new()
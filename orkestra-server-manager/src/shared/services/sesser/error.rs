@@ -1,11 +1,37 @@
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::models::session::SessionState;
+
 #[derive(Debug, Error)]
-pub enum UpdateSessionError {
+pub enum SesserError {
     #[error("Session not found: {0}")]
     SessionNotFound(Uuid),
 
     #[error("Session is full")]
     SessionIsFull,
+
+    #[error("Session doesn't accept players while {0}")]
+    NotJoinable(SessionState),
+
+    #[error("Session can't go from {from} to {to}")]
+    InvalidTransition {
+        from: SessionState,
+        to: SessionState,
+    },
+
+    #[error("Start server error: {0}")]
+    StartServer(#[from] std::io::Error),
+
+    #[error("Game server exited before it was ready: {0}")]
+    ExitedBeforeReady(String),
+
+    #[error("Game server wasn't ready within {0} seconds")]
+    ReadyTimeout(u64),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] sqlx::Error),
+
+    #[error("Malformed session: {0}")]
+    Malformed(String),
 }
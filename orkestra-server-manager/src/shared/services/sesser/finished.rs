@@ -0,0 +1,35 @@
+use std::sync::Mutex;
+
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+
+use crate::models::session::Session;
+
+/// Sessions that reached `Ended` or `Failed`, with the players they had at the
+/// end. Kept until someone takes them, so sessions finished while the manager
+/// starts up aren't lost
+#[derive(Debug)]
+pub struct FinishedSessions {
+    sender: UnboundedSender<Session>,
+    receiver: Mutex<Option<UnboundedReceiver<Session>>>,
+}
+
+impl FinishedSessions {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        Self {
+            sender,
+            receiver: Mutex::new(Some(receiver)),
+        }
+    }
+
+    pub fn report(&self, session: Session) {
+        // Nothing to do once the receiver is gone
+        let _ = self.sender.send(session);
+    }
+
+    /// The finished sessions, only the first caller gets them
+    pub fn take(&self) -> Option<UnboundedReceiver<Session>> {
+        self.receiver.lock().unwrap().take()
+    }
+}
@@ -0,0 +1,419 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, SeekFrom},
+    net::Ipv4Addr,
+    os::unix::process::CommandExt,
+    path::Path,
+    process::Stdio,
+    sync::Arc,
+    time::Duration,
+};
+
+use dashmap::{DashMap, DashSet};
+use tokio::{
+    io::{AsyncReadExt, AsyncSeekExt},
+    net::{TcpListener, TcpStream},
+    process::{Child, Command},
+    sync::Notify,
+};
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::{
+    models::session::Session,
+    shared::{config::AppConfig, session_logs},
+};
+
+use super::error::SesserError;
+
+/// How often a starting game server is checked for readiness
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How much of a game server's log ends up in the manager log
+const LOG_TAIL_BYTES: u64 = 4 * 1024;
+
+/// Env var with the secret authorizing the game server's callbacks
+const SECRET_ENV: &str = "ORKESTRA_SESSION_SECRET";
+
+/// Env var with the url the game server's callbacks go under
+const CALLBACK_URL_ENV: &str = "ORKESTRA_CALLBACK_URL";
+
+/// Starts game servers of the project
+#[derive(Debug, Clone)]
+pub struct Launcher {
+    project_name: String,
+    manager_url: String,
+}
+
+impl Launcher {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            project_name: config.project_name.clone(),
+            manager_url: config.manager_url(),
+        }
+    }
+
+    /// Command starting the game server of the session in its own process group,
+    /// so it can be signalled with everything the script started and doesn't get
+    /// the manager's Ctrl-C. Stdio is left to the caller
+    pub fn command(&self, session: &Session, port: u16) -> Command {
+        let project_name = &self.project_name;
+        let mut command = std::process::Command::new("bash");
+
+        command
+            .arg(format!("./{project_name}/{project_name}.sh"))
+            .arg("-log")
+            .arg(format!("-Port={port}"))
+            .arg("--serverid")
+            .arg(session.id.to_string())
+            .arg("--servercode")
+            .arg(&session.code)
+            // Kept out of the arguments, which anyone on the host can list
+            .env(SECRET_ENV, &session.secret.0)
+            .env(
+                CALLBACK_URL_ENV,
+                format!(
+                    "{}/api/v1/internal/sessions/{}",
+                    self.manager_url, session.id
+                ),
+            )
+            .process_group(0);
+
+        Command::from(command)
+    }
+
+    /// Starts the game server with stdout and stderr going to `log_path`
+    pub fn spawn(&self, session: &Session, port: u16, log_path: &Path) -> io::Result<Child> {
+        File::create(log_path)?;
+
+        // Appending, so writes land at the start of the log again once it's
+        // truncated by the rotation
+        let log = OpenOptions::new().append(true).open(log_path)?;
+
+        self.command(session, port)
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log)
+            .spawn()
+    }
+}
+
+/// Delays between restarts of a game server, doubling with every restart
+#[derive(Debug, Clone, Copy)]
+pub struct RestartBackoff {
+    base: Duration,
+    max: Duration,
+}
+
+impl RestartBackoff {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            base: Duration::from_secs(config.restart_backoff_secs),
+            max: Duration::from_secs(config.restart_backoff_max_secs),
+        }
+    }
+
+    /// Delay before the `restart`th restart, counted from 1
+    pub fn delay(&self, restart: u32) -> Duration {
+        let factor = 2u32.saturating_pow(restart.saturating_sub(1));
+
+        self.base.saturating_mul(factor).min(self.max)
+    }
+}
+
+/// Finds a free port and marks it pending, `preferred` if it's still free
+pub async fn reserve_port(pending: &DashSet<u16>, preferred: Option<u16>) -> io::Result<u16> {
+    if let Some(port) = preferred {
+        if pending.insert(port) {
+            if TcpListener::bind(("0.0.0.0", port)).await.is_ok() {
+                return Ok(port);
+            }
+
+            pending.remove(&port);
+        }
+
+        debug!(event = "Preferred port is taken", port = port);
+    }
+
+    loop {
+        let port = TcpListener::bind("0.0.0.0:0").await?.local_addr()?.port();
+
+        if pending.insert(port) {
+            return Ok(port);
+        }
+
+        debug!(event = "Port is already pending", port = port);
+    }
+}
+
+/// How a starting game server tells it accepts players: the ready callback,
+/// or the marker in its output, or the game port accepting TCP connections
+#[derive(Debug)]
+pub struct Readiness {
+    /// Printed by the game server once it's ready. The game port is probed without it
+    marker: Option<String>,
+    timeout: Duration,
+
+    /// Game servers being waited for, woken by the ready callback
+    reported: DashMap<Uuid, Arc<Notify>>,
+}
+
+impl Readiness {
+    pub fn new(config: &AppConfig) -> Self {
+        Self {
+            marker: config.ready_marker.clone().filter(|m| !m.is_empty()),
+            timeout: Duration::from_secs(config.ready_timeout_secs),
+            reported: Default::default(),
+        }
+    }
+
+    /// Wakes the wait for the session's game server, `false` when nobody waits for it
+    pub fn report(&self, id: Uuid) -> bool {
+        match self.reported.get(&id) {
+            Some(reported) => {
+                reported.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Waits for the game server to become ready. A game server which exits
+    /// first or isn't ready in time is killed along with its process group
+    pub async fn wait(
+        &self,
+        id: Uuid,
+        child: &mut Child,
+        port: u16,
+        log_path: &Path,
+    ) -> Result<(), SesserError> {
+        let reported = Arc::new(Notify::new());
+        self.reported.insert(id, reported.clone());
+
+        let result = self.probe(&reported, child, port, log_path).await;
+
+        self.reported.remove(&id);
+
+        result
+    }
+
+    async fn probe(
+        &self,
+        reported: &Notify,
+        child: &mut Child,
+        port: u16,
+        log_path: &Path,
+    ) -> Result<(), SesserError> {
+        let probe = async {
+            let mut interval = tokio::time::interval(READY_POLL_INTERVAL);
+            let mut scan = self.marker.as_deref().map(MarkerScan::new);
+
+            loop {
+                interval.tick().await;
+
+                let ready = match &mut scan {
+                    Some(scan) => scan.found(log_path).await.unwrap_or(false),
+                    None => TcpStream::connect((Ipv4Addr::LOCALHOST, port))
+                        .await
+                        .is_ok(),
+                };
+
+                if ready {
+                    break;
+                }
+            }
+        };
+
+        let err = tokio::select! {
+            _ = probe => return Ok(()),
+            _ = reported.notified() => return Ok(()),
+            status = child.wait() => SesserError::ExitedBeforeReady(match status {
+                Ok(status) => status.to_string(),
+                Err(err) => err.to_string(),
+            }),
+            _ = tokio::time::sleep(self.timeout) => SesserError::ReadyTimeout(self.timeout.as_secs()),
+        };
+
+        if let Some(pid) = child.id() {
+            signal_group(pid, libc::SIGKILL);
+        }
+
+        let _ = child.kill().await;
+
+        Err(err)
+    }
+}
+
+/// Looks for the ready marker in a game server log, reading only what was
+/// appended since the last look
+struct MarkerScan<'a> {
+    marker: &'a [u8],
+    offset: u64,
+    /// End of the last read, the marker may continue in the next one
+    carry: Vec<u8>,
+}
+
+impl<'a> MarkerScan<'a> {
+    fn new(marker: &'a str) -> Self {
+        Self {
+            marker: marker.as_bytes(),
+            offset: 0,
+            carry: Vec::new(),
+        }
+    }
+
+    async fn found(&mut self, log_path: &Path) -> io::Result<bool> {
+        let mut file = tokio::fs::File::open(log_path).await?;
+        let len = file.metadata().await?.len();
+
+        // Truncated by the rotation, the game server writes from the start again
+        if len < self.offset {
+            self.offset = 0;
+            self.carry.clear();
+        }
+
+        file.seek(SeekFrom::Start(self.offset)).await?;
+
+        let mut appended = std::mem::take(&mut self.carry);
+        let read = (&mut file)
+            .take(len - self.offset)
+            .read_to_end(&mut appended)
+            .await?;
+        self.offset += read as u64;
+
+        if appended
+            .windows(self.marker.len())
+            .any(|window| window == self.marker)
+        {
+            return Ok(true);
+        }
+
+        let kept = appended.len().saturating_sub(self.marker.len() - 1);
+        self.carry = appended.split_off(kept);
+
+        Ok(false)
+    }
+}
+
+/// Sends `signal` to the process group led by `pid`
+pub fn signal_group(pid: u32, signal: i32) -> bool {
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+
+    unsafe { libc::kill(-pid, signal) == 0 }
+}
+
+/// Whether the process is still running and is the one started at `started_at`.
+/// Without a start time only the pid is checked.
+pub fn is_alive(pid: u32, started_at: Option<u64>) -> bool {
+    let Ok(pid) = i32::try_from(pid) else {
+        return false;
+    };
+
+    // Signal 0 only checks that the process exists and may be signalled
+    let exists = unsafe { libc::kill(pid, 0) } == 0
+        || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM);
+
+    if !exists {
+        return false;
+    }
+
+    match (started_at, start_time(pid as u32)) {
+        (Some(expected), Some(actual)) => expected == actual,
+        _ => true,
+    }
+}
+
+/// Start time of the process in clock ticks since boot, `None` where `/proc`
+/// isn't available
+pub fn start_time(pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+
+    // The command name may contain spaces and parentheses, fields are counted
+    // from the last `)`. Start time is the 22nd field, state being the 3rd.
+    stat.rsplit_once(')')?
+        .1
+        .split_whitespace()
+        .nth(19)?
+        .parse()
+        .ok()
+}
+
+/// Last few kilobytes of a game server log
+pub async fn log_tail(path: &Path) -> String {
+    session_logs::tail(path, LOG_TAIL_BYTES)
+        .await
+        .map(|(tail, _)| tail)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, time::Duration};
+
+    use uuid::Uuid;
+
+    use super::{MarkerScan, RestartBackoff};
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let backoff = RestartBackoff {
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+        };
+
+        assert_eq!(backoff.delay(1), Duration::from_secs(1));
+        assert_eq!(backoff.delay(2), Duration::from_secs(2));
+        assert_eq!(backoff.delay(4), Duration::from_secs(8));
+        assert_eq!(backoff.delay(7), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn backoff_saturates() {
+        let backoff = RestartBackoff {
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(60),
+        };
+
+        assert_eq!(backoff.delay(0), Duration::from_secs(1));
+        assert_eq!(backoff.delay(40), Duration::from_secs(60));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(60));
+
+        let unbounded = RestartBackoff {
+            base: Duration::from_secs(u64::MAX),
+            max: Duration::MAX,
+        };
+
+        assert_eq!(unbounded.delay(u32::MAX), Duration::MAX);
+    }
+
+    #[tokio::test]
+    async fn marker_scan_finds_marker_split_across_reads() {
+        let path = std::env::temp_dir().join(format!("{}.log", Uuid::new_v4()));
+        let mut log = std::fs::File::create(&path).unwrap();
+        let mut scan = MarkerScan::new("SERVER READY");
+
+        log.write_all(b"loading map\nSERVER RE").unwrap();
+        assert!(!scan.found(&path).await.unwrap());
+
+        log.write_all(b"ADY\n").unwrap();
+        assert!(scan.found(&path).await.unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn marker_scan_reads_truncated_log_from_start() {
+        let path = std::env::temp_dir().join(format!("{}.log", Uuid::new_v4()));
+        std::fs::write(&path, b"a long line before the rotation\n").unwrap();
+        let mut scan = MarkerScan::new("READY");
+
+        assert!(!scan.found(&path).await.unwrap());
+
+        std::fs::write(&path, b"READY\n").unwrap();
+        assert!(scan.found(&path).await.unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
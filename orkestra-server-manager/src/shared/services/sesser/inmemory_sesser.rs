@@ -1,22 +1,28 @@
 use std::{
     collections::HashSet,
     net::{Ipv4Addr, SocketAddrV4},
-    process::Stdio,
     sync::{atomic::AtomicU32, Arc},
 };
 
 use anyhow::Result;
 use dashmap::{DashMap, DashSet};
-use tokio::{net::TcpListener, sync::oneshot};
-use tracing::{debug, info_span, warn, Instrument};
+use tokio::process::Child;
+use tracing::{debug, error, info, info_span, warn, Instrument};
 use uuid::Uuid;
 
 use crate::{
-    models::session::{Id, Session, SessionConfig, UpdateSession},
-    shared::config::AppConfig,
+    models::session::{
+        unix_now, Id, Session, SessionConfig, SessionPolicy, SessionSecret, SessionState,
+    },
+    shared::{config::AppConfig, session_logs::SessionLogs},
 };
 
-use super::{error::UpdateSessionError, Sesser};
+use super::{
+    error::SesserError,
+    finished::FinishedSessions,
+    game_server::{self, Launcher, Readiness, RestartBackoff},
+    Sesser,
+};
 
 static GLOBAL_CODE: AtomicU32 = AtomicU32::new(0);
 
@@ -30,7 +36,12 @@ impl InMemorySesser {
         Ok(Self {
             inner: Arc::new(InMemorySesserInner {
                 host: config.host.parse()?,
-                project_name: config.project_name.clone(),
+                launcher: Launcher::new(config),
+                logs: SessionLogs::new(config)?,
+                readiness: Readiness::new(config),
+                finished: FinishedSessions::new(),
+                default_policy: config.session_policy(),
+                restart_backoff: RestartBackoff::new(config),
                 sessions: Default::default(),
                 pending_ports: Default::default(),
             }),
@@ -44,39 +55,92 @@ struct InMemorySesserInner {
     pending_ports: DashSet<u16>,
 
     host: Ipv4Addr,
-    project_name: String,
+    launcher: Launcher,
+    logs: SessionLogs,
+    readiness: Readiness,
+    finished: FinishedSessions,
+    default_policy: SessionPolicy,
+    restart_backoff: RestartBackoff,
 }
 
 impl Sesser for InMemorySesser {
-    async fn create_session(&self, creator_id: Id, config: SessionConfig) -> Result<Session> {
-        let free_port = loop {
-            let free_port = TcpListener::bind("0.0.0.0:0").await.unwrap();
-            let free_port = free_port.local_addr().unwrap().port();
+    fn readiness(&self) -> &Readiness {
+        &self.inner.readiness
+    }
 
-            debug!(event = "Check port for pending", port = free_port);
+    fn finished(&self) -> &FinishedSessions {
+        &self.inner.finished
+    }
 
-            if self.inner.pending_ports.insert(free_port) {
-                break free_port;
-            }
+    fn host(&self) -> Ipv4Addr {
+        self.inner.host
+    }
 
-            debug!(event = "Port is already pending", port = free_port);
-        };
+    fn launcher(&self) -> &Launcher {
+        &self.inner.launcher
+    }
+
+    fn session_logs(&self) -> &SessionLogs {
+        &self.inner.logs
+    }
+
+    fn restart_backoff(&self) -> &RestartBackoff {
+        &self.inner.restart_backoff
+    }
+
+    fn pending_ports(&self) -> &DashSet<u16> {
+        &self.inner.pending_ports
+    }
+
+    /// Ends the session and forgets it, finished sessions aren't kept in memory
+    async fn exited(&self, id: Uuid, cleanly: bool) {
+        match self.end_session(id, cleanly).in_current_span().await {
+            Ok(_) | Err(SesserError::SessionNotFound(_)) => {}
+            Err(err) => error!(event = "Couldn't end session", session_id = %id, error = %err),
+        }
+
+        self.inner.sessions.remove(&id);
+    }
+
+    async fn create_session(
+        &self,
+        creator_id: Id,
+        config: SessionConfig,
+    ) -> Result<Session, SesserError> {
+        let free_port = game_server::reserve_port(&self.inner.pending_ports, None).await?;
 
         debug!(event = "Found free port", port = free_port);
 
         let code = GLOBAL_CODE.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         let code = format!("{:06}", code);
 
+        let now = unix_now();
+
+        let policy = config.policy(self.inner.default_policy);
+
         let session = Session {
             id: Uuid::new_v4(),
             addr: SocketAddrV4::new(self.inner.host, free_port),
             title: config.title,
             code: code.clone(),
+            game_map: config.game_map,
             max_players: config.max_players,
+            policy,
+            restart: config.restart,
+            restarts: 0,
+            owner: creator_id.clone(),
+            creator_connected: false,
             players: HashSet::from([creator_id]),
+            pid: None,
+            state: SessionState::Allocating,
+            created_at: now,
+            state_changed_at: now,
+            secret: SessionSecret::generate(),
+            results: None,
         };
 
-        let (sender, receiver) = oneshot::channel();
+        // Saved before the spawn, so a game server exiting right away finds it to remove
+        self.inner.sessions.insert(session.id, session.clone());
 
         debug!(
             event = "Starting game server",
@@ -84,25 +148,14 @@ impl Sesser for InMemorySesser {
             port = free_port,
         );
 
-        let this = self.clone();
-        let session_clone = session.clone();
-        tokio::spawn(
-            async move {
-                this.start_server_process(session_clone, free_port, sender)
-                    .await;
-            }
-            .in_current_span(),
-        );
+        let log_path = self.log_path(session.id);
 
-        match receiver.await.unwrap() {
-            Ok(_) => {
-                debug!(
-                    event = "Game server started",
-                    session_id = %session.id,
-                    port = free_port,
-                );
-            }
+        let mut child = match self.inner.launcher.spawn(&session, free_port, &log_path) {
+            Ok(child) => child,
             Err(err) => {
+                self.inner.sessions.remove(&session.id);
+                self.inner.pending_ports.remove(&free_port);
+
                 warn!(
                     event = "Occurs error while starting game server",
                     session_id = %session.id,
@@ -112,146 +165,173 @@ impl Sesser for InMemorySesser {
 
                 return Err(err.into());
             }
+        };
+
+        let pid = child.id();
+
+        let starting = self
+            .modify_session(session.id, move |session| {
+                session.pid = pid;
+                session.transition(SessionState::Starting).map_err(|from| {
+                    SesserError::InvalidTransition {
+                        from,
+                        to: SessionState::Starting,
+                    }
+                })
+            })
+            .await;
+
+        // Fails when the session was terminated while spawning, the game server
+        // would otherwise run with nothing watching it
+        if let Err(err) = starting {
+            self.inner.pending_ports.remove(&free_port);
+
+            error!(event = "Couldn't save session", session_id = %session.id, error = %err);
+
+            if let Some(pid) = pid {
+                game_server::signal_group(pid, libc::SIGKILL);
+            }
+
+            self.inner.sessions.remove(&session.id);
+
+            return Err(err);
         }
 
-        self.inner.sessions.insert(session.id, session.clone());
+        let ready = self
+            .inner
+            .readiness
+            .wait(session.id, &mut child, free_port, &log_path)
+            .in_current_span()
+            .await;
+
+        self.inner.pending_ports.remove(&free_port);
+
+        if let Err(err) = ready {
+            self.inner.sessions.remove(&session.id);
+
+            let log = game_server::log_tail(&log_path).await;
+
+            warn!(
+                event = "Game server didn't become ready",
+                session_id = %session.id,
+                port = free_port,
+                error = %err,
+                log = %log,
+            );
+
+            return Err(err);
+        }
+
+        // Fails when the session was terminated while starting
+        let session = match self
+            .transition_session(session.id, SessionState::Ready)
+            .await
+        {
+            Ok(session) => session,
+            Err(err) => {
+                if let Some(pid) = pid {
+                    game_server::signal_group(pid, libc::SIGKILL);
+                }
+
+                self.inner.sessions.remove(&session.id);
+
+                return Err(err);
+            }
+        };
 
         debug!(
             event = "Session was saved in memory",
             session = ?session
         );
 
+        let this = self.clone();
+        let id = session.id;
+        tokio::spawn(
+            async move { this.watch_child(id, free_port, child).await }
+                .instrument(info_span!("create_game_server")),
+        );
+
         Ok(session)
     }
 
-    fn get_by_id(&self, id: Uuid) -> Option<Session> {
-        self.inner.sessions.get(&id).map(|v| v.clone())
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Session>, SesserError> {
+        Ok(self.inner.sessions.get(&id).map(|v| v.clone()))
     }
 
-    fn get_all_sessions(&self) -> Vec<Session> {
-        self.inner
+    async fn get_all_sessions(&self) -> Result<Vec<Session>, SesserError> {
+        Ok(self
+            .inner
             .sessions
             .iter()
+            .filter(|session| !session.state.is_finished())
             .map(|session| session.clone())
-            .collect()
+            .collect())
     }
 
-    fn filter_by_code(&self, code: String) -> Vec<Session> {
-        self.inner
+    async fn filter_by_code(&self, code: String) -> Result<Vec<Session>, SesserError> {
+        Ok(self
+            .inner
             .sessions
             .iter()
-            .find(|session| session.code.eq(&code))
+            .find(|session| !session.state.is_finished() && session.code.eq(&code))
             .map(|session| vec![session.clone()])
-            .unwrap_or_default()
+            .unwrap_or_default())
     }
 
-    fn update_session(
-        &self,
-        id: Uuid,
-        update: UpdateSession,
-    ) -> Result<Session, UpdateSessionError> {
-        let mut session = self
+    async fn modify_session<F>(&self, id: Uuid, modify: F) -> Result<Session, SesserError>
+    where
+        F: FnOnce(&mut Session) -> Result<(), SesserError> + Send,
+    {
+        // The entry stays locked while `modify` runs
+        let mut entry = self
             .inner
             .sessions
             .get_mut(&id)
-            .ok_or(UpdateSessionError::SessionNotFound(id))?;
+            .ok_or(SesserError::SessionNotFound(id))?;
 
-        match update {
-            UpdateSession::AddPlayer(id) => {
-                if session.players.len() < session.max_players as _ {
-                    session.players.insert(id);
+        let mut session = entry.clone();
+        modify(&mut session)?;
+        *entry = session.clone();
 
-                    Ok(session.clone())
-                } else {
-                    Err(UpdateSessionError::SessionIsFull)
-                }
-            }
-            UpdateSession::RemovePlayer(id) => {
-                session.players.remove(&id);
+        Ok(session)
+    }
+
+    /// Sessions are lost with the manager, so their game servers are stopped
+    /// rather than left running without anyone watching them
+    async fn shutdown(&self) {
+        let pids: Vec<(Uuid, u32)> = self
+            .inner
+            .sessions
+            .iter()
+            .filter_map(|session| session.pid.map(|pid| (session.id, pid)))
+            .collect();
 
-                Ok(session.clone())
+        for (id, pid) in pids {
+            if game_server::signal_group(pid, libc::SIGTERM) {
+                info!(
+                    target: "game_server",
+                    event = "Stopped game server on shutdown",
+                    session_id = %id,
+                    pid = pid,
+                );
             }
         }
     }
 }
 
 impl InMemorySesser {
-    async fn start_server_process(
-        &self,
-        session: Session,
-        free_port: u16,
-        sender: oneshot::Sender<Result<(), std::io::Error>>,
-    ) {
-        let span = info_span!("create_game_server");
-        let _guard = span.enter();
-
-        let result = tokio::process::Command::new("bash")
-            .arg(format!(
-                "./{}/{}.sh",
-                self.inner.project_name, self.inner.project_name
-            ))
-            .arg("-log")
-            .arg(format!("-Port={free_port}"))
-            .arg("--serverid")
-            .arg(session.id.to_string())
-            .arg("--servercode")
-            .arg(session.code)
-            .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::piped())
-            .spawn();
-
-        let result = match result {
-            Ok(result) => {
-                let _ = sender.send(Ok(()));
-                result
-            }
-            Err(err) => {
-                let _ = sender.send(Err(err));
-                self.inner.pending_ports.remove(&free_port);
-
-                return;
-            }
-        };
-
-        self.inner.pending_ports.remove(&free_port);
-        let result = result.wait_with_output().in_current_span().await;
-
-        match result {
-            Ok(output) => {
-                let status = output.status;
-
-                if status.success() {
-                    debug!(
-                        target: "game_server",
-                        event = "Game server was finished and removed",
-                        session_id = ?session.id,
-                        port = free_port,
-                    );
-                } else {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-
-                    warn!(
-                        target: "game_server",
-                        event = "Game server exit with error",
-                        session_id = %session.id,
-                        port = free_port,
-                        status = %status,
-                        stderr = %stderr
-                    );
+    /// Waits for the game server to exit, then restarts it or forgets its session
+    async fn watch_child(self, id: Uuid, mut port: u16, mut child: Child) {
+        loop {
+            let exited_cleanly = self.wait_child(id, port, &mut child).await;
+
+            match self.after_exit(id, exited_cleanly).in_current_span().await {
+                Some((restarted_port, restarted)) => {
+                    port = restarted_port;
+                    child = restarted;
                 }
-            }
-            Err(err) => {
-                warn!(
-                    target: "game_server",
-                    event = "Occurs error while running the game server",
-                    session_id = %session.id,
-                    port = free_port,
-                    error = %err
-                );
+                None => return,
             }
         }
-
-        self.inner.sessions.remove(&session.id);
     }
 }
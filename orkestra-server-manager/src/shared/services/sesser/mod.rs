@@ -1,29 +1,495 @@
-use std::future::Future;
+use std::{
+    future::Future,
+    net::{Ipv4Addr, SocketAddrV4},
+    path::PathBuf,
+    time::Duration,
+};
 
-use anyhow::Result;
-use error::UpdateSessionError;
+use dashmap::DashSet;
+use error::SesserError;
+use finished::FinishedSessions;
+use game_server::{Launcher, Readiness, RestartBackoff};
+use tokio::process::Child;
+use tracing::{debug, error, info, warn, Instrument};
 use uuid::Uuid;
 
-use crate::models::session::{Id, Session, SessionConfig, UpdateSession};
+use crate::{
+    models::session::{Id, Session, SessionConfig, SessionState, UpdateSession},
+    shared::session_logs::{self, SessionLogs},
+};
 
 pub mod error;
+pub mod finished;
+pub mod game_server;
 pub mod inmemory_sesser;
+pub mod sqlite_sesser;
 
 pub trait Sesser: Clone + Send + Sync + 'static {
+    fn readiness(&self) -> &Readiness;
+
+    fn finished(&self) -> &FinishedSessions;
+
+    /// Address game servers are reachable at
+    fn host(&self) -> Ipv4Addr;
+
+    fn launcher(&self) -> &Launcher;
+
+    fn session_logs(&self) -> &SessionLogs;
+
+    fn restart_backoff(&self) -> &RestartBackoff;
+
+    /// Ports handed out to game servers that don't listen on them yet
+    fn pending_ports(&self) -> &DashSet<u16>;
+
     fn create_session(
         &self,
         creator_id: Id,
         config: SessionConfig,
-    ) -> impl Future<Output = Result<Session>> + Send;
+    ) -> impl Future<Output = Result<Session, SesserError>> + Send;
 
-    fn get_by_id(&self, id: Uuid) -> Option<Session>;
-    fn get_all_sessions(&self) -> Vec<Session>;
+    fn get_by_id(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<Session>, SesserError>> + Send;
+    fn get_all_sessions(&self) -> impl Future<Output = Result<Vec<Session>, SesserError>> + Send;
 
-    fn filter_by_code(&self, code: String) -> Vec<Session>;
+    fn filter_by_code(
+        &self,
+        code: String,
+    ) -> impl Future<Output = Result<Vec<Session>, SesserError>> + Send;
+
+    /// Reads the session, applies `modify` and stores the result as one atomic
+    /// step, no other change to the session can land in between. Nothing is
+    /// stored when `modify` fails
+    fn modify_session<F>(
+        &self,
+        id: Uuid,
+        modify: F,
+    ) -> impl Future<Output = Result<Session, SesserError>> + Send
+    where
+        F: FnOnce(&mut Session) -> Result<(), SesserError> + Send;
 
     fn update_session(
         &self,
         id: Uuid,
         update: UpdateSession,
-    ) -> Result<Session, UpdateSessionError>;
+    ) -> impl Future<Output = Result<Session, SesserError>> + Send {
+        self.modify_session(id, move |session| match update {
+            UpdateSession::AddPlayer(id) => {
+                if !session.state.is_joinable() {
+                    return Err(SesserError::NotJoinable(session.state));
+                }
+
+                if session.players.len() >= session.max_players as _ {
+                    return Err(SesserError::SessionIsFull);
+                }
+
+                session.players.insert(id);
+
+                Ok(())
+            }
+            UpdateSession::RemovePlayer(id) => {
+                session.players.remove(&id);
+
+                Ok(())
+            }
+        })
+    }
+
+    /// Moves the session to `next` if the lifecycle allows it
+    fn transition_session(
+        &self,
+        id: Uuid,
+        next: SessionState,
+    ) -> impl Future<Output = Result<Session, SesserError>> + Send {
+        self.modify_session(id, move |session| {
+            session
+                .transition(next)
+                .map_err(|from| SesserError::InvalidTransition { from, to: next })
+        })
+    }
+
+    /// Records the exit of the session's game server. A crash fails the
+    /// session unless it was already draining
+    fn end_session(
+        &self,
+        id: Uuid,
+        exited_cleanly: bool,
+    ) -> impl Future<Output = Result<Session, SesserError>> + Send {
+        async move {
+            let session = self
+                .modify_session(id, move |session| {
+                    let next = if exited_cleanly || session.state == SessionState::Draining {
+                        SessionState::Ended
+                    } else {
+                        SessionState::Failed
+                    };
+
+                    session
+                        .transition(next)
+                        .map_err(|from| SesserError::InvalidTransition { from, to: next })
+                })
+                .await?;
+
+            self.finished().report(session.clone());
+
+            Ok(session)
+        }
+    }
+
+    /// Moves the session back to starting when its restart policy wants the
+    /// exited game server started again, `None` when it doesn't. Called from
+    /// a restart that failed too, while the session is still starting
+    fn begin_restart(
+        &self,
+        id: Uuid,
+        exited_cleanly: bool,
+    ) -> impl Future<Output = Result<Option<Session>, SesserError>> + Send {
+        async move {
+            let mut restarting = false;
+
+            let session = self
+                .modify_session(id, |session| {
+                    let running = matches!(
+                        session.state,
+                        SessionState::Starting | SessionState::Ready | SessionState::InProgress
+                    );
+
+                    if !running || !session.restart.allows(exited_cleanly, session.restarts) {
+                        return Ok(());
+                    }
+
+                    if session.state != SessionState::Starting {
+                        session.transition(SessionState::Starting).map_err(|from| {
+                            SesserError::InvalidTransition {
+                                from,
+                                to: SessionState::Starting,
+                            }
+                        })?;
+                    }
+
+                    session.restarts += 1;
+                    session.pid = None;
+                    restarting = true;
+
+                    Ok(())
+                })
+                .await?;
+
+            Ok(restarting.then_some(session))
+        }
+    }
+
+    /// Kills the session's game server with everything it started, `false`
+    /// when there was nothing to signal
+    fn kill_session(&self, id: Uuid) -> impl Future<Output = Result<bool, SesserError>> + Send {
+        async move {
+            let session = self
+                .get_by_id(id)
+                .await?
+                .ok_or(SesserError::SessionNotFound(id))?;
+
+            Ok(session
+                .pid
+                .is_some_and(|pid| game_server::signal_group(pid, libc::SIGKILL)))
+        }
+    }
+
+    /// Asks the session's game server to stop with SIGTERM and kills it with
+    /// everything it started if it's still around after `grace`. The session
+    /// is drained right away and ended by whoever watches the process
+    fn terminate_session(
+        &self,
+        id: Uuid,
+        grace: Duration,
+    ) -> impl Future<Output = Result<Session, SesserError>> + Send {
+        async move {
+            let session = self
+                .modify_session(id, |session| {
+                    // Terminating twice only signals again
+                    if session.state == SessionState::Draining {
+                        return Ok(());
+                    }
+
+                    session.transition(SessionState::Draining).map_err(|from| {
+                        SesserError::InvalidTransition {
+                            from,
+                            to: SessionState::Draining,
+                        }
+                    })
+                })
+                .await?;
+
+            let Some(pid) = session.pid else {
+                return Ok(session);
+            };
+
+            // Taken before the signal, so a reused pid isn't killed after the grace
+            let started_at = game_server::start_time(pid);
+
+            if !game_server::signal_group(pid, libc::SIGTERM) {
+                return Ok(session);
+            }
+
+            tokio::spawn(
+                async move {
+                    tokio::time::sleep(grace).await;
+
+                    if !game_server::is_alive(pid, started_at) {
+                        return;
+                    }
+
+                    if game_server::signal_group(pid, libc::SIGKILL) {
+                        warn!(
+                            target: "game_server",
+                            event = "Game server ignored SIGTERM and was killed",
+                            session_id = %id,
+                            pid = pid,
+                        );
+                    }
+                }
+                .in_current_span(),
+            );
+
+            Ok(session)
+        }
+    }
+
+    /// Handles a game server that exited for good: ends its session and lets
+    /// the backend forget it if it wants to
+    fn exited(&self, id: Uuid, cleanly: bool) -> impl Future<Output = ()> + Send;
+
+    fn log_path(&self, id: Uuid) -> PathBuf {
+        self.session_logs().path(id)
+    }
+
+    /// Whether the game server exited cleanly
+    fn wait_child<'a>(
+        &'a self,
+        id: Uuid,
+        port: u16,
+        child: &'a mut Child,
+    ) -> impl Future<Output = bool> + Send + 'a {
+        async move {
+            match child.wait().in_current_span().await {
+                Ok(status) if status.success() => {
+                    debug!(
+                        target: "game_server",
+                        event = "Game server was finished",
+                        session_id = %id,
+                        port = port,
+                    );
+
+                    true
+                }
+                Ok(status) => {
+                    let log = game_server::log_tail(&self.log_path(id)).await;
+
+                    warn!(
+                        target: "game_server",
+                        event = "Game server exit with error",
+                        session_id = %id,
+                        port = port,
+                        status = %status,
+                        log = %log,
+                    );
+
+                    false
+                }
+                Err(err) => {
+                    warn!(
+                        target: "game_server",
+                        event = "Occurs error while running the game server",
+                        session_id = %id,
+                        port = port,
+                        error = %err
+                    );
+
+                    false
+                }
+            }
+        }
+    }
+
+    /// Restarts the exited game server if the session's restart policy asks
+    /// for it, ends the session otherwise
+    fn after_exit(
+        &self,
+        id: Uuid,
+        cleanly: bool,
+    ) -> impl Future<Output = Option<(u16, Child)>> + Send {
+        async move {
+            match self.begin_restart(id, cleanly).in_current_span().await {
+                Ok(Some(session)) => {
+                    let restarted = self.restart(session).in_current_span().await;
+
+                    if restarted.is_none() {
+                        self.exited(id, false).in_current_span().await;
+                    }
+
+                    restarted
+                }
+                // Already finished
+                Err(SesserError::SessionNotFound(_)) => None,
+                Ok(None) => {
+                    self.exited(id, cleanly).in_current_span().await;
+                    None
+                }
+                Err(err) => {
+                    error!(event = "Couldn't restart game server", session_id = %id, error = %err);
+
+                    self.exited(id, cleanly).in_current_span().await;
+                    None
+                }
+            }
+        }
+    }
+
+    /// Starts the game server of the session again after the backoff, trying
+    /// again while the restart policy allows. `None` once it gives up
+    fn restart(&self, mut session: Session) -> impl Future<Output = Option<(u16, Child)>> + Send {
+        async move {
+            loop {
+                let delay = self.restart_backoff().delay(session.restarts);
+
+                info!(
+                    target: "game_server",
+                    event = "Restarting game server",
+                    session_id = %session.id,
+                    restart = session.restarts,
+                    delay_secs = delay.as_secs(),
+                );
+
+                tokio::time::sleep(delay).await;
+
+                let err = match self.respawn(&session).in_current_span().await {
+                    Ok(restarted) => return Some(restarted),
+                    Err(err) => err,
+                };
+
+                let log = game_server::log_tail(&self.log_path(session.id)).await;
+
+                warn!(
+                    target: "game_server",
+                    event = "Game server didn't restart",
+                    session_id = %session.id,
+                    error = %err,
+                    log = %log,
+                );
+
+                match self
+                    .begin_restart(session.id, false)
+                    .in_current_span()
+                    .await
+                {
+                    Ok(Some(next)) => session = next,
+                    _ => return None,
+                }
+            }
+        }
+    }
+
+    /// Spawns the game server of a restarting session, on its old port if
+    /// that's still free
+    fn respawn<'a>(
+        &'a self,
+        session: &'a Session,
+    ) -> impl Future<Output = Result<(u16, Child), SesserError>> + Send + 'a {
+        async move {
+            let port =
+                game_server::reserve_port(self.pending_ports(), Some(session.addr.port())).await?;
+
+            let result = self.respawn_on(session.id, port).in_current_span().await;
+
+            self.pending_ports().remove(&port);
+
+            Ok((port, result?))
+        }
+    }
+
+    fn respawn_on(
+        &self,
+        id: Uuid,
+        port: u16,
+    ) -> impl Future<Output = Result<Child, SesserError>> + Send {
+        async move {
+            let addr = SocketAddrV4::new(self.host(), port);
+
+            // Fails when the session was terminated during the backoff
+            let session = self
+                .modify_session(id, move |session| match session.state {
+                    SessionState::Starting => {
+                        session.addr = addr;
+                        Ok(())
+                    }
+                    from => Err(SesserError::InvalidTransition {
+                        from,
+                        to: SessionState::Starting,
+                    }),
+                })
+                .await?;
+
+            let logs = self.session_logs().clone();
+
+            if let Err(err) = session_logs::unblock(move || logs.archive(id)).await {
+                debug!(event = "Couldn't archive game server log", session_id = %id, error = %err);
+            }
+
+            let log_path = self.log_path(id);
+
+            let mut child = self.launcher().spawn(&session, port, &log_path)?;
+            let pid = child.id();
+
+            let ready = async {
+                self.modify_session(id, move |session| {
+                    session.pid = pid;
+                    Ok(())
+                })
+                .await?;
+
+                self.readiness()
+                    .wait(id, &mut child, port, &log_path)
+                    .in_current_span()
+                    .await?;
+
+                self.transition_session(id, SessionState::Ready).await
+            };
+
+            if let Err(err) = ready.await {
+                if let Some(pid) = pid {
+                    game_server::signal_group(pid, libc::SIGKILL);
+                }
+
+                return Err(err);
+            }
+
+            Ok(child)
+        }
+    }
+
+    /// Takes the game server's word that it's ready. A `create_session` still
+    /// waiting for it returns right away
+    fn report_ready(&self, id: Uuid) -> impl Future<Output = Result<Session, SesserError>> + Send {
+        async move {
+            let session = self
+                .get_by_id(id)
+                .await?
+                .ok_or(SesserError::SessionNotFound(id))?;
+
+            match session.state {
+                SessionState::Starting if self.readiness().report(id) => Ok(session),
+                SessionState::Ready | SessionState::InProgress => Ok(session),
+                from => Err(SesserError::InvalidTransition {
+                    from,
+                    to: SessionState::Ready,
+                }),
+            }
+        }
+    }
+
+    /// Called once the manager stops serving. Game servers run in their own
+    /// process group and outlive the manager unless the sesser stops them here
+    fn shutdown(&self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
 }
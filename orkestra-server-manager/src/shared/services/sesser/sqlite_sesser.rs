@@ -0,0 +1,969 @@
+use std::{
+    collections::HashSet,
+    net::{Ipv4Addr, SocketAddrV4},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use anyhow::Result;
+use dashmap::DashSet;
+use sqlx::FromRow;
+use tokio::process::Child;
+use tracing::{debug, error, info, info_span, warn, Instrument};
+use uuid::Uuid;
+
+use crate::{
+    models::session::{
+        unix_now, Id, Session, SessionConfig, SessionPolicy, SessionSecret, SessionState,
+    },
+    shared::{config::AppConfig, database::Database, session_logs::SessionLogs},
+};
+
+use super::{
+    error::SesserError,
+    finished::FinishedSessions,
+    game_server::{self, Launcher, Readiness, RestartBackoff},
+    Sesser,
+};
+
+/// Ended sessions are kept around for this long for inspection
+const ENDED_RETENTION_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// Columns [`SessionRow`] is read from
+const SESSION_COLUMNS: &str = "id, addr, title, code, game_map, max_players, players, pid, \
+    pid_started_at, state, created_at, state_changed_at, secret, results, owner_id, \
+    creator_connected, idle_timeout_secs, max_lifetime_secs, creator_grace_secs, restart_policy, \
+    restarts";
+
+/// Sessions whose game server may still be running
+const LIVE_FILTER: &str = "state NOT IN ('ended', 'failed')";
+
+/// Sesser keeping sessions and their game server pids in SQLite, so a
+/// restarted manager re-adopts game servers that are still running.
+#[derive(Clone, Debug)]
+pub struct SqliteSesser {
+    inner: Arc<SqliteSesserInner>,
+}
+
+#[derive(Debug)]
+struct SqliteSesserInner {
+    database: Database,
+
+    pending_ports: DashSet<u16>,
+    next_code: AtomicU32,
+
+    host: Ipv4Addr,
+    launcher: Launcher,
+    logs: SessionLogs,
+    readiness: Readiness,
+    finished: FinishedSessions,
+    default_policy: SessionPolicy,
+    restart_backoff: RestartBackoff,
+    orphan_poll_interval: Duration,
+}
+
+#[derive(Debug, FromRow)]
+struct SessionRow {
+    id: String,
+    addr: String,
+    title: String,
+    code: String,
+    game_map: String,
+    max_players: i64,
+    players: String,
+    pid: Option<i64>,
+    pid_started_at: Option<i64>,
+    state: String,
+    created_at: i64,
+    state_changed_at: i64,
+    secret: String,
+    results: Option<String>,
+    owner_id: String,
+    creator_connected: bool,
+    idle_timeout_secs: i64,
+    max_lifetime_secs: i64,
+    creator_grace_secs: i64,
+    restart_policy: String,
+    restarts: i64,
+}
+
+impl SqliteSesser {
+    pub async fn new(config: &AppConfig, database: Database) -> Result<Self> {
+        let this = Self {
+            inner: Arc::new(SqliteSesserInner {
+                database,
+                pending_ports: Default::default(),
+                next_code: AtomicU32::new(0),
+                host: config.host.parse()?,
+                launcher: Launcher::new(config),
+                logs: SessionLogs::new(config)?,
+                readiness: Readiness::new(config),
+                finished: FinishedSessions::new(),
+                default_policy: config.session_policy(),
+                restart_backoff: RestartBackoff::new(config),
+                orphan_poll_interval: Duration::from_secs(config.orphan_poll_interval_secs.max(1)),
+            }),
+        };
+
+        this.reconcile().in_current_span().await?;
+
+        Ok(this)
+    }
+
+    /// Re-adopts game servers left running by the previous manager and ends
+    /// the sessions whose process is gone
+    async fn reconcile(&self) -> Result<()> {
+        const CLEANUP_QUERY: &str =
+            "DELETE FROM sessions WHERE state IN ('ended', 'failed') AND ended_at < ?;";
+
+        let database = self.inner.database.as_ref();
+
+        sqlx::query(CLEANUP_QUERY)
+            .bind(unix_now() as i64 - ENDED_RETENTION_SECS)
+            .execute(database)
+            .in_current_span()
+            .await?;
+
+        let live_query = format!("SELECT {SESSION_COLUMNS} FROM sessions WHERE {LIVE_FILTER};");
+
+        let rows: Vec<SessionRow> = sqlx::query_as(&live_query)
+            .fetch_all(database)
+            .in_current_span()
+            .await?;
+
+        let mut max_code = None;
+
+        for row in rows {
+            let pid = row.pid.and_then(|pid| u32::try_from(pid).ok());
+            let started_at = row.pid_started_at.and_then(|t| u64::try_from(t).ok());
+
+            let session = match row.into_session() {
+                Ok(session) => session,
+                Err(err) => {
+                    warn!(event = "Failing unreadable session", error = %err);
+
+                    if let SesserError::Malformed(id) = err {
+                        self.finish(&id, SessionState::Failed)
+                            .in_current_span()
+                            .await;
+                    }
+                    continue;
+                }
+            };
+
+            let Some(pid) = pid else {
+                info!(
+                    event = "Game server was never spawned, failing session",
+                    session_id = %session.id,
+                );
+
+                self.finish(&session.id.to_string(), SessionState::Failed)
+                    .in_current_span()
+                    .await;
+                continue;
+            };
+
+            if !game_server::is_alive(pid, started_at) {
+                info!(
+                    event = "Game server is gone, ending session",
+                    session_id = %session.id,
+                    pid = pid,
+                );
+
+                self.finish(&session.id.to_string(), SessionState::Ended)
+                    .in_current_span()
+                    .await;
+
+                // Its players may still show as in the session
+                let mut session = session;
+                let _ = session.transition(SessionState::Ended);
+                self.inner.finished.report(session);
+                continue;
+            }
+
+            // Whoever asked for a session still starting never got its address
+            if session.state == SessionState::Starting {
+                info!(
+                    event = "Killing game server that was still starting",
+                    session_id = %session.id,
+                    pid = pid,
+                );
+
+                game_server::signal_group(pid, libc::SIGKILL);
+
+                self.finish(&session.id.to_string(), SessionState::Failed)
+                    .in_current_span()
+                    .await;
+                continue;
+            }
+
+            info!(
+                event = "Re-adopted running game server",
+                session_id = %session.id,
+                pid = pid,
+                addr = %session.addr,
+                state = %session.state,
+            );
+
+            if let Ok(code) = session.code.parse::<u32>() {
+                max_code = max_code.max(Some(code));
+            }
+
+            let id = session.id;
+            let this = self.clone();
+            tokio::spawn(
+                async move { this.watch_orphan(id, pid, started_at).await }
+                    .instrument(info_span!("orphan_watcher")),
+            );
+        }
+
+        // Codes of re-adopted sessions must not be handed out again
+        if let Some(code) = max_code {
+            self.inner.next_code.store(code + 1, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    /// Waits for a game server started by this manager to exit, restarting it
+    /// while the session's restart policy asks for it
+    async fn watch_child(self, id: Uuid, mut port: u16, mut child: Child) {
+        loop {
+            let exited_cleanly = self.wait_child(id, port, &mut child).await;
+
+            match self.after_exit(id, exited_cleanly).in_current_span().await {
+                Some((restarted_port, restarted)) => {
+                    port = restarted_port;
+                    child = restarted;
+                }
+                None => return,
+            }
+        }
+    }
+
+    /// Polls a re-adopted game server, it isn't our child so it can't be waited for
+    async fn watch_orphan(self, id: Uuid, pid: u32, started_at: Option<u64>) {
+        let mut interval = tokio::time::interval(self.inner.orphan_poll_interval);
+
+        loop {
+            interval.tick().await;
+
+            if !game_server::is_alive(pid, started_at) {
+                break;
+            }
+        }
+
+        debug!(
+            target: "game_server",
+            event = "Re-adopted game server exited",
+            session_id = %id,
+            pid = pid,
+        );
+
+        // The exit status of a process we didn't spawn is unknown
+        if let Some((port, child)) = self.after_exit(id, true).in_current_span().await {
+            self.watch_child(id, port, child).await;
+        }
+    }
+
+    /// Finishes a session without reading it, for rows that can't be read into
+    /// a [`Session`] and game servers that never became ready
+    async fn finish(&self, id: &str, state: SessionState) {
+        let finish_query = format!(
+            "UPDATE sessions SET state = ?, state_changed_at = ?, ended_at = ? \
+            WHERE id = ? AND {LIVE_FILTER};"
+        );
+
+        let now = unix_now() as i64;
+
+        if let Err(err) = sqlx::query(&finish_query)
+            .bind(state.as_str())
+            .bind(now)
+            .bind(now)
+            .bind(id)
+            .execute(self.inner.database.as_ref())
+            .in_current_span()
+            .await
+        {
+            error!(event = "Couldn't finish session", session_id = id, error = %err);
+        }
+    }
+
+    async fn insert(&self, session: &Session) -> Result<(), sqlx::Error> {
+        const INSERT_QUERY: &str = "INSERT INTO sessions (id, addr, title, code, game_map, \
+            max_players, players, state, created_at, state_changed_at, secret, owner_id, \
+            creator_connected, idle_timeout_secs, max_lifetime_secs, creator_grace_secs, \
+            restart_policy) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?);";
+
+        let players: Vec<&Id> = session.players.iter().collect();
+
+        sqlx::query(INSERT_QUERY)
+            .bind(session.id.to_string())
+            .bind(session.addr.to_string())
+            .bind(&session.title)
+            .bind(&session.code)
+            .bind(&session.game_map)
+            .bind(session.max_players as i64)
+            .bind(serde_json::to_string(&players).unwrap_or_default())
+            .bind(session.state.as_str())
+            .bind(session.created_at as i64)
+            .bind(session.state_changed_at as i64)
+            .bind(&session.secret.0)
+            .bind(&session.owner.0)
+            .bind(session.creator_connected)
+            .bind(session.policy.idle_timeout_secs as i64)
+            .bind(session.policy.max_lifetime_secs as i64)
+            .bind(session.policy.creator_grace_secs as i64)
+            .bind(serde_json::to_string(&session.restart).unwrap_or_default())
+            .execute(self.inner.database.as_ref())
+            .in_current_span()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn spawned(&self, session: &Session) -> Result<(), sqlx::Error> {
+        const SPAWNED_QUERY: &str = "UPDATE sessions SET pid = ?, pid_started_at = ?, state = ?, \
+            state_changed_at = ? WHERE id = ?;";
+
+        let pid = session.pid;
+        let started_at = pid.and_then(game_server::start_time);
+
+        sqlx::query(SPAWNED_QUERY)
+            .bind(pid.map(i64::from))
+            .bind(started_at.and_then(|t| i64::try_from(t).ok()))
+            .bind(session.state.as_str())
+            .bind(session.state_changed_at as i64)
+            .bind(session.id.to_string())
+            .execute(self.inner.database.as_ref())
+            .in_current_span()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn fetch(&self, filter: &str, bind: Option<String>) -> Result<Vec<Session>, SesserError> {
+        let query = format!("SELECT {SESSION_COLUMNS} FROM sessions WHERE {filter};");
+
+        let mut query = sqlx::query_as(&query);
+
+        if let Some(bind) = bind {
+            query = query.bind(bind);
+        }
+
+        let rows: Vec<SessionRow> = query
+            .fetch_all(self.inner.database.as_ref())
+            .in_current_span()
+            .await?;
+
+        rows.into_iter().map(SessionRow::into_session).collect()
+    }
+}
+
+impl Sesser for SqliteSesser {
+    fn readiness(&self) -> &Readiness {
+        &self.inner.readiness
+    }
+
+    fn finished(&self) -> &FinishedSessions {
+        &self.inner.finished
+    }
+
+    fn host(&self) -> Ipv4Addr {
+        self.inner.host
+    }
+
+    fn launcher(&self) -> &Launcher {
+        &self.inner.launcher
+    }
+
+    fn session_logs(&self) -> &SessionLogs {
+        &self.inner.logs
+    }
+
+    fn restart_backoff(&self) -> &RestartBackoff {
+        &self.inner.restart_backoff
+    }
+
+    fn pending_ports(&self) -> &DashSet<u16> {
+        &self.inner.pending_ports
+    }
+
+    async fn exited(&self, id: Uuid, cleanly: bool) {
+        match self.end_session(id, cleanly).in_current_span().await {
+            // Already finished
+            Ok(_) | Err(SesserError::SessionNotFound(_)) => {}
+            Err(err) => error!(event = "Couldn't end session", session_id = %id, error = %err),
+        }
+    }
+
+    async fn create_session(
+        &self,
+        creator_id: Id,
+        config: SessionConfig,
+    ) -> Result<Session, SesserError> {
+        let free_port = game_server::reserve_port(&self.inner.pending_ports, None).await?;
+
+        let code = self.inner.next_code.fetch_add(1, Ordering::Relaxed);
+        let now = unix_now();
+
+        let policy = config.policy(self.inner.default_policy);
+
+        let mut session = Session {
+            id: Uuid::new_v4(),
+            addr: SocketAddrV4::new(self.inner.host, free_port),
+            title: config.title,
+            code: format!("{:06}", code),
+            game_map: config.game_map,
+            max_players: config.max_players,
+            policy,
+            restart: config.restart,
+            restarts: 0,
+            owner: creator_id.clone(),
+            creator_connected: false,
+            players: HashSet::from([creator_id]),
+            pid: None,
+            state: SessionState::Allocating,
+            created_at: now,
+            state_changed_at: now,
+            secret: SessionSecret::generate(),
+            results: None,
+        };
+
+        if let Err(err) = self.insert(&session).in_current_span().await {
+            self.inner.pending_ports.remove(&free_port);
+
+            error!(event = "Couldn't persist session", session_id = %session.id, error = %err);
+
+            return Err(err.into());
+        }
+
+        debug!(
+            event = "Starting game server",
+            session_id = %session.id,
+            port = free_port,
+        );
+
+        // The game server writes into a file rather than a pipe, so it keeps
+        // running when the manager goes away
+        let log_path = self.log_path(session.id);
+
+        let spawned = self.inner.launcher.spawn(&session, free_port, &log_path);
+
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(err) => {
+                self.inner.pending_ports.remove(&free_port);
+
+                warn!(
+                    event = "Occurs error while starting game server",
+                    session_id = %session.id,
+                    port = free_port,
+                    error = %err
+                );
+
+                self.finish(&session.id.to_string(), SessionState::Failed)
+                    .in_current_span()
+                    .await;
+
+                return Err(err.into());
+            }
+        };
+
+        let _ = session.transition(SessionState::Starting);
+        session.pid = child.id();
+
+        if let Err(err) = self.spawned(&session).in_current_span().await {
+            self.inner.pending_ports.remove(&free_port);
+
+            error!(event = "Couldn't persist session", session_id = %session.id, error = %err);
+
+            if let Some(pid) = child.id() {
+                game_server::signal_group(pid, libc::SIGKILL);
+            }
+
+            self.finish(&session.id.to_string(), SessionState::Failed)
+                .in_current_span()
+                .await;
+
+            return Err(err.into());
+        }
+
+        let ready = self
+            .inner
+            .readiness
+            .wait(session.id, &mut child, free_port, &log_path)
+            .in_current_span()
+            .await;
+
+        self.inner.pending_ports.remove(&free_port);
+
+        if let Err(err) = ready {
+            let log = game_server::log_tail(&log_path).await;
+
+            warn!(
+                event = "Game server didn't become ready",
+                session_id = %session.id,
+                port = free_port,
+                error = %err,
+                log = %log,
+            );
+
+            self.finish(&session.id.to_string(), SessionState::Failed)
+                .in_current_span()
+                .await;
+
+            return Err(err);
+        }
+
+        let session = match self
+            .transition_session(session.id, SessionState::Ready)
+            .in_current_span()
+            .await
+        {
+            Ok(session) => session,
+            Err(err) => {
+                error!(event = "Couldn't mark session ready", session_id = %session.id, error = %err);
+
+                if let Some(pid) = child.id() {
+                    game_server::signal_group(pid, libc::SIGKILL);
+                }
+
+                self.finish(&session.id.to_string(), SessionState::Failed)
+                    .in_current_span()
+                    .await;
+
+                return Err(err);
+            }
+        };
+
+        debug!(
+            event = "Game server is ready",
+            session = ?session
+        );
+
+        let this = self.clone();
+        let id = session.id;
+        tokio::spawn(
+            async move { this.watch_child(id, free_port, child).await }
+                .instrument(info_span!("create_game_server")),
+        );
+
+        Ok(session)
+    }
+
+    async fn get_by_id(&self, id: Uuid) -> Result<Option<Session>, SesserError> {
+        // Finished sessions are kept for a while, so their outcome can be looked up
+        let mut sessions = self
+            .fetch("id = ?", Some(id.to_string()))
+            .in_current_span()
+            .await?;
+
+        Ok(sessions.pop())
+    }
+
+    async fn get_all_sessions(&self) -> Result<Vec<Session>, SesserError> {
+        self.fetch(LIVE_FILTER, None).in_current_span().await
+    }
+
+    async fn filter_by_code(&self, code: String) -> Result<Vec<Session>, SesserError> {
+        self.fetch(&format!("{LIVE_FILTER} AND code = ?"), Some(code))
+            .in_current_span()
+            .await
+    }
+
+    async fn modify_session<F>(&self, id: Uuid, modify: F) -> Result<Session, SesserError>
+    where
+        F: FnOnce(&mut Session) -> Result<(), SesserError> + Send,
+    {
+        const UPDATE_QUERY: &str = "UPDATE sessions SET addr = ?, players = ?, pid = ?, \
+            pid_started_at = ?, state = ?, state_changed_at = ?, ended_at = ?, results = ?, \
+            restarts = ?, creator_connected = ? WHERE id = ?;";
+
+        // A no-op write as the first statement takes SQLite's write lock right
+        // away, so concurrent modifications queue up instead of racing
+        let lock_query = format!(
+            "UPDATE sessions SET players = players WHERE id = ? AND {LIVE_FILTER} \
+            RETURNING {SESSION_COLUMNS};"
+        );
+
+        let mut transaction = self
+            .inner
+            .database
+            .as_ref()
+            .begin()
+            .in_current_span()
+            .await?;
+
+        let row: Option<SessionRow> = sqlx::query_as(&lock_query)
+            .bind(id.to_string())
+            .fetch_optional(&mut *transaction)
+            .in_current_span()
+            .await?;
+
+        let row = row.ok_or(SesserError::SessionNotFound(id))?;
+        let (pid, pid_started_at) = (row.pid, row.pid_started_at);
+
+        let mut session = row.into_session()?;
+
+        modify(&mut session)?;
+
+        // A restarted game server is a new process
+        let pid_started_at = if session.pid.map(i64::from) == pid {
+            pid_started_at
+        } else {
+            session
+                .pid
+                .and_then(game_server::start_time)
+                .and_then(|t| i64::try_from(t).ok())
+        };
+
+        let players: Vec<&Id> = session.players.iter().collect();
+        let ended_at = session
+            .state
+            .is_finished()
+            .then_some(session.state_changed_at as i64);
+
+        sqlx::query(UPDATE_QUERY)
+            .bind(session.addr.to_string())
+            .bind(serde_json::to_string(&players).unwrap_or_default())
+            .bind(session.pid.map(i64::from))
+            .bind(pid_started_at)
+            .bind(session.state.as_str())
+            .bind(session.state_changed_at as i64)
+            .bind(ended_at)
+            .bind(session.results.as_ref().map(|results| results.to_string()))
+            .bind(session.restarts as i64)
+            .bind(session.creator_connected)
+            .bind(id.to_string())
+            .execute(&mut *transaction)
+            .in_current_span()
+            .await?;
+
+        transaction.commit().in_current_span().await?;
+
+        Ok(session)
+    }
+}
+
+impl SessionRow {
+    fn into_session(self) -> Result<Session, SesserError> {
+        let malformed = || SesserError::Malformed(self.id.clone());
+
+        Ok(Session {
+            id: self.id.parse().map_err(|_| malformed())?,
+            addr: self.addr.parse().map_err(|_| malformed())?,
+            max_players: u32::try_from(self.max_players).map_err(|_| malformed())?,
+            players: serde_json::from_str::<Vec<Id>>(&self.players)
+                .map_err(|_| malformed())?
+                .into_iter()
+                .collect(),
+            state: SessionState::parse(&self.state).ok_or_else(malformed)?,
+            created_at: u64::try_from(self.created_at).map_err(|_| malformed())?,
+            state_changed_at: u64::try_from(self.state_changed_at).map_err(|_| malformed())?,
+            pid: self.pid.and_then(|pid| u32::try_from(pid).ok()),
+            results: self
+                .results
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()
+                .map_err(|_| malformed())?,
+            secret: SessionSecret(self.secret),
+            owner: Id(self.owner_id),
+            creator_connected: self.creator_connected,
+            policy: SessionPolicy {
+                idle_timeout_secs: u64::try_from(self.idle_timeout_secs)
+                    .map_err(|_| malformed())?,
+                max_lifetime_secs: u64::try_from(self.max_lifetime_secs)
+                    .map_err(|_| malformed())?,
+                creator_grace_secs: u64::try_from(self.creator_grace_secs)
+                    .map_err(|_| malformed())?,
+            },
+            restart: serde_json::from_str(&self.restart_policy).map_err(|_| malformed())?,
+            restarts: u32::try_from(self.restarts).map_err(|_| malformed())?,
+            title: self.title,
+            code: self.code,
+            game_map: self.game_map,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::Ordering;
+
+    use uuid::Uuid;
+
+    use crate::{
+        models::session::{unix_now, Id, Session, SessionState, UpdateSession},
+        shared::{config::AppConfig, database::Database},
+    };
+
+    use super::{Sesser, SesserError, SqliteSesser};
+
+    async fn sesser() -> SqliteSesser {
+        let log_dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+
+        let config: AppConfig = envy::from_iter(
+            [
+                ("HOST", "127.0.0.1"),
+                ("PORT", "8001"),
+                ("PROJECT_NAME", "test"),
+                ("REPO_PATH", "test"),
+                ("AUTH_URL", "http://127.0.0.1:8000"),
+                ("SESSION_LOG_DIR", log_dir.to_str().unwrap()),
+            ]
+            .map(|(key, value)| (key.to_string(), value.to_string())),
+        )
+        .unwrap();
+
+        let database = Database::in_memory().await.unwrap();
+
+        SqliteSesser::new(&config, database).await.unwrap()
+    }
+
+    fn session(state: SessionState, code: &str) -> Session {
+        Session {
+            code: code.to_string(),
+            state,
+            ..Session::fixture()
+        }
+    }
+
+    /// Stores the session as if its game server was spawned with `pid`
+    async fn store(sesser: &SqliteSesser, session: &Session, pid: Option<u32>) {
+        sesser.insert(session).await.unwrap();
+
+        if pid.is_some() {
+            let mut spawned = session.clone();
+            spawned.pid = pid;
+            sesser.spawned(&spawned).await.unwrap();
+        }
+    }
+
+    async fn state(sesser: &SqliteSesser, id: Uuid) -> Option<SessionState> {
+        sesser
+            .get_by_id(id)
+            .await
+            .unwrap()
+            .map(|session| session.state)
+    }
+
+    fn exited_pid() -> u32 {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        child.wait().unwrap();
+
+        child.id()
+    }
+
+    #[tokio::test]
+    async fn reconcile_fails_never_spawned_sessions() {
+        let sesser = sesser().await;
+        let session = session(SessionState::Ready, "000001");
+        store(&sesser, &session, None).await;
+
+        sesser.reconcile().await.unwrap();
+
+        assert_eq!(state(&sesser, session.id).await, Some(SessionState::Failed));
+    }
+
+    #[tokio::test]
+    async fn reconcile_ends_sessions_whose_game_server_is_gone() {
+        let sesser = sesser().await;
+        let mut finished = sesser.finished().take().unwrap();
+
+        let session = session(SessionState::InProgress, "000001");
+        store(&sesser, &session, Some(exited_pid())).await;
+
+        sesser.reconcile().await.unwrap();
+
+        assert_eq!(state(&sesser, session.id).await, Some(SessionState::Ended));
+
+        let reported = finished.try_recv().unwrap();
+        assert_eq!(reported.id, session.id);
+        assert_eq!(reported.state, SessionState::Ended);
+        assert_eq!(reported.players, session.players);
+    }
+
+    #[tokio::test]
+    async fn reconcile_adopts_running_game_servers() {
+        let sesser = sesser().await;
+        let session = session(SessionState::Ready, "000041");
+        store(&sesser, &session, Some(std::process::id())).await;
+
+        sesser.reconcile().await.unwrap();
+
+        assert_eq!(state(&sesser, session.id).await, Some(SessionState::Ready));
+        assert_eq!(sesser.inner.next_code.load(Ordering::Relaxed), 42);
+    }
+
+    #[tokio::test]
+    async fn reconcile_fails_unreadable_sessions() {
+        let sesser = sesser().await;
+        let session = session(SessionState::Ready, "000001");
+        store(&sesser, &session, Some(std::process::id())).await;
+
+        sqlx::query("UPDATE sessions SET addr = 'nowhere' WHERE id = ?;")
+            .bind(session.id.to_string())
+            .execute(sesser.inner.database.as_ref())
+            .await
+            .unwrap();
+
+        sesser.reconcile().await.unwrap();
+
+        let state: String = sqlx::query_scalar("SELECT state FROM sessions WHERE id = ?;")
+            .bind(session.id.to_string())
+            .fetch_one(sesser.inner.database.as_ref())
+            .await
+            .unwrap();
+
+        assert_eq!(state, "failed");
+    }
+
+    #[tokio::test]
+    async fn reconcile_removes_long_finished_sessions() {
+        let sesser = sesser().await;
+        let (old, recent) = (
+            session(SessionState::Ended, "000001"),
+            session(SessionState::Failed, "000002"),
+        );
+
+        for (session, ended_at) in [(&old, 0), (&recent, unix_now() as i64)] {
+            store(&sesser, session, None).await;
+
+            sqlx::query("UPDATE sessions SET ended_at = ? WHERE id = ?;")
+                .bind(ended_at)
+                .bind(session.id.to_string())
+                .execute(sesser.inner.database.as_ref())
+                .await
+                .unwrap();
+        }
+
+        sesser.reconcile().await.unwrap();
+
+        assert_eq!(state(&sesser, old.id).await, None);
+        assert_eq!(state(&sesser, recent.id).await, Some(SessionState::Failed));
+    }
+
+    #[tokio::test]
+    async fn modify_session_stores_changes() {
+        let sesser = sesser().await;
+        let session = session(SessionState::Ready, "000001");
+        store(&sesser, &session, Some(std::process::id())).await;
+
+        let player = Id("player".to_string());
+
+        let modified = sesser
+            .modify_session(session.id, |session| {
+                session.players.insert(player.clone());
+                session.restarts = 2;
+                session
+                    .transition(SessionState::InProgress)
+                    .map_err(|from| SesserError::InvalidTransition {
+                        from,
+                        to: SessionState::InProgress,
+                    })
+            })
+            .await
+            .unwrap();
+
+        let stored = sesser.get_by_id(session.id).await.unwrap().unwrap();
+
+        assert_eq!(stored.state, SessionState::InProgress);
+        assert_eq!(stored.players, modified.players);
+        assert!(stored.players.contains(&player));
+        assert_eq!(stored.restarts, 2);
+        assert_eq!(stored.pid, Some(std::process::id()));
+    }
+
+    #[tokio::test]
+    async fn failed_modify_session_stores_nothing() {
+        let sesser = sesser().await;
+        let session = session(SessionState::Ready, "000001");
+        store(&sesser, &session, None).await;
+
+        let result = sesser
+            .modify_session(session.id, |session| {
+                session.players.clear();
+                Err(SesserError::SessionIsFull)
+            })
+            .await;
+
+        assert!(matches!(result, Err(SesserError::SessionIsFull)));
+
+        let stored = sesser.get_by_id(session.id).await.unwrap().unwrap();
+        assert_eq!(stored.players, session.players);
+    }
+
+    #[tokio::test]
+    async fn modify_session_ends_sessions() {
+        let sesser = sesser().await;
+        let session = session(SessionState::Ready, "000001");
+        store(&sesser, &session, None).await;
+
+        sesser
+            .modify_session(session.id, |session| {
+                session.transition(SessionState::Ended).unwrap();
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(state(&sesser, session.id).await, Some(SessionState::Ended));
+        assert!(sesser.get_all_sessions().await.unwrap().is_empty());
+
+        // Finished sessions can't be modified anymore
+        let result = sesser.modify_session(session.id, |_| Ok(())).await;
+        assert!(matches!(result, Err(SesserError::SessionNotFound(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_joins_never_overfill_a_session() {
+        let sesser = sesser().await;
+        let mut session = session(SessionState::Ready, "000001");
+        session.max_players = 4;
+        session
+            .players
+            .extend([Id("a".to_string()), Id("b".to_string())]);
+        store(&sesser, &session, None).await;
+
+        let joins = (0..10).map(|player| {
+            let sesser = sesser.clone();
+            let id = session.id;
+
+            tokio::spawn(async move {
+                sesser
+                    .update_session(id, UpdateSession::AddPlayer(Id(format!("player{player}"))))
+                    .await
+            })
+        });
+
+        let mut joined = 0;
+
+        for join in joins.collect::<Vec<_>>() {
+            match join.await.unwrap() {
+                Ok(_) => joined += 1,
+                Err(SesserError::SessionIsFull) => {}
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        }
+
+        assert_eq!(joined, 1);
+
+        let stored = sesser.get_by_id(session.id).await.unwrap().unwrap();
+        assert_eq!(stored.players.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn modify_unknown_session_fails() {
+        let sesser = sesser().await;
+        let id = Uuid::new_v4();
+
+        let result = sesser.modify_session(id, |_| Ok(())).await;
+
+        assert!(matches!(result, Err(SesserError::SessionNotFound(found)) if found == id));
+    }
+}
@@ -0,0 +1,398 @@
+use std::{
+    collections::HashSet,
+    fs::OpenOptions,
+    io::{self, SeekFrom},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+use tracing::{debug, error, info_span, Instrument};
+use uuid::Uuid;
+
+use super::{config::AppConfig, context::Context, services::sesser::Sesser};
+
+/// How often live logs are checked against the size cap
+const ROTATE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Logs of the game servers, `<session id>.log` in `SESSION_LOG_DIR`. Once
+/// larger than `SESSION_LOG_MAX_BYTES`, or when the game server restarts, a log
+/// is moved to `<session id>.log.1`, shifting older ones up to `SESSION_LOG_FILES`.
+/// Logs of finished sessions are removed after `SESSION_LOG_RETENTION_SECS`
+#[derive(Debug, Clone)]
+pub struct SessionLogs {
+    dir: PathBuf,
+    max_bytes: u64,
+    files: u32,
+    retention: Duration,
+}
+
+impl SessionLogs {
+    pub fn new(config: &AppConfig) -> io::Result<Self> {
+        let dir = PathBuf::from(&config.session_log_dir);
+        std::fs::create_dir_all(&dir)?;
+
+        Ok(Self {
+            dir,
+            max_bytes: config.session_log_max_bytes,
+            files: config.session_log_files,
+            retention: Duration::from_secs(config.session_log_retention_secs),
+        })
+    }
+
+    /// The log the session's game server is writing
+    pub fn path(&self, id: Uuid) -> PathBuf {
+        self.dir.join(format!("{id}.log"))
+    }
+
+    /// Rotates the session's log if it outgrew the cap, `true` when it did.
+    /// The game server keeps its file open, so the log is copied aside and
+    /// truncated rather than renamed, losing what's written in between.
+    /// Blocks, see [`unblock`]
+    pub fn rotate(&self, id: Uuid) -> io::Result<bool> {
+        let path = self.path(id);
+
+        if self.max_bytes == 0 || std::fs::metadata(&path)?.len() <= self.max_bytes {
+            return Ok(false);
+        }
+
+        if let Some(rotated) = self.shift(&path) {
+            std::fs::copy(&path, rotated)?;
+        }
+
+        OpenOptions::new().write(true).open(&path)?.set_len(0)?;
+
+        Ok(true)
+    }
+
+    /// Moves the log of a game server run that exited aside, so the next run
+    /// starts a fresh log. Nothing writes it anymore, so it's renamed. Blocks
+    pub fn archive(&self, id: Uuid) -> io::Result<()> {
+        let path = self.path(id);
+
+        match self.shift(&path) {
+            Some(rotated) => std::fs::rename(&path, rotated),
+            None => std::fs::remove_file(&path),
+        }
+    }
+
+    /// Removes logs of sessions not in `live` that weren't written for the
+    /// retention time, the number of removed files. Blocks
+    pub fn expire(&self, live: &HashSet<Uuid>) -> io::Result<usize> {
+        let mut removed = 0;
+
+        for entry in std::fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+
+            // `<session id>.log` and its rotations, anything else isn't ours
+            let Some(id) = name
+                .to_str()
+                .and_then(|name| name.split_once('.'))
+                .and_then(|(id, _)| id.parse::<Uuid>().ok())
+            else {
+                continue;
+            };
+
+            if live.contains(&id) {
+                continue;
+            }
+
+            let expired = entry
+                .metadata()?
+                .modified()?
+                .elapsed()
+                .is_ok_and(|age| age > self.retention);
+
+            if expired {
+                std::fs::remove_file(entry.path())?;
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    /// Makes room for a new `<session id>.log.1` by shifting the rotated logs
+    /// up and dropping the oldest, its path unless no rotated logs are kept
+    fn shift(&self, path: &Path) -> Option<PathBuf> {
+        let rotated = |n: u32| path.with_extension(format!("log.{n}"));
+
+        if self.files == 0 {
+            return None;
+        }
+
+        let _ = std::fs::remove_file(rotated(self.files));
+
+        for n in (1..self.files).rev() {
+            let _ = std::fs::rename(rotated(n), rotated(n + 1));
+        }
+
+        Some(rotated(1))
+    }
+}
+
+/// Runs blocking file work on the blocking pool rather than a runtime thread
+pub async fn unblock<T, F>(work: F) -> io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(io::Error::other)?
+}
+
+/// Last `bytes` of the log from the first full line on, and the offset it
+/// ends at
+pub async fn tail(path: &Path, bytes: u64) -> io::Result<(String, u64)> {
+    let mut file = File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let start = len.saturating_sub(bytes);
+
+    file.seek(SeekFrom::Start(start)).await?;
+
+    let mut tail = Vec::new();
+    file.take(len - start).read_to_end(&mut tail).await?;
+
+    // The first line is cut unless the tail starts with the file
+    let tail = match tail.iter().position(|b| *b == b'\n') {
+        Some(newline) if start > 0 => &tail[newline + 1..],
+        _ => &tail[..],
+    };
+
+    Ok((String::from_utf8_lossy(tail).into_owned(), len))
+}
+
+/// What was appended to the log since `offset`, and the offset to read on
+/// from. A log shorter than `offset` was rotated and is read from its start
+pub async fn read_from(path: &Path, offset: u64) -> io::Result<(Vec<u8>, u64)> {
+    let mut file = File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let offset = if len < offset { 0 } else { offset };
+
+    file.seek(SeekFrom::Start(offset)).await?;
+
+    let mut appended = Vec::new();
+    file.take(len - offset).read_to_end(&mut appended).await?;
+
+    Ok((appended, len))
+}
+
+/// Keeps the logs of live sessions under the size cap and removes expired
+/// ones, runs for the manager's lifetime
+pub async fn rotate<S: Sesser>(context: Context<S>) {
+    let logs = context.session_logs();
+    let sesser = context.sesser();
+
+    let mut interval = tokio::time::interval(ROTATE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let span = info_span!("log_rotation");
+        let _guard = span.enter();
+
+        let sessions = match sesser.get_all_sessions().in_current_span().await {
+            Ok(sessions) => sessions,
+            Err(err) => {
+                error!(event = "Couldn't list sessions", error = %err);
+                continue;
+            }
+        };
+
+        let ids: HashSet<Uuid> = sessions.iter().map(|session| session.id).collect();
+
+        let work = {
+            let logs = logs.clone();
+
+            move || {
+                let expired = logs.expire(&ids);
+                let rotated: Vec<_> = ids.iter().map(|id| (*id, logs.rotate(*id))).collect();
+
+                Ok((expired, rotated))
+            }
+        };
+
+        let (expired, rotated) = match unblock(work).in_current_span().await {
+            Ok(done) => done,
+            Err(err) => {
+                error!(event = "Couldn't rotate game server logs", error = %err);
+                continue;
+            }
+        };
+
+        match expired {
+            Ok(0) => {}
+            Ok(removed) => debug!(
+                event = "Removed expired game server logs",
+                removed = removed
+            ),
+            Err(err) => error!(event = "Couldn't remove expired game server logs", error = %err),
+        }
+
+        for (id, result) in rotated {
+            match result {
+                Ok(true) => debug!(event = "Rotated game server log", session_id = %id),
+                Ok(false) => {}
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    error!(
+                        event = "Couldn't rotate game server log",
+                        session_id = %id,
+                        error = %err,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, path::PathBuf, time::Duration};
+
+    use uuid::Uuid;
+
+    use super::{read_from, tail, SessionLogs};
+
+    fn logs(max_bytes: u64, files: u32) -> SessionLogs {
+        let dir = std::env::temp_dir().join(Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&dir).unwrap();
+
+        SessionLogs {
+            dir,
+            max_bytes,
+            files,
+            retention: Duration::ZERO,
+        }
+    }
+
+    fn rotated(logs: &SessionLogs, id: Uuid, n: u32) -> PathBuf {
+        logs.path(id).with_extension(format!("log.{n}"))
+    }
+
+    #[tokio::test]
+    async fn tail_starts_at_full_line() {
+        let logs = logs(0, 0);
+        let path = logs.path(Uuid::new_v4());
+        std::fs::write(&path, "first line\nsecond\nthird\n").unwrap();
+
+        assert_eq!(tail(&path, 10).await.unwrap(), ("third\n".to_string(), 24));
+        assert_eq!(
+            tail(&path, 100).await.unwrap(),
+            ("first line\nsecond\nthird\n".to_string(), 24)
+        );
+
+        std::fs::remove_dir_all(&logs.dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn read_from_restarts_after_truncation() {
+        let logs = logs(0, 0);
+        let path = logs.path(Uuid::new_v4());
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+
+        assert_eq!(read_from(&path, 4).await.unwrap(), (b"two\n".to_vec(), 8));
+        assert_eq!(read_from(&path, 8).await.unwrap(), (Vec::new(), 8));
+
+        std::fs::write(&path, "new\n").unwrap();
+        assert_eq!(read_from(&path, 8).await.unwrap(), (b"new\n".to_vec(), 4));
+
+        std::fs::remove_dir_all(&logs.dir).unwrap();
+    }
+
+    #[test]
+    fn rotate_keeps_files_up_to_the_limit() {
+        let logs = logs(4, 2);
+        let id = Uuid::new_v4();
+        let path = logs.path(id);
+
+        std::fs::write(&path, "1234").unwrap();
+        assert!(!logs.rotate(id).unwrap());
+
+        for content in ["first", "second", "third"] {
+            std::fs::write(&path, content).unwrap();
+            assert!(logs.rotate(id).unwrap());
+        }
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "");
+        assert_eq!(
+            std::fs::read_to_string(rotated(&logs, id, 1)).unwrap(),
+            "third"
+        );
+        assert_eq!(
+            std::fs::read_to_string(rotated(&logs, id, 2)).unwrap(),
+            "second"
+        );
+        assert!(!rotated(&logs, id, 3).exists());
+
+        std::fs::remove_dir_all(&logs.dir).unwrap();
+    }
+
+    #[test]
+    fn archive_counts_against_rotated_files() {
+        let logs = logs(4, 2);
+        let id = Uuid::new_v4();
+        let path = logs.path(id);
+
+        std::fs::write(&path, "rotated").unwrap();
+        assert!(logs.rotate(id).unwrap());
+
+        std::fs::write(&path, "first run").unwrap();
+        logs.archive(id).unwrap();
+
+        assert!(!path.exists());
+        assert_eq!(
+            std::fs::read_to_string(rotated(&logs, id, 1)).unwrap(),
+            "first run"
+        );
+        assert_eq!(
+            std::fs::read_to_string(rotated(&logs, id, 2)).unwrap(),
+            "rotated"
+        );
+
+        std::fs::write(&path, "second run").unwrap();
+        logs.archive(id).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(rotated(&logs, id, 1)).unwrap(),
+            "second run"
+        );
+        assert_eq!(
+            std::fs::read_to_string(rotated(&logs, id, 2)).unwrap(),
+            "first run"
+        );
+        assert!(!rotated(&logs, id, 3).exists());
+
+        std::fs::remove_dir_all(&logs.dir).unwrap();
+    }
+
+    #[test]
+    fn expire_removes_only_finished_sessions_logs() {
+        let logs = logs(0, 1);
+        let (live, finished) = (Uuid::new_v4(), Uuid::new_v4());
+
+        for id in [live, finished] {
+            std::fs::write(logs.path(id), "log").unwrap();
+            std::fs::write(rotated(&logs, id, 1), "rotated").unwrap();
+        }
+
+        let other = logs.dir.join("notes.txt");
+        std::fs::write(&other, "not a log").unwrap();
+
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert_eq!(logs.expire(&HashSet::from([live])).unwrap(), 2);
+        assert!(logs.path(live).exists());
+        assert!(rotated(&logs, live, 1).exists());
+        assert!(!logs.path(finished).exists());
+        assert!(!rotated(&logs, finished, 1).exists());
+        assert!(other.exists());
+
+        std::fs::remove_dir_all(&logs.dir).unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use tracing::{error, info, info_span, Instrument};
+use uuid::Uuid;
+
+use crate::models::session::{unix_now, Session, SessionState};
+
+use super::{context::Context, services::sesser::Sesser};
+
+/// Terminates sessions that stayed empty or lived past their policy, runs for
+/// the manager's lifetime. How long a session has been empty is only known
+/// since the manager started
+pub async fn enforce<S: Sesser>(context: Context<S>, interval: Duration) {
+    let sesser = context.sesser();
+
+    let mut empty_since: HashMap<Uuid, Instant> = HashMap::new();
+    let mut interval = tokio::time::interval(interval);
+
+    loop {
+        interval.tick().await;
+
+        let span = info_span!("session_policies");
+        let _guard = span.enter();
+
+        let sessions = match sesser.get_all_sessions().in_current_span().await {
+            Ok(sessions) => sessions,
+            Err(err) => {
+                error!(event = "Couldn't list sessions", error = %err);
+                continue;
+            }
+        };
+
+        track_empty(&mut empty_since, &sessions);
+
+        for session in sessions {
+            let Some(reason) = violation(&session, empty_since.get(&session.id)) else {
+                continue;
+            };
+
+            info!(
+                event = "Terminating session by its policy",
+                session_id = %session.id,
+                reason,
+            );
+
+            let result = sesser
+                .terminate_session(session.id, context.terminate_grace())
+                .in_current_span()
+                .await;
+
+            if let Err(err) = result {
+                error!(
+                    event = "Couldn't terminate session",
+                    session_id = %session.id,
+                    error = %err,
+                );
+            }
+        }
+    }
+}
+
+/// Keeps when each live session became empty, forgetting the others
+fn track_empty(empty_since: &mut HashMap<Uuid, Instant>, sessions: &[Session]) {
+    empty_since.retain(|id, _| {
+        sessions
+            .iter()
+            .any(|session| session.id == *id && is_empty(session))
+    });
+
+    for session in sessions.iter().filter(|session| is_empty(session)) {
+        empty_since.entry(session.id).or_insert_with(Instant::now);
+    }
+}
+
+/// Nobody is playing. The player set the join and leave requests maintain
+/// holds the creator from the start, so a creator whose game client never
+/// connected doesn't count
+fn is_empty(session: &Session) -> bool {
+    match session.players.len() {
+        0 => true,
+        1 => !session.creator_connected && session.players.contains(&session.owner),
+        _ => false,
+    }
+}
+
+/// Which limit of its policy the session is over, if any
+fn violation(session: &Session, empty_since: Option<&Instant>) -> Option<&'static str> {
+    // Starting sessions aren't up yet and draining ones are already going away
+    if !matches!(
+        session.state,
+        SessionState::Ready | SessionState::InProgress
+    ) {
+        return None;
+    }
+
+    let policy = session.policy;
+    let age = unix_now().saturating_sub(session.created_at);
+
+    if policy.max_lifetime_secs > 0 && age >= policy.max_lifetime_secs {
+        return Some("max lifetime");
+    }
+
+    let idle = empty_since
+        .is_some_and(|since| since.elapsed() >= Duration::from_secs(policy.idle_timeout_secs));
+
+    if policy.idle_timeout_secs > 0 && idle && age >= policy.creator_grace_secs {
+        return Some("idle");
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        time::{Duration, Instant},
+    };
+
+    use uuid::Uuid;
+
+    use crate::models::session::{unix_now, Id, Session, SessionPolicy, SessionState};
+
+    use super::{track_empty, violation};
+
+    fn session(state: SessionState, age_secs: u64, policy: SessionPolicy) -> Session {
+        let created_at = unix_now() - age_secs;
+
+        Session {
+            players: HashSet::new(),
+            state,
+            created_at,
+            state_changed_at: created_at,
+            policy,
+            ..Session::fixture()
+        }
+    }
+
+    fn ago(secs: u64) -> Instant {
+        Instant::now() - Duration::from_secs(secs)
+    }
+
+    const POLICY: SessionPolicy = SessionPolicy {
+        idle_timeout_secs: 30,
+        max_lifetime_secs: 3600,
+        creator_grace_secs: 120,
+    };
+
+    #[test]
+    fn max_lifetime_applies_to_busy_sessions() {
+        let old = session(SessionState::InProgress, 3600, POLICY);
+        let young = session(SessionState::InProgress, 3599, POLICY);
+
+        assert_eq!(violation(&old, None), Some("max lifetime"));
+        assert_eq!(violation(&young, None), None);
+    }
+
+    #[test]
+    fn idle_after_timeout_and_creator_grace() {
+        let ready = session(SessionState::Ready, 600, POLICY);
+        assert_eq!(violation(&ready, Some(&ago(30))), Some("idle"));
+        assert_eq!(violation(&ready, Some(&ago(29))), None);
+        assert_eq!(violation(&ready, None), None);
+
+        let fresh = session(SessionState::Ready, 60, POLICY);
+        assert_eq!(violation(&fresh, Some(&ago(30))), None);
+    }
+
+    #[test]
+    fn creator_who_never_connected_counts_as_idle() {
+        let waiting = Session {
+            players: HashSet::from([Id("owner".to_string())]),
+            ..session(SessionState::Ready, 600, POLICY)
+        };
+        let playing = Session {
+            id: Uuid::new_v4(),
+            creator_connected: true,
+            ..waiting.clone()
+        };
+
+        let mut empty_since = HashMap::new();
+        track_empty(&mut empty_since, &[waiting.clone(), playing.clone()]);
+
+        assert!(empty_since.contains_key(&waiting.id));
+        assert!(!empty_since.contains_key(&playing.id));
+
+        // As if the creator stayed away for the idle timeout
+        empty_since.insert(waiting.id, ago(30));
+        assert_eq!(
+            violation(&waiting, empty_since.get(&waiting.id)),
+            Some("idle")
+        );
+    }
+
+    #[test]
+    fn zero_turns_limits_off() {
+        let policy = SessionPolicy::default();
+        let ready = session(SessionState::Ready, 100_000, policy);
+
+        assert_eq!(violation(&ready, Some(&ago(60))), None);
+    }
+
+    #[test]
+    fn only_running_sessions_are_checked() {
+        for state in [
+            SessionState::Allocating,
+            SessionState::Starting,
+            SessionState::Draining,
+            SessionState::Ended,
+            SessionState::Failed,
+        ] {
+            let session = session(state, 7200, POLICY);
+
+            assert_eq!(violation(&session, Some(&ago(60))), None, "{state}");
+        }
+    }
+}
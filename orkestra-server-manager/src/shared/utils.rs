@@ -71,3 +71,42 @@ pub fn unauthorized_json(value: serde_json::Value) -> (StatusCode, Json<serde_js
 pub fn just_unauthorized() -> (StatusCode, Json<serde_json::Value>) {
     (StatusCode::UNAUTHORIZED, Json(serde_json::json!({})))
 }
+
+pub fn forbidden_json(value: serde_json::Value) -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::FORBIDDEN, Json(value))
+}
+
+pub fn just_forbidden() -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::FORBIDDEN, Json(serde_json::json!({})))
+}
+
+pub fn conflict_json(value: serde_json::Value) -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::CONFLICT, Json(value))
+}
+
+pub fn not_found_json(value: serde_json::Value) -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::NOT_FOUND, Json(value))
+}
+
+pub fn just_not_found() -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::NOT_FOUND, Json(serde_json::json!({})))
+}
+
+pub fn upgrade_required_json(value: serde_json::Value) -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::UPGRADE_REQUIRED, Json(value))
+}
+
+pub fn internal_error_json(value: serde_json::Value) -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::INTERNAL_SERVER_ERROR, Json(value))
+}
+
+pub fn service_unavailable_json(value: serde_json::Value) -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::SERVICE_UNAVAILABLE, Json(value))
+}
+
+pub fn just_internal_error() -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(serde_json::json!({})),
+    )
+}